@@ -19,6 +19,11 @@ fn main() {
         x11_platform: { all(feature = "x11", free_unix, not(redox)) },
         wayland_platform: { all(feature = "wayland", free_unix, not(redox)) },
         orbital_platform: { redox },
+
+        // Backends without a display server.
+        //
+        // Only on the platforms where the event loop can be pumped, the others own the thread.
+        headless_platform: { all(feature = "headless", any(windows_platform, macos_platform, x11_platform, wayland_platform)) },
     }
 
     // Winit defined cfgs.
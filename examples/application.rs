@@ -236,6 +236,7 @@ impl Application {
             },
             Action::ToggleMaximize => window.toggle_maximize(),
             Action::ToggleImeInput => window.toggle_ime(),
+            Action::ToggleShortcutsInhibited => window.toggle_shortcuts_inhibited(),
             Action::Minimize => window.minimize(),
             Action::NextCursor => window.next_cursor(),
             Action::NextCustomCursor => {
@@ -442,6 +443,9 @@ impl ApplicationHandler for Application {
             WindowEvent::Occluded(occluded) => {
                 window.set_occluded(occluded);
             },
+            WindowEvent::ShortcutsInhibited(inhibited) => {
+                info!("Window={window_id:?} shortcuts inhibited: {inhibited}");
+            },
             WindowEvent::CloseRequested => {
                 info!("Closing Window={window_id:?}");
                 self.windows.remove(&window_id);
@@ -504,12 +508,18 @@ impl ApplicationHandler for Application {
             },
             WindowEvent::Ime(event) => match event {
                 Ime::Enabled => info!("IME enabled for Window={window_id:?}"),
-                Ime::Preedit(text, caret_pos) => {
-                    info!("Preedit: {}, with caret at {:?}", text, caret_pos);
+                Ime::Preedit(text, caret_pos, segments) => {
+                    info!(
+                        "Preedit: {}, with caret at {:?} and segments {:?}",
+                        text, caret_pos, segments
+                    );
                 },
                 Ime::Commit(text) => {
                     info!("Committed: {}", text);
                 },
+                Ime::DeleteSurrounding { before, after } => {
+                    info!("Delete surrounding: {before} bytes before and {after} bytes after");
+                },
                 Ime::Disabled => info!("IME disabled for Window={window_id:?}"),
             },
             WindowEvent::PinchGesture { delta, .. } => {
@@ -545,6 +555,7 @@ impl ApplicationHandler for Application {
             | WindowEvent::DragEntered { .. }
             | WindowEvent::DragMoved { .. }
             | WindowEvent::DragDropped { .. }
+            | WindowEvent::FramePresented { .. }
             | WindowEvent::Destroyed
             | WindowEvent::Moved(_) => (),
         }
@@ -602,6 +613,8 @@ impl ApplicationHandlerExtMacOS for Application {
 struct WindowState {
     /// IME input.
     ime: bool,
+    /// Whether the keyboard shortcuts inhibition is requested.
+    shortcuts_inhibited: bool,
     /// Render surface.
     ///
     /// NOTE: This surface must be dropped before the `Window`.
@@ -676,6 +689,7 @@ impl WindowState {
             #[cfg(not(android_platform))]
             start_time: std::time::Instant::now(),
             ime,
+            shortcuts_inhibited: false,
             cursor_position: Default::default(),
             cursor_hidden: Default::default(),
             modifiers: Default::default(),
@@ -697,6 +711,14 @@ impl WindowState {
         }
     }
 
+    pub fn toggle_shortcuts_inhibited(&mut self) {
+        self.shortcuts_inhibited = !self.shortcuts_inhibited;
+        if let Err(err) = self.window.set_shortcuts_inhibited(self.shortcuts_inhibited) {
+            error!("Error changing shortcuts inhibition: {err}");
+            self.shortcuts_inhibited = false;
+        }
+    }
+
     pub fn minimize(&mut self) {
         self.window.set_minimized(true);
     }
@@ -874,7 +896,9 @@ impl WindowState {
     /// Show window menu.
     fn show_menu(&self) {
         if let Some(position) = self.cursor_position {
-            self.window.show_window_menu(position.into());
+            if let Err(err) = self.window.show_window_menu(position.into()) {
+                info!("Error showing window menu: {err}");
+            }
         }
     }
 
@@ -1023,6 +1047,7 @@ enum Action {
     CreateNewWindow,
     ToggleResizeIncrements,
     ToggleImeInput,
+    ToggleShortcutsInhibited,
     ToggleDecorations,
     ToggleResizable,
     ToggleFullscreen,
@@ -1060,6 +1085,7 @@ impl Action {
             Action::ToggleCursorVisibility => "Hide cursor",
             Action::CreateNewWindow => "Create new window",
             Action::ToggleImeInput => "Toggle IME input",
+            Action::ToggleShortcutsInhibited => "Toggle system keyboard shortcuts inhibition",
             Action::ToggleDecorations => "Toggle decorations",
             Action::ToggleResizable => "Toggle window resizable state",
             Action::ToggleFullscreen => "Toggle fullscreen",
@@ -1280,6 +1306,7 @@ const KEY_BINDINGS: &[Binding<&'static str>] = &[
     Binding::new("F", ModifiersState::ALT, Action::ToggleSimpleFullscreen),
     Binding::new("D", ModifiersState::CONTROL, Action::ToggleDecorations),
     Binding::new("I", ModifiersState::CONTROL, Action::ToggleImeInput),
+    Binding::new("E", ModifiersState::CONTROL, Action::ToggleShortcutsInhibited),
     Binding::new("L", ModifiersState::CONTROL, Action::CycleCursorGrab),
     Binding::new("P", ModifiersState::CONTROL, Action::ToggleResizeIncrements),
     Binding::new("R", ModifiersState::CONTROL, Action::ToggleResizable),
@@ -61,8 +61,10 @@ fn main() -> std::process::ExitCode {
         let timeout = Some(Duration::ZERO);
         let status = event_loop.pump_app_events(timeout, &mut app);
 
-        if let PumpStatus::Exit(exit_code) = status {
-            break ExitCode::from(exit_code as u8);
+        match status {
+            PumpStatus::Exit(exit_code) => break ExitCode::from(exit_code as u8),
+            PumpStatus::ConnectionLost => break ExitCode::FAILURE,
+            PumpStatus::Continue => (),
         }
 
         // Sleep for 1/60 second to simulate application work
@@ -51,6 +51,8 @@ pub struct EventLoop {
 #[derive(Default, Debug, PartialEq, Eq, Hash)]
 pub struct EventLoopBuilder {
     pub(crate) platform_specific: platform_impl::PlatformSpecificEventLoopAttributes,
+    #[cfg(headless_platform)]
+    pub(crate) headless: bool,
 }
 
 static EVENT_LOOP_CREATED: AtomicBool = AtomicBool::new(false);
@@ -93,6 +95,15 @@ impl EventLoopBuilder {
     pub fn build(&mut self) -> Result<EventLoop, EventLoopError> {
         let _span = tracing::debug_span!("winit::EventLoopBuilder::build").entered();
 
+        // The headless event loops don't share anything, and can be created on any thread.
+        #[cfg(headless_platform)]
+        if self.headless {
+            return Ok(EventLoop {
+                event_loop: platform_impl::EventLoop::new_headless()?,
+                _marker: PhantomData,
+            });
+        }
+
         if EVENT_LOOP_CREATED.swap(true, Ordering::Relaxed) {
             return Err(EventLoopError::RecreationAttempt);
         }
@@ -127,7 +138,7 @@ impl EventLoop {
     /// To get the actual event loop, call [`build`][EventLoopBuilder::build] on that.
     #[inline]
     pub fn builder() -> EventLoopBuilder {
-        EventLoopBuilder { platform_specific: Default::default() }
+        EventLoopBuilder::default()
     }
 
     /// Run the application with the event loop on the calling thread.
@@ -393,6 +404,34 @@ impl winit_wayland::EventLoopBuilderExtWayland for EventLoopBuilder {
         self.platform_specific.any_thread = any_thread;
         self
     }
+
+    #[inline]
+    fn with_reconnect(&mut self, reconnect: bool) -> &mut Self {
+        self.platform_specific.wayland_reconnect = reconnect;
+        self
+    }
+}
+
+#[cfg(headless_platform)]
+impl winit_headless::EventLoopExtHeadless for EventLoop {
+    #[inline]
+    fn is_headless(&self) -> bool {
+        self.simulator().is_some()
+    }
+
+    #[inline]
+    fn simulator(&self) -> Option<winit_headless::Simulator> {
+        self.event_loop.headless_simulator()
+    }
+}
+
+#[cfg(headless_platform)]
+impl winit_headless::EventLoopBuilderExtHeadless for EventLoopBuilder {
+    #[inline]
+    fn with_headless(&mut self) -> &mut Self {
+        self.headless = true;
+        self
+    }
 }
 
 #[cfg(windows_platform)]
@@ -423,7 +462,7 @@ impl winit_win32::EventLoopBuilderExtWindows for EventLoopBuilder {
 impl winit_x11::EventLoopExtX11 for EventLoop {
     #[inline]
     fn is_x11(&self) -> bool {
-        !self.event_loop.is_wayland()
+        self.event_loop.is_x11()
     }
 }
 
@@ -192,9 +192,13 @@
 //!
 //! * `x11` (enabled by default): On Unix platforms, enables the X11 backend.
 //! * `wayland` (enabled by default): On Unix platforms, enables the Wayland backend.
+//! * `headless`: On Windows, macOS, X11 and Wayland, enables the in-memory backend for testing
+//!   applications, see
+//!   [`EventLoopBuilderExtHeadless`][platform::headless::EventLoopBuilderExtHeadless].
 //! * `rwh_06`: Implement `raw-window-handle v0.6` traits.
 //! * `serde`: Enables serialization/deserialization of certain types with [Serde](https://crates.io/crates/serde).
 //! * `mint`: Enables mint (math interoperability standard types) conversions.
+//! * `record`: Enables the [`record`] module, to record and replay the events of an application.
 //!
 //! See the [`platform`] module for documentation on platform-specific cargo
 //! features.
@@ -298,3 +302,5 @@ mod os_error;
 mod platform_impl;
 
 pub mod platform;
+#[cfg(feature = "record")]
+pub mod record;
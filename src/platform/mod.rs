@@ -6,12 +6,18 @@
 pub use winit_android as android;
 #[cfg(macos_platform)]
 pub use winit_appkit as macos;
+#[cfg(headless_platform)]
+pub use winit_headless as headless;
 #[cfg(orbital_platform)]
 pub use winit_orbital as orbital;
 #[cfg(ios_platform)]
 pub use winit_uikit as ios;
 #[cfg(any(x11_platform, wayland_platform))]
+pub mod modifiers;
+#[cfg(any(x11_platform, wayland_platform))]
 pub mod startup_notify;
+#[cfg(any(x11_platform, wayland_platform))]
+pub mod unix;
 #[cfg(wayland_platform)]
 pub use winit_wayland as wayland;
 #[cfg(web_platform)]
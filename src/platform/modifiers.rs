@@ -0,0 +1,39 @@
+//! Querying the keyboard modifiers state.
+//!
+//! [`WindowEvent::ModifiersChanged`] is only delivered to the focused window, so an application
+//! can't know about e.g. an engaged Caps Lock until one of its windows is focused and the
+//! modifiers change. [`ActiveEventLoopExtModifiers::modifiers`] allows to query the current state
+//! at any point, including the lock keys and the latched/locked (sticky) modifiers.
+//!
+//! [`WindowEvent::ModifiersChanged`]: crate::event::WindowEvent::ModifiersChanged
+
+use crate::error::NotSupportedError;
+use crate::event::Modifiers;
+use crate::event_loop::ActiveEventLoop;
+
+pub trait ActiveEventLoopExtModifiers {
+    /// The current state of the keyboard modifiers and lock keys.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** The state is only known once one of the surfaces got the keyboard focus,
+    ///   before that the modifiers are empty.
+    fn modifiers(&self) -> Result<Modifiers, NotSupportedError>;
+}
+
+impl ActiveEventLoopExtModifiers for dyn ActiveEventLoop + '_ {
+    fn modifiers(&self) -> Result<Modifiers, NotSupportedError> {
+        #[cfg(wayland_platform)]
+        if let Some(event_loop) = self.cast_ref::<crate::platform_impl::wayland::ActiveEventLoop>()
+        {
+            return Ok(event_loop.modifiers());
+        }
+
+        #[cfg(x11_platform)]
+        if let Some(event_loop) = self.cast_ref::<crate::platform_impl::x11::ActiveEventLoop>() {
+            return Ok(event_loop.modifiers());
+        }
+
+        Err(NotSupportedError::new("querying modifiers is not supported"))
+    }
+}
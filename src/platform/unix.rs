@@ -0,0 +1,283 @@
+//! Choosing the display server backend and inserting file descriptors and timers into the event
+//! loop.
+//!
+//! ## Backends
+//!
+//! By default the event loop is created with Wayland, and falls back to X11 when Wayland isn't
+//! available. The order can be changed with [`EventLoopBuilderExtUnix::with_backends`], or by the
+//! user with the `WINIT_UNIX_BACKEND` environment variable, e.g. `WINIT_UNIX_BACKEND=x11,wayland`.
+//!
+//! ## Event sources
+//!
+//! The X11 and Wayland backends are built on top of a file descriptor based event loop, so
+//! instead of running a separate thread and waking up the event loop with an
+//! [`EventLoopProxy`], an application can wait for its own file descriptors (e.g. sockets or
+//! pipes) and timers directly in the event loop. The events are delivered on the event loop
+//! thread to the [`ApplicationHandlerExtUnix`] returned by [`ApplicationHandler::unix_handler`].
+//!
+//! [`EventLoopProxy`]: crate::event_loop::EventLoopProxy
+//! [`ApplicationHandler::unix_handler`]: crate::application::ApplicationHandler::unix_handler
+
+use std::error::Error;
+use std::fmt;
+use std::os::fd::AsFd;
+use std::time::{Duration, Instant};
+
+use rwh_06::{RawDisplayHandle, RawWindowHandle};
+pub use winit_core::application::unix::{
+    ApplicationHandlerExtUnix, EventSourceId, FdInterest, FdReadiness,
+};
+
+use crate::error::{EventLoopError, NotSupportedError, RequestError};
+use crate::event_loop::{ActiveEventLoop, EventLoopBuilder};
+use crate::window::Window;
+
+/// A display server backend.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Backend {
+    /// The Wayland backend, requires the `wayland` feature.
+    Wayland,
+    /// The X11 backend, requires the `x11` feature.
+    X11,
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Wayland => f.write_str("Wayland"),
+            Self::X11 => f.write_str("X11"),
+        }
+    }
+}
+
+/// None of the backends could be used to create the event loop.
+///
+/// This is the source of the [`EventLoopError::Os`] returned when creating the event loop, use
+/// [`EventLoopErrorExtUnix::backends_error`] to get it.
+#[derive(Debug)]
+pub struct BackendsError {
+    pub(crate) rejected: Vec<(Backend, EventLoopError)>,
+}
+
+impl BackendsError {
+    /// The backends which were tried, in order, with the reason why they were rejected.
+    pub fn rejected(&self) -> &[(Backend, EventLoopError)] {
+        &self.rejected
+    }
+}
+
+impl fmt::Display for BackendsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.rejected.is_empty() {
+            return f.write_str("no backend was requested");
+        }
+
+        f.write_str("no backend is available")?;
+        for (backend, err) in &self.rejected {
+            write!(f, "; {backend}: {err}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for BackendsError {}
+
+pub trait EventLoopErrorExtUnix {
+    /// The reasons why each of the backends was rejected, when the error comes from creating the
+    /// event loop.
+    fn backends_error(&self) -> Option<&BackendsError>;
+}
+
+impl EventLoopErrorExtUnix for EventLoopError {
+    fn backends_error(&self) -> Option<&BackendsError> {
+        match self {
+            Self::Os(err) => err.source()?.downcast_ref(),
+            _ => None,
+        }
+    }
+}
+
+pub trait EventLoopBuilderExtUnix {
+    /// Try the `backends` in order when creating the event loop, falling back to the next one when
+    /// a backend isn't available or fails to connect to its display server.
+    ///
+    /// The `WINIT_UNIX_BACKEND` environment variable takes precedence over this, while forcing a
+    /// backend with e.g. [`EventLoopBuilderExtX11::with_x11`] takes precedence over both.
+    ///
+    /// The default is `[Backend::Wayland, Backend::X11]`.
+    ///
+    /// [`EventLoopBuilderExtX11::with_x11`]: crate::platform::x11::EventLoopBuilderExtX11::with_x11
+    fn with_backends(&mut self, backends: &[Backend]) -> &mut Self;
+
+    /// Create the event loop on the display connection of the host application, instead of
+    /// opening a new connection.
+    ///
+    /// The backend is chosen from the handle, a [`RawDisplayHandle::Wayland`] for Wayland and a
+    /// [`RawDisplayHandle::Xlib`] for X11, creating the event loop fails with other handles. The
+    /// backend preferences are ignored.
+    ///
+    /// - **Wayland:** Winit dispatches its own event queue, so the queues of the host aren't
+    ///   affected.
+    /// - **X11:** Winit reads the events of its windows, of the root window, and those which can't
+    ///   be attributed to a window before reading them, like the XInput 2 and XKB events. The other
+    ///   events stay in the Xlib queue, and the host must pass them to `XFilterEvent` for the input
+    ///   method of winit. Since winit reads the socket, the host must also process the events
+    ///   reported by `XPending` after each dispatch of winit, its own poll of the connection
+    ///   doesn't wake up for the events already queued. Winit installs its own Xlib error handler.
+    ///   A [`RawDisplayHandle::Xcb`] isn't supported since winit reads the events with Xlib, and
+    ///   creating the event loop fails when winit already opened an X11 connection on another
+    ///   `Display`.
+    ///
+    /// # Safety
+    ///
+    /// The display must stay valid until the event loop is dropped, and the connection isn't
+    /// closed by winit. On X11, the `Display` must have been opened after calling `XInitThreads`,
+    /// and must stay valid for the rest of the process, since winit keeps the connection after
+    /// the event loop is dropped.
+    unsafe fn with_raw_display_handle(&mut self, display: RawDisplayHandle) -> &mut Self;
+}
+
+impl EventLoopBuilderExtUnix for EventLoopBuilder {
+    #[inline]
+    fn with_backends(&mut self, backends: &[Backend]) -> &mut Self {
+        self.platform_specific.backends = Some(backends.to_vec());
+        self
+    }
+
+    #[inline]
+    unsafe fn with_raw_display_handle(&mut self, display: RawDisplayHandle) -> &mut Self {
+        self.platform_specific.display = Some(display);
+        self
+    }
+}
+
+pub trait ActiveEventLoopExtUnix {
+    /// Insert a file descriptor into the event loop.
+    ///
+    /// [`ApplicationHandlerExtUnix::fd_ready`] is emitted whenever the file descriptor is ready
+    /// for the given `interest`, until it's removed with
+    /// [`remove_event_source`][Self::remove_event_source]. The file descriptor is closed at that
+    /// point when `fd` owns it.
+    fn insert_fd<F: AsFd + 'static>(
+        &self,
+        fd: F,
+        interest: FdInterest,
+    ) -> Result<EventSourceId, RequestError>;
+
+    /// Insert a timer into the event loop.
+    ///
+    /// [`ApplicationHandlerExtUnix::timer_expired`] is emitted once `deadline` is reached, and then
+    /// every `interval` when it's set, until it's removed with
+    /// [`remove_event_source`][Self::remove_event_source].
+    fn insert_timer(
+        &self,
+        deadline: Instant,
+        interval: Option<Duration>,
+    ) -> Result<EventSourceId, RequestError>;
+
+    /// Remove a file descriptor or a timer from the event loop.
+    ///
+    /// The events it already produced but which weren't delivered yet are discarded.
+    fn remove_event_source(&self, id: EventSourceId);
+
+    /// Wrap a native window created by the host application, e.g. a plugin host, in a [`Window`].
+    ///
+    /// The input of the native window is selected, and the usual [`WindowEvent`]s are emitted for
+    /// it. The native window isn't destroyed when the returned [`Window`] is dropped, its lifetime
+    /// stays managed by the host, and the events selected on it before are selected again.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Takes a [`RawWindowHandle::Xlib`] or [`RawWindowHandle::Xcb`]. The core button
+    ///   presses are left to the host when it selected them from another connection, the buttons
+    ///   are reported through XInput 2.
+    /// - **Wayland:** Takes a [`RawWindowHandle::Wayland`]. The surface must be created on the
+    ///   display connection of the event loop, e.g. one created with
+    ///   [`EventLoopBuilderExtUnix::with_raw_display_handle`]. The size of the surface isn't known
+    ///   until set with [`Window::request_surface_size`], the scale factor is always `1.`, and the
+    ///   methods acting on the toplevel, like [`Window::set_title`], have no effect.
+    ///
+    /// # Safety
+    ///
+    /// The native window must stay valid until the returned [`Window`] is dropped.
+    ///
+    /// [`WindowEvent`]: crate::event::WindowEvent
+    unsafe fn adopt_window(&self, window: RawWindowHandle)
+        -> Result<Box<dyn Window>, RequestError>;
+}
+
+impl ActiveEventLoopExtUnix for dyn ActiveEventLoop + '_ {
+    fn insert_fd<F: AsFd + 'static>(
+        &self,
+        fd: F,
+        interest: FdInterest,
+    ) -> Result<EventSourceId, RequestError> {
+        #[cfg(wayland_platform)]
+        if let Some(event_loop) = self.cast_ref::<crate::platform_impl::wayland::ActiveEventLoop>()
+        {
+            return event_loop.insert_fd(fd, interest);
+        }
+
+        #[cfg(x11_platform)]
+        if let Some(event_loop) = self.cast_ref::<crate::platform_impl::x11::ActiveEventLoop>() {
+            return event_loop.insert_fd(fd, interest);
+        }
+
+        let _ = (fd, interest);
+        Err(NotSupportedError::new("inserting file descriptors is not supported").into())
+    }
+
+    fn insert_timer(
+        &self,
+        deadline: Instant,
+        interval: Option<Duration>,
+    ) -> Result<EventSourceId, RequestError> {
+        #[cfg(wayland_platform)]
+        if let Some(event_loop) = self.cast_ref::<crate::platform_impl::wayland::ActiveEventLoop>()
+        {
+            return event_loop.insert_timer(deadline, interval);
+        }
+
+        #[cfg(x11_platform)]
+        if let Some(event_loop) = self.cast_ref::<crate::platform_impl::x11::ActiveEventLoop>() {
+            return event_loop.insert_timer(deadline, interval);
+        }
+
+        let _ = (deadline, interval);
+        Err(NotSupportedError::new("inserting timers is not supported").into())
+    }
+
+    fn remove_event_source(&self, id: EventSourceId) {
+        #[cfg(wayland_platform)]
+        if let Some(event_loop) = self.cast_ref::<crate::platform_impl::wayland::ActiveEventLoop>()
+        {
+            return event_loop.remove_event_source(id);
+        }
+
+        #[cfg(x11_platform)]
+        if let Some(event_loop) = self.cast_ref::<crate::platform_impl::x11::ActiveEventLoop>() {
+            return event_loop.remove_event_source(id);
+        }
+
+        let _ = id;
+    }
+
+    unsafe fn adopt_window(
+        &self,
+        window: RawWindowHandle,
+    ) -> Result<Box<dyn Window>, RequestError> {
+        #[cfg(wayland_platform)]
+        if let Some(event_loop) = self.cast_ref::<crate::platform_impl::wayland::ActiveEventLoop>()
+        {
+            return unsafe { event_loop.adopt_window(window) };
+        }
+
+        #[cfg(x11_platform)]
+        if let Some(event_loop) = self.cast_ref::<crate::platform_impl::x11::ActiveEventLoop>() {
+            return event_loop.adopt_window(window);
+        }
+
+        let _ = window;
+        Err(NotSupportedError::new("adopting a window is not supported").into())
+    }
+}
@@ -0,0 +1,103 @@
+//! The event loop of the platform, or the headless one independently of the platform.
+
+#[cfg(any(x11_platform, wayland_platform))]
+use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, RawFd};
+use std::time::Duration;
+
+use winit_core::application::ApplicationHandler;
+use winit_core::error::EventLoopError;
+use winit_core::event_loop::pump_events::PumpStatus;
+use winit_core::event_loop::ActiveEventLoop;
+
+use super::platform;
+
+#[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
+pub enum EventLoop {
+    Platform(platform::EventLoop),
+    Headless(winit_headless::EventLoop),
+}
+
+impl EventLoop {
+    pub(crate) fn new(
+        attributes: &mut platform::PlatformSpecificEventLoopAttributes,
+    ) -> Result<Self, EventLoopError> {
+        platform::EventLoop::new(attributes).map(Self::Platform)
+    }
+
+    pub(crate) fn new_headless() -> Result<Self, EventLoopError> {
+        winit_headless::EventLoop::new().map(Self::Headless)
+    }
+
+    pub fn headless_simulator(&self) -> Option<winit_headless::Simulator> {
+        match self {
+            Self::Platform(_) => None,
+            Self::Headless(evlp) => Some(evlp.simulator()),
+        }
+    }
+
+    #[cfg(wayland_platform)]
+    pub fn is_wayland(&self) -> bool {
+        matches!(self, Self::Platform(evlp) if evlp.is_wayland())
+    }
+
+    #[cfg(x11_platform)]
+    pub fn is_x11(&self) -> bool {
+        matches!(self, Self::Platform(evlp) if evlp.is_x11())
+    }
+
+    pub fn run_app<A: ApplicationHandler>(self, app: A) -> Result<(), EventLoopError> {
+        match self {
+            Self::Platform(evlp) => evlp.run_app(app),
+            Self::Headless(evlp) => evlp.run_app(app),
+        }
+    }
+
+    pub fn run_app_on_demand<A: ApplicationHandler>(
+        &mut self,
+        app: A,
+    ) -> Result<(), EventLoopError> {
+        match self {
+            Self::Platform(evlp) => evlp.run_app_on_demand(app),
+            Self::Headless(evlp) => evlp.run_app_on_demand(app),
+        }
+    }
+
+    pub fn pump_app_events<A: ApplicationHandler>(
+        &mut self,
+        timeout: Option<Duration>,
+        app: A,
+    ) -> PumpStatus {
+        match self {
+            Self::Platform(evlp) => evlp.pump_app_events(timeout, app),
+            Self::Headless(evlp) => evlp.pump_app_events(timeout, app),
+        }
+    }
+
+    pub fn window_target(&self) -> &dyn ActiveEventLoop {
+        match self {
+            Self::Platform(evlp) => evlp.window_target(),
+            Self::Headless(evlp) => evlp.window_target(),
+        }
+    }
+}
+
+#[cfg(any(x11_platform, wayland_platform))]
+impl AsFd for EventLoop {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        match self {
+            Self::Platform(evlp) => evlp.as_fd(),
+            Self::Headless(evlp) => evlp.as_fd(),
+        }
+    }
+}
+
+#[cfg(any(x11_platform, wayland_platform))]
+impl AsRawFd for EventLoop {
+    fn as_raw_fd(&self) -> RawFd {
+        match self {
+            Self::Platform(evlp) => evlp.as_raw_fd(),
+            Self::Headless(evlp) => evlp.as_raw_fd(),
+        }
+    }
+}
@@ -7,6 +7,8 @@ use std::env;
 use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, RawFd};
 use std::time::Duration;
 
+use rwh_06::RawDisplayHandle;
+use tracing::warn;
 pub(crate) use winit_common::xkb::{physicalkey_to_scancode, scancode_to_physicalkey};
 use winit_core::application::ApplicationHandler;
 use winit_core::error::{EventLoopError, NotSupportedError};
@@ -17,6 +19,8 @@ pub(crate) use winit_wayland as wayland;
 #[cfg(x11_platform)]
 pub(crate) use winit_x11 as x11;
 
+use crate::platform::unix::{Backend as UnixBackend, BackendsError};
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub(crate) enum Backend {
     #[cfg(x11_platform)]
@@ -25,9 +29,12 @@ pub(crate) enum Backend {
     Wayland,
 }
 
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
 pub(crate) struct PlatformSpecificEventLoopAttributes {
     pub(crate) forced_backend: Option<Backend>,
+    pub(crate) backends: Option<Vec<UnixBackend>>,
+    pub(crate) display: Option<RawDisplayHandle>,
+    pub(crate) wayland_reconnect: bool,
     pub(crate) any_thread: bool,
 }
 
@@ -82,53 +89,100 @@ impl EventLoop {
             );
         }
 
-        // NOTE: Wayland first because of X11 could be present under Wayland as well. Empty
-        // variables are also treated as not set.
-        let backend = match (
-            attributes.forced_backend,
-            env::var("WAYLAND_DISPLAY")
-                .ok()
-                .filter(|var| !var.is_empty())
-                .or_else(|| env::var("WAYLAND_SOCKET").ok())
-                .filter(|var| !var.is_empty())
-                .is_some(),
-            env::var("DISPLAY").map(|var| !var.is_empty()).unwrap_or(false),
-        ) {
-            // User is forcing a backend.
-            (Some(backend), ..) => backend,
-            // Wayland is present.
+        // User is providing the display connection.
+        if let Some(display) = attributes.display {
+            // SAFETY: Guaranteed by the caller of `with_raw_display_handle`.
+            return unsafe { EventLoop::from_display(display) };
+        }
+
+        // User is forcing a backend.
+        match attributes.forced_backend {
             #[cfg(wayland_platform)]
-            (None, true, _) => Backend::Wayland,
-            // X11 is present.
+            Some(Backend::Wayland) => return EventLoop::new_wayland_any_thread(attributes),
             #[cfg(x11_platform)]
-            (None, _, true) => Backend::X,
-            // No backend is present.
-            (_, wayland_display, x11_display) => {
-                let msg = if wayland_display && !cfg!(wayland_platform) {
-                    "DISPLAY is not set; note: enable the `winit/wayland` feature to support \
-                     Wayland"
-                } else if x11_display && !cfg!(x11_platform) {
-                    "neither WAYLAND_DISPLAY nor WAYLAND_SOCKET is set; note: enable the \
-                     `winit/x11` feature to support X11"
-                } else {
-                    "neither WAYLAND_DISPLAY nor WAYLAND_SOCKET nor DISPLAY is set."
-                };
-                return Err(NotSupportedError::new(msg).into());
-            },
+            Some(Backend::X) => return EventLoop::new_x11_any_thread(),
+            None => (),
+        }
+
+        // NOTE: Wayland first because of X11 could be present under Wayland as well.
+        let backends = match env::var("WINIT_UNIX_BACKEND") {
+            Ok(var) if !var.trim().is_empty() => parse_backends(&var),
+            _ => attributes
+                .backends
+                .clone()
+                .unwrap_or_else(|| vec![UnixBackend::Wayland, UnixBackend::X11]),
         };
 
-        // Create the display based on the backend.
+        // Fall back to the next backend when one can't be used.
+        let mut rejected = Vec::new();
+        for backend in backends {
+            match EventLoop::new_backend(backend, attributes) {
+                Ok(event_loop) => return Ok(event_loop),
+                Err(err) => rejected.push((backend, err)),
+            }
+        }
+
+        Err(os_error!(BackendsError { rejected }).into())
+    }
+
+    unsafe fn from_display(display: RawDisplayHandle) -> Result<EventLoop, EventLoopError> {
+        match display {
+            #[cfg(wayland_platform)]
+            RawDisplayHandle::Wayland(handle) => unsafe {
+                wayland::EventLoop::from_display(handle.display)
+                    .map(|evlp| EventLoop::Wayland(Box::new(evlp)))
+            },
+            #[cfg(x11_platform)]
+            RawDisplayHandle::Xlib(rwh_06::XlibDisplayHandle {
+                display: Some(display), ..
+            }) => unsafe { x11::EventLoop::from_display(display).map(EventLoop::X) },
+            // The X11 backend reads the events with Xlib, which can't use an XCB connection.
+            #[cfg(x11_platform)]
+            RawDisplayHandle::Xcb(_) => Err(NotSupportedError::new(
+                "the X11 backend needs the Xlib `Display` of the XCB connection",
+            )
+            .into()),
+            _ => Err(NotSupportedError::new("unsupported display handle").into()),
+        }
+    }
+
+    #[cfg_attr(not(wayland_platform), allow(unused_variables))]
+    fn new_backend(
+        backend: UnixBackend,
+        attributes: &PlatformSpecificEventLoopAttributes,
+    ) -> Result<EventLoop, EventLoopError> {
+        // Empty variables are treated as not set.
+        let is_set = |name| env::var(name).is_ok_and(|var| !var.is_empty());
+
         match backend {
             #[cfg(wayland_platform)]
-            Backend::Wayland => EventLoop::new_wayland_any_thread(),
+            UnixBackend::Wayland if is_set("WAYLAND_DISPLAY") || is_set("WAYLAND_SOCKET") => {
+                EventLoop::new_wayland_any_thread(attributes)
+            },
             #[cfg(x11_platform)]
-            Backend::X => EventLoop::new_x11_any_thread(),
+            UnixBackend::X11 if is_set("DISPLAY") => EventLoop::new_x11_any_thread(),
+            UnixBackend::Wayland if !cfg!(wayland_platform) => {
+                Err(NotSupportedError::new("enable the `winit/wayland` feature to support Wayland")
+                    .into())
+            },
+            UnixBackend::X11 if !cfg!(x11_platform) => {
+                Err(NotSupportedError::new("enable the `winit/x11` feature to support X11").into())
+            },
+            UnixBackend::Wayland => {
+                Err(NotSupportedError::new("neither WAYLAND_DISPLAY nor WAYLAND_SOCKET is set")
+                    .into())
+            },
+            UnixBackend::X11 => Err(NotSupportedError::new("DISPLAY is not set").into()),
         }
     }
 
     #[cfg(wayland_platform)]
-    fn new_wayland_any_thread() -> Result<EventLoop, EventLoopError> {
-        wayland::EventLoop::new().map(|evlp| EventLoop::Wayland(Box::new(evlp)))
+    fn new_wayland_any_thread(
+        attributes: &PlatformSpecificEventLoopAttributes,
+    ) -> Result<EventLoop, EventLoopError> {
+        let mut evlp = wayland::EventLoop::new()?;
+        evlp.set_reconnect(attributes.wayland_reconnect);
+        Ok(EventLoop::Wayland(Box::new(evlp)))
     }
 
     #[cfg(x11_platform)]
@@ -147,6 +201,17 @@ impl EventLoop {
         }
     }
 
+    #[inline]
+    #[allow(dead_code)]
+    pub fn is_x11(&self) -> bool {
+        match *self {
+            #[cfg(x11_platform)]
+            EventLoop::X(_) => true,
+            #[cfg(wayland_platform)]
+            _ => false,
+        }
+    }
+
     pub fn run_app<A: ApplicationHandler>(self, app: A) -> Result<(), EventLoopError> {
         x11_or_wayland!(match self; EventLoop(evlp) => evlp.run_app(app))
     }
@@ -183,6 +248,22 @@ impl AsRawFd for EventLoop {
     }
 }
 
+/// Parse the comma-separated backends of `WINIT_UNIX_BACKEND`.
+fn parse_backends(var: &str) -> Vec<UnixBackend> {
+    var.split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .filter_map(|name| match name.to_ascii_lowercase().as_str() {
+            "wayland" => Some(UnixBackend::Wayland),
+            "x11" => Some(UnixBackend::X11),
+            _ => {
+                warn!("unknown backend `{name}` in WINIT_UNIX_BACKEND");
+                None
+            },
+        })
+        .collect()
+}
+
 #[cfg(target_os = "linux")]
 fn is_main_thread() -> bool {
     rustix::thread::gettid() == rustix::process::getpid()
@@ -20,6 +20,12 @@ pub use self::platform::*;
 #[cfg(web_platform)]
 use self::web as platform;
 
+// The headless event loop is selected at runtime, in place of the event loop of the platform.
+#[cfg(headless_platform)]
+mod headless;
+#[cfg(headless_platform)]
+pub(crate) use self::headless::EventLoop;
+
 #[cfg(all(
     not(ios_platform),
     not(windows_platform),
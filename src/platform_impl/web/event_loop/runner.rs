@@ -10,11 +10,12 @@ use wasm_bindgen::prelude::Closure;
 use wasm_bindgen::JsCast;
 use web_sys::{Document, KeyboardEvent, Navigator, PageTransitionEvent, PointerEvent, WheelEvent};
 use web_time::{Duration, Instant};
+use winit_common::timers::Timers;
 use winit_core::application::ApplicationHandler;
 use winit_core::event::{
     DeviceEvent, DeviceId, ElementState, RawKeyEvent, StartCause, WindowEvent,
 };
-use winit_core::event_loop::{ControlFlow, DeviceEvents};
+use winit_core::event_loop::{ControlFlow, DeviceEvents, TimerId};
 use winit_core::window::WindowId;
 
 use super::super::event;
@@ -44,6 +45,7 @@ struct Execution {
     main_thread: MainThreadMarker,
     event_loop_proxy: Arc<EventLoopProxy>,
     control_flow: Cell<ControlFlow>,
+    timers: Timers,
     poll_strategy: Cell<PollStrategy>,
     wait_until_strategy: Cell<WaitUntilStrategy>,
     exit: Cell<bool>,
@@ -157,6 +159,7 @@ impl Runner {
                 self.app.device_event(&self.event_loop, device_id, event)
             },
             Event::UserWakeUp => self.app.proxy_wake_up(&self.event_loop),
+            Event::Timers => runner.0.timers.dispatch(self.app.as_mut(), &self.event_loop),
             Event::Suspended => self.app.suspended(&self.event_loop),
             Event::Resumed => self.app.resumed(&self.event_loop),
             Event::CreateSurfaces => self.app.can_create_surfaces(&self.event_loop),
@@ -191,6 +194,7 @@ impl Shared {
                 main_thread,
                 event_loop_proxy: Arc::new(proxy_spawner),
                 control_flow: Cell::new(ControlFlow::default()),
+                timers: Timers::default(),
                 poll_strategy: Cell::new(PollStrategy::default()),
                 wait_until_strategy: Cell::new(WaitUntilStrategy::default()),
                 exit: Cell::new(false),
@@ -500,10 +504,20 @@ impl Shared {
 
     // Run the logic for waking from a WaitUntil, which involves clearing the queue
     // Generally there shouldn't be events built up when this is called
-    pub fn resume_time_reached(&self, start: Instant, requested_resume: Instant) {
-        let start_cause =
-            Event::NewEvents(StartCause::ResumeTimeReached { start, requested_resume });
-        self.run_until_cleared(iter::once(start_cause));
+    pub fn resume_time_reached(&self, start: Instant) {
+        // The wake up could be for a timer instead of the requested resume time.
+        let start_cause = match self.control_flow() {
+            ControlFlow::WaitUntil(requested_resume) if requested_resume <= Instant::now() => {
+                StartCause::ResumeTimeReached { start, requested_resume }
+            },
+            ControlFlow::WaitUntil(requested_resume) => {
+                StartCause::WaitCancelled { start, requested_resume: Some(requested_resume) }
+            },
+            ControlFlow::Poll | ControlFlow::Wait => {
+                StartCause::WaitCancelled { start, requested_resume: None }
+            },
+        };
+        self.run_until_cleared(iter::once(Event::NewEvents(start_cause)));
     }
 
     // Add an event to the event loop runner, from the user or an event handler
@@ -618,6 +632,7 @@ impl Shared {
         for event in events {
             self.handle_event(event);
         }
+        self.handle_event(Event::Timers);
         self.process_destroy_pending_windows();
 
         // Collect all of the redraw events to avoid double-locking the RefCell
@@ -705,7 +720,8 @@ impl Shared {
         let new_state = if self.exiting() {
             State::Exit
         } else {
-            match self.control_flow() {
+            // Also wake up for the earliest timer.
+            match self.0.timers.control_flow(self.control_flow()) {
                 ControlFlow::Poll => {
                     let cloned = self.clone();
                     State::Poll {
@@ -730,7 +746,7 @@ impl Shared {
                         _timeout: backend::Schedule::new_with_duration(
                             self.wait_until_strategy(),
                             self.window(),
-                            move || cloned.resume_time_reached(start, end),
+                            move || cloned.resume_time_reached(start),
                             delay,
                         ),
                     }
@@ -825,6 +841,14 @@ impl Shared {
         self.0.control_flow.set(control_flow)
     }
 
+    pub(crate) fn schedule_timer(&self, deadline: Instant, timer_id: TimerId) {
+        self.0.timers.schedule(deadline, timer_id)
+    }
+
+    pub(crate) fn cancel_timer(&self, timer_id: TimerId) {
+        self.0.timers.cancel(timer_id)
+    }
+
     pub(crate) fn exit(&self) {
         self.0.exit.set(true)
     }
@@ -886,4 +910,5 @@ pub(crate) enum Event {
     Resumed,
     AboutToWait,
     UserWakeUp,
+    Timers,
 }
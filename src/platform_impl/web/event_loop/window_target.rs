@@ -1,17 +1,20 @@
 use std::cell::Cell;
 use std::clone::Clone;
+use std::future::Future;
 use std::iter;
+use std::pin::Pin;
 use std::rc::Rc;
 use std::sync::Arc;
 
 use web_sys::Element;
+use web_time::Instant;
 use winit_core::application::ApplicationHandler;
 use winit_core::cursor::{CustomCursor as CoreCustomCursor, CustomCursorSource};
 use winit_core::error::{NotSupportedError, RequestError};
-use winit_core::event::{ElementState, KeyEvent, TouchPhase, WindowEvent};
+use winit_core::event::{ElementState, KeyEvent, ScrollSource, TouchPhase, WindowEvent};
 use winit_core::event_loop::{
     ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEvents,
-    EventLoopProxy as RootEventLoopProxy, OwnedDisplayHandle as CoreOwnedDisplayHandle,
+    EventLoopProxy as RootEventLoopProxy, OwnedDisplayHandle as CoreOwnedDisplayHandle, TimerId,
 };
 use winit_core::keyboard::ModifiersState;
 use winit_core::monitor::MonitorHandle as CoremMonitorHandle;
@@ -376,6 +379,8 @@ impl ActiveEventLoop {
                         device_id: None,
                         delta,
                         phase: TouchPhase::Moved,
+                        source: ScrollSource::Unknown,
+                        value120: None,
                     },
                 },
             )));
@@ -539,6 +544,18 @@ impl RootActiveEventLoop for ActiveEventLoop {
         self.runner.control_flow()
     }
 
+    fn schedule_timer(&self, deadline: Instant, timer_id: TimerId) {
+        self.runner.schedule_timer(deadline, timer_id)
+    }
+
+    fn cancel_timer(&self, timer_id: TimerId) {
+        self.runner.cancel_timer(timer_id)
+    }
+
+    fn spawn_local(&self, future: Pin<Box<dyn Future<Output = ()>>>) {
+        wasm_bindgen_futures::spawn_local(future)
+    }
+
     fn exit(&self) {
         self.runner.exit()
     }
@@ -12,8 +12,8 @@ use winit_core::error::{NotSupportedError, RequestError};
 use winit_core::icon::Icon;
 use winit_core::monitor::{Fullscreen, MonitorHandle as CoremMonitorHandle};
 use winit_core::window::{
-    CursorGrabMode, ImePurpose, ResizeDirection, Theme, UserAttentionType, Window as RootWindow,
-    WindowAttributes, WindowButtons, WindowId, WindowLevel,
+    CursorGrabMode, ImePurpose, PresentationHint, ResizeDirection, Theme, UserAttentionType,
+    Window as RootWindow, WindowAttributes, WindowButtons, WindowId, WindowLevel,
 };
 
 use super::main_thread::MainThreadMarker;
@@ -116,6 +116,14 @@ impl RootWindow for Window {
 
     fn pre_present_notify(&self) {}
 
+    fn set_frame_presented_events(&self, _enabled: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_frame_presented_events is not supported").into())
+    }
+
+    fn set_presentation_hint(&self, _hint: PresentationHint) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_presentation_hint is not supported").into())
+    }
+
     fn reset_dead_keys(&self) {
         // Not supported
     }
@@ -303,6 +311,18 @@ impl RootWindow for Window {
         // Intentionally a no-op, no window ordering
     }
 
+    fn set_workspace(&self, _workspace: u32) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_workspace is not supported").into())
+    }
+
+    fn workspace(&self) -> Option<u32> {
+        None
+    }
+
+    fn set_sticky(&self, _sticky: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_sticky is not supported").into())
+    }
+
     fn set_window_icon(&self, _: Option<Icon>) {
         // Currently an intentional no-op
     }
@@ -319,6 +339,10 @@ impl RootWindow for Window {
         // Currently not implemented
     }
 
+    fn set_ime_surrounding_text(&self, _: &str, _: usize, _: usize) {
+        // Currently not implemented
+    }
+
     fn focus_window(&self) {
         self.inner.dispatch(|inner| {
             let _ = inner.canvas.raw().focus();
@@ -349,6 +373,10 @@ impl RootWindow for Window {
 
     fn set_content_protected(&self, _: bool) {}
 
+    fn is_content_protected(&self) -> bool {
+        false
+    }
+
     fn title(&self) -> String {
         String::new()
     }
@@ -391,12 +419,26 @@ impl RootWindow for Window {
         Err(NotSupportedError::new("drag_resize_window is not supported").into())
     }
 
-    fn show_window_menu(&self, _: Position) {}
+    fn show_window_menu(&self, _: Position) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("show_window_menu is not supported").into())
+    }
 
     fn set_cursor_hittest(&self, _: bool) -> Result<(), RequestError> {
         Err(NotSupportedError::new("set_cursor_hittest is not supported").into())
     }
 
+    fn set_input_region(&self, _region: Option<&[(Position, Size)]>) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_input_region is not supported").into())
+    }
+
+    fn set_opaque_region(&self, _region: Option<&[(Position, Size)]>) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_opaque_region is not supported").into())
+    }
+
+    fn set_shortcuts_inhibited(&self, _inhibited: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_shortcuts_inhibited is not supported").into())
+    }
+
     fn current_monitor(&self) -> Option<CoremMonitorHandle> {
         Some(self.inner.queue(|inner| inner.monitor.current_monitor()).into())
     }
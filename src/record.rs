@@ -0,0 +1,511 @@
+//! Recording and replaying the events dispatched to an [`ApplicationHandler`].
+//!
+//! A [`Recorder`] wraps an application, and writes every [`new_events`], [`window_event`] and
+//! [`device_event`] it receives to a file, with the time elapsed since the recording started. The
+//! file contains one JSON object per line, see [`Entry`].
+//!
+//! A [`Replayer`] reads such a recording back, and dispatches the recorded events to an
+//! application through the same trait at the same pace. This allows to reproduce a session
+//! captured by a user, for example with the headless backend.
+//!
+//! ```no_run
+//! # use winit::application::ApplicationHandler;
+//! # use winit::event::WindowEvent;
+//! # use winit::event_loop::{ActiveEventLoop, EventLoop};
+//! # use winit::window::WindowId;
+//! # #[derive(Default)]
+//! # struct App;
+//! # impl ApplicationHandler for App {
+//! #     fn can_create_surfaces(&mut self, _: &dyn ActiveEventLoop) {}
+//! #     fn window_event(&mut self, _: &dyn ActiveEventLoop, _: WindowId, _: WindowEvent) {}
+//! # }
+//! use winit::record::Recorder;
+//!
+//! let event_loop = EventLoop::new()?;
+//! let mut recorder = Recorder::create("session.jsonl", App::default())?;
+//! event_loop.run_app(&mut recorder)?;
+//! recorder.finish()?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! [`new_events`]: ApplicationHandler::new_events
+//! [`window_event`]: ApplicationHandler::window_event
+//! [`device_event`]: ApplicationHandler::device_event
+
+use std::collections::{HashMap, VecDeque};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+#[cfg(not(web_platform))]
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+#[cfg(web_platform)]
+use web_time::{Duration, Instant};
+
+use crate::application::{macos, unix, ApplicationHandler};
+use crate::event::{DeviceEvent, DeviceId, StartCause, WindowEvent};
+use crate::event_loop::{ActiveEventLoop, ControlFlow, TimerId};
+use crate::window::WindowId;
+
+/// A single line of a recording.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    /// The time elapsed between the start of the recording and the event.
+    pub time: Duration,
+    pub event: RecordedEvent,
+}
+
+/// An event received by the [`Recorder`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RecordedEvent {
+    /// See [`ApplicationHandler::new_events`].
+    NewEvents(RecordedStartCause),
+    /// See [`ApplicationHandler::window_event`].
+    WindowEvent { window_id: WindowId, event: WindowEvent },
+    /// See [`ApplicationHandler::device_event`].
+    DeviceEvent { device_id: Option<DeviceId>, event: DeviceEvent },
+}
+
+/// A [`StartCause`], with instants stored relative to the start of the recording.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RecordedStartCause {
+    ResumeTimeReached { start: Duration, requested_resume: Duration },
+    WaitCancelled { start: Duration, requested_resume: Option<Duration> },
+    Poll,
+    Init,
+}
+
+impl RecordedStartCause {
+    fn new(cause: StartCause, origin: Instant) -> Self {
+        let since = |instant: Instant| instant.saturating_duration_since(origin);
+        match cause {
+            StartCause::ResumeTimeReached { start, requested_resume } => Self::ResumeTimeReached {
+                start: since(start),
+                requested_resume: since(requested_resume),
+            },
+            StartCause::WaitCancelled { start, requested_resume } => Self::WaitCancelled {
+                start: since(start),
+                requested_resume: requested_resume.map(since),
+            },
+            StartCause::Poll => Self::Poll,
+            StartCause::Init => Self::Init,
+        }
+    }
+
+    /// Convert back to a [`StartCause`], relative to the given origin.
+    pub fn to_start_cause(self, origin: Instant) -> StartCause {
+        match self {
+            Self::ResumeTimeReached { start, requested_resume } => StartCause::ResumeTimeReached {
+                start: origin + start,
+                requested_resume: origin + requested_resume,
+            },
+            Self::WaitCancelled { start, requested_resume } => StartCause::WaitCancelled {
+                start: origin + start,
+                requested_resume: requested_resume.map(|resume| origin + resume),
+            },
+            Self::Poll => StartCause::Poll,
+            Self::Init => StartCause::Init,
+        }
+    }
+}
+
+/// An [`ApplicationHandler`] recording the events dispatched to the wrapped application.
+///
+/// The recording is flushed on every [`about_to_wait`], so that it survives a crash of the
+/// application. When writing fails, the recording stops and the error is returned by
+/// [`Recorder::finish`]; the application keeps receiving events.
+///
+/// [`about_to_wait`]: ApplicationHandler::about_to_wait
+#[derive(Debug)]
+pub struct Recorder<A, W: Write = BufWriter<File>> {
+    app: A,
+    writer: W,
+    origin: Instant,
+    error: Option<io::Error>,
+}
+
+impl<A: ApplicationHandler> Recorder<A> {
+    /// Record to a newly created file, truncating it if it exists.
+    pub fn create(path: impl AsRef<Path>, app: A) -> io::Result<Self> {
+        let file = File::create(path)?;
+        Ok(Self::new(BufWriter::new(file), app))
+    }
+}
+
+impl<A: ApplicationHandler, W: Write> Recorder<A, W> {
+    /// Record to the given writer.
+    ///
+    /// The recording starts now, entries are timestamped relative to this call.
+    pub fn new(writer: W, app: A) -> Self {
+        Self { app, writer, origin: Instant::now(), error: None }
+    }
+
+    /// The wrapped application.
+    pub fn app(&self) -> &A {
+        &self.app
+    }
+
+    /// The wrapped application.
+    pub fn app_mut(&mut self) -> &mut A {
+        &mut self.app
+    }
+
+    /// Flush the recording, and return the wrapped application.
+    ///
+    /// Returns the first error encountered while recording.
+    pub fn finish(mut self) -> io::Result<A> {
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
+
+        self.writer.flush()?;
+        Ok(self.app)
+    }
+
+    fn record(&mut self, event: RecordedEvent) {
+        if self.error.is_some() {
+            return;
+        }
+
+        let entry = Entry { time: self.origin.elapsed(), event };
+        let result = serde_json::to_writer(&mut self.writer, &entry)
+            .map_err(io::Error::from)
+            .and_then(|_| self.writer.write_all(b"\n"));
+        self.fail_on(result);
+    }
+
+    fn fail_on(&mut self, result: io::Result<()>) {
+        if let Err(error) = result {
+            warn!("failed to record events, recording stopped: {error}");
+            self.error = Some(error);
+        }
+    }
+}
+
+#[deny(clippy::missing_trait_methods)]
+impl<A: ApplicationHandler, W: Write> ApplicationHandler for Recorder<A, W> {
+    fn new_events(&mut self, event_loop: &dyn ActiveEventLoop, cause: StartCause) {
+        self.record(RecordedEvent::NewEvents(RecordedStartCause::new(cause, self.origin)));
+        self.app.new_events(event_loop, cause);
+    }
+
+    fn resumed(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.resumed(event_loop);
+    }
+
+    fn can_create_surfaces(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.can_create_surfaces(event_loop);
+    }
+
+    fn proxy_wake_up(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.proxy_wake_up(event_loop);
+    }
+
+    fn timer_fired(&mut self, event_loop: &dyn ActiveEventLoop, timer_id: TimerId) {
+        self.app.timer_fired(event_loop, timer_id);
+    }
+
+    fn window_event(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        window_id: WindowId,
+        event: WindowEvent,
+    ) {
+        self.record(RecordedEvent::WindowEvent { window_id, event: event.clone() });
+        self.app.window_event(event_loop, window_id, event);
+    }
+
+    fn device_event(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        device_id: Option<DeviceId>,
+        event: DeviceEvent,
+    ) {
+        self.record(RecordedEvent::DeviceEvent { device_id, event });
+        self.app.device_event(event_loop, device_id, event);
+    }
+
+    fn about_to_wait(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.about_to_wait(event_loop);
+        if self.error.is_none() {
+            let result = self.writer.flush();
+            self.fail_on(result);
+        }
+    }
+
+    fn suspended(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.suspended(event_loop);
+    }
+
+    fn destroy_surfaces(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.destroy_surfaces(event_loop);
+    }
+
+    fn memory_warning(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.memory_warning(event_loop);
+    }
+
+    fn macos_handler(&mut self) -> Option<&mut dyn macos::ApplicationHandlerExtMacOS> {
+        self.app.macos_handler()
+    }
+
+    fn unix_handler(&mut self) -> Option<&mut dyn unix::ApplicationHandlerExtUnix> {
+        self.app.unix_handler()
+    }
+}
+
+/// An [`ApplicationHandler`] dispatching a recording to the wrapped application.
+///
+/// The replayer is run on an event loop like any application. The lifecycle events of that event
+/// loop, like [`can_create_surfaces`], are forwarded to the application so that it creates its
+/// windows. Its input is ignored, and replaced with the recorded events, which are dispatched
+/// from [`about_to_wait`] once the time their event loop iteration was recorded at has elapsed.
+///
+/// The [`WindowId`]s created during the replay differ from the recorded ones. By default, the
+/// recorded windows are paired with the live windows in the order they first received an event,
+/// which matches as long as the application creates its windows in the same order. Events for a
+/// recorded window are held back until a live window has been paired with it. Pairs can also be
+/// set explicitly with [`Replayer::map_window`], and [`DeviceId`]s with [`Replayer::map_device`].
+///
+/// [`WindowEvent::RedrawRequested`] is taken from the live event loop instead of the recording,
+/// and [`WindowEvent::ScaleFactorChanged`] is replayed with a [`SurfaceSizeWriter`] that isn't
+/// connected to any window.
+///
+/// [`can_create_surfaces`]: ApplicationHandler::can_create_surfaces
+/// [`about_to_wait`]: ApplicationHandler::about_to_wait
+/// [`SurfaceSizeWriter`]: crate::event::SurfaceSizeWriter
+#[derive(Debug)]
+pub struct Replayer<A> {
+    app: A,
+    entries: VecDeque<Entry>,
+    origin: Option<Instant>,
+    /// Recorded windows not yet paired, in the order of their first event.
+    unpaired_windows: VecDeque<WindowId>,
+    windows: HashMap<WindowId, WindowId>,
+    devices: HashMap<DeviceId, DeviceId>,
+    /// The deadline set on the event loop to wake up for the next entry.
+    deadline: Option<Instant>,
+    exit_when_done: bool,
+}
+
+impl<A: ApplicationHandler> Replayer<A> {
+    /// Replay the recording stored in the given file.
+    pub fn open(path: impl AsRef<Path>, app: A) -> io::Result<Self> {
+        let file = File::open(path)?;
+        Self::from_reader(BufReader::new(file), app)
+    }
+
+    /// Replay a recording read from the given reader.
+    pub fn from_reader(reader: impl BufRead, app: A) -> io::Result<Self> {
+        let mut entries = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                entries.push(serde_json::from_str(&line)?);
+            }
+        }
+
+        Ok(Self::new(entries, app))
+    }
+
+    /// Replay the given entries.
+    pub fn new(entries: Vec<Entry>, app: A) -> Self {
+        let mut unpaired_windows = VecDeque::new();
+        for entry in &entries {
+            if let RecordedEvent::WindowEvent { window_id, .. } = entry.event {
+                if !unpaired_windows.contains(&window_id) {
+                    unpaired_windows.push_back(window_id);
+                }
+            }
+        }
+
+        Self {
+            app,
+            entries: entries.into(),
+            origin: None,
+            unpaired_windows,
+            windows: HashMap::new(),
+            devices: HashMap::new(),
+            deadline: None,
+            exit_when_done: false,
+        }
+    }
+
+    /// Dispatch events of the recorded window to the given live window.
+    pub fn map_window(&mut self, recorded: WindowId, live: WindowId) -> &mut Self {
+        self.unpaired_windows.retain(|&window_id| window_id != recorded);
+        self.windows.insert(recorded, live);
+        self
+    }
+
+    /// Dispatch events of the recorded device with the given live device.
+    ///
+    /// Unmapped devices are dispatched with their recorded identifier.
+    pub fn map_device(&mut self, recorded: DeviceId, live: DeviceId) -> &mut Self {
+        self.devices.insert(recorded, live);
+        self
+    }
+
+    /// Exit the event loop once all the recorded events were dispatched.
+    pub fn exit_when_done(&mut self, exit: bool) -> &mut Self {
+        self.exit_when_done = exit;
+        self
+    }
+
+    /// Whether all the recorded events were dispatched.
+    pub fn is_done(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The wrapped application.
+    pub fn app(&self) -> &A {
+        &self.app
+    }
+
+    /// The wrapped application.
+    pub fn app_mut(&mut self) -> &mut A {
+        &mut self.app
+    }
+
+    /// Return the wrapped application.
+    pub fn into_inner(self) -> A {
+        self.app
+    }
+
+    fn pair_window(&mut self, live: WindowId) {
+        if self.windows.values().any(|&window_id| window_id == live) {
+            return;
+        }
+
+        if let Some(recorded) = self.unpaired_windows.pop_front() {
+            self.windows.insert(recorded, live);
+        }
+    }
+
+    /// Dispatch the entries that are due, returns the time at which the next one is.
+    fn dispatch_due(&mut self, event_loop: &dyn ActiveEventLoop) -> Option<Instant> {
+        let now = Instant::now();
+        let origin = *self.origin.get_or_insert(now);
+
+        while let Some(entry) = self.entries.front() {
+            // The events of a recorded iteration are dispatched together.
+            let time = origin + entry.time;
+            if matches!(entry.event, RecordedEvent::NewEvents(_)) && time > now {
+                return Some(time);
+            }
+
+            if let RecordedEvent::WindowEvent { window_id, .. } = entry.event {
+                if !self.windows.contains_key(&window_id) {
+                    // Wait for the application to create the window.
+                    return None;
+                }
+            }
+
+            let entry = self.entries.pop_front().unwrap();
+            match entry.event {
+                RecordedEvent::NewEvents(cause) => {
+                    self.app.new_events(event_loop, cause.to_start_cause(origin))
+                },
+                RecordedEvent::WindowEvent { event: WindowEvent::RedrawRequested, .. } => (),
+                RecordedEvent::WindowEvent { window_id, event } => {
+                    self.app.window_event(event_loop, self.windows[&window_id], event)
+                },
+                RecordedEvent::DeviceEvent { device_id, event } => {
+                    let device_id =
+                        device_id.map(|id| self.devices.get(&id).copied().unwrap_or(id));
+                    self.app.device_event(event_loop, device_id, event)
+                },
+            }
+        }
+
+        None
+    }
+}
+
+#[deny(clippy::missing_trait_methods)]
+impl<A: ApplicationHandler> ApplicationHandler for Replayer<A> {
+    fn new_events(&mut self, _: &dyn ActiveEventLoop, _: StartCause) {
+        // The recorded causes are dispatched instead.
+        self.origin.get_or_insert_with(Instant::now);
+    }
+
+    fn resumed(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.resumed(event_loop);
+    }
+
+    fn can_create_surfaces(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.can_create_surfaces(event_loop);
+    }
+
+    fn proxy_wake_up(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.proxy_wake_up(event_loop);
+    }
+
+    fn timer_fired(&mut self, event_loop: &dyn ActiveEventLoop, timer_id: TimerId) {
+        self.app.timer_fired(event_loop, timer_id);
+    }
+
+    fn window_event(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        window_id: WindowId,
+        event: WindowEvent,
+    ) {
+        self.pair_window(window_id);
+        if event == WindowEvent::RedrawRequested {
+            self.app.window_event(event_loop, window_id, event);
+        }
+    }
+
+    fn device_event(&mut self, _: &dyn ActiveEventLoop, _: Option<DeviceId>, _: DeviceEvent) {}
+
+    fn about_to_wait(&mut self, event_loop: &dyn ActiveEventLoop) {
+        let next = self.dispatch_due(event_loop);
+        self.app.about_to_wait(event_loop);
+
+        // Restore the control flow of the application, unless it changed it.
+        let control_flow = match event_loop.control_flow() {
+            ControlFlow::WaitUntil(deadline) if Some(deadline) == self.deadline.take() => {
+                event_loop.set_control_flow(ControlFlow::Wait);
+                ControlFlow::Wait
+            },
+            control_flow => control_flow,
+        };
+
+        if self.is_done() && self.exit_when_done {
+            event_loop.exit();
+        }
+
+        // Wake up for the next entry, unless the application wants to wake up earlier.
+        match (next, control_flow) {
+            (None, _) | (_, ControlFlow::Poll) => (),
+            (Some(next), ControlFlow::WaitUntil(deadline)) if deadline <= next => (),
+            (Some(next), _) => {
+                event_loop.set_control_flow(ControlFlow::WaitUntil(next));
+                self.deadline = Some(next);
+            },
+        }
+    }
+
+    fn suspended(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.suspended(event_loop);
+    }
+
+    fn destroy_surfaces(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.destroy_surfaces(event_loop);
+    }
+
+    fn memory_warning(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.memory_warning(event_loop);
+    }
+
+    fn macos_handler(&mut self) -> Option<&mut dyn macos::ApplicationHandlerExtMacOS> {
+        self.app.macos_handler()
+    }
+
+    fn unix_handler(&mut self) -> Option<&mut dyn unix::ApplicationHandlerExtUnix> {
+        self.app.unix_handler()
+    }
+}
@@ -0,0 +1,319 @@
+#![cfg(headless_platform)]
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use winit::application::ApplicationHandler;
+use winit::dpi::{PhysicalPosition, PhysicalSize};
+use winit::event::{DeviceEvent, DeviceId, WindowEvent};
+use winit::event_loop::pump_events::{EventLoopExtPumpEvents, PumpStatus};
+use winit::event_loop::{ActiveEventLoop, DeviceEvents, EventLoop, TimerId, UserEventReceiver};
+use winit::monitor::VideoMode;
+use winit::platform::headless::{EventLoopBuilderExtHeadless, EventLoopExtHeadless, Simulator};
+use winit::window::{Window, WindowAttributes, WindowId};
+
+struct App {
+    attributes: Vec<WindowAttributes>,
+    windows: Vec<Box<dyn Window>>,
+    events: Vec<(WindowId, WindowEvent)>,
+    device_events: Vec<DeviceEvent>,
+    receiver: Option<UserEventReceiver<u32>>,
+    user_events: Vec<u32>,
+}
+
+impl App {
+    fn new(attributes: impl IntoIterator<Item = WindowAttributes>) -> Self {
+        Self {
+            attributes: attributes.into_iter().collect(),
+            windows: Vec::new(),
+            events: Vec::new(),
+            device_events: Vec::new(),
+            receiver: None,
+            user_events: Vec::new(),
+        }
+    }
+
+    /// The events received since the last call, without the redraws.
+    fn take_events(&mut self) -> Vec<(WindowId, WindowEvent)> {
+        let events = std::mem::take(&mut self.events);
+        events.into_iter().filter(|(_, event)| *event != WindowEvent::RedrawRequested).collect()
+    }
+}
+
+impl ApplicationHandler for App {
+    fn can_create_surfaces(&mut self, event_loop: &dyn ActiveEventLoop) {
+        for attributes in self.attributes.drain(..) {
+            self.windows.push(event_loop.create_window(attributes).unwrap());
+        }
+    }
+
+    fn window_event(&mut self, event_loop: &dyn ActiveEventLoop, id: WindowId, event: WindowEvent) {
+        if event == WindowEvent::CloseRequested {
+            self.windows.retain(|window| window.id() != id);
+            if self.windows.is_empty() {
+                event_loop.exit();
+            }
+        }
+        self.events.push((id, event));
+    }
+
+    fn device_event(&mut self, _: &dyn ActiveEventLoop, _: Option<DeviceId>, event: DeviceEvent) {
+        self.device_events.push(event);
+    }
+
+    fn proxy_wake_up(&mut self, _: &dyn ActiveEventLoop) {
+        if let Some(receiver) = &self.receiver {
+            self.user_events.extend(receiver.try_iter());
+        }
+    }
+}
+
+fn headless_event_loop() -> (EventLoop, Simulator) {
+    let event_loop = EventLoop::builder().with_headless().build().unwrap();
+    assert!(event_loop.is_headless());
+    let simulator = event_loop.simulator().unwrap();
+    (event_loop, simulator)
+}
+
+fn pump(event_loop: &mut EventLoop, app: &mut App) -> PumpStatus {
+    event_loop.pump_app_events(Some(Duration::ZERO), app)
+}
+
+#[test]
+fn initial_iteration() {
+    struct InitApp {
+        window: Option<Box<dyn Window>>,
+        events: Vec<WindowEvent>,
+        timers: Vec<TimerId>,
+        future_done: Rc<Cell<bool>>,
+        proxy_wake_ups: usize,
+    }
+
+    impl ApplicationHandler for InitApp {
+        fn can_create_surfaces(&mut self, event_loop: &dyn ActiveEventLoop) {
+            let attributes = WindowAttributes::default().with_title("headless");
+            self.window = Some(event_loop.create_window(attributes).unwrap());
+
+            event_loop.schedule_timer(Instant::now(), TimerId::from_raw(1));
+            event_loop.schedule_timer(Instant::now(), TimerId::from_raw(2));
+            event_loop.cancel_timer(TimerId::from_raw(2));
+
+            let future_done = self.future_done.clone();
+            event_loop.spawn_local(Box::pin(async move { future_done.set(true) }));
+        }
+
+        fn window_event(&mut self, _: &dyn ActiveEventLoop, _: WindowId, event: WindowEvent) {
+            self.events.push(event);
+        }
+
+        fn timer_fired(&mut self, _: &dyn ActiveEventLoop, timer_id: TimerId) {
+            self.timers.push(timer_id);
+        }
+
+        fn proxy_wake_up(&mut self, _: &dyn ActiveEventLoop) {
+            self.proxy_wake_ups += 1;
+        }
+    }
+
+    let (mut event_loop, simulator) = headless_event_loop();
+    let mut app = InitApp {
+        window: None,
+        events: Vec::new(),
+        timers: Vec::new(),
+        future_done: Rc::default(),
+        proxy_wake_ups: 0,
+    };
+
+    let status = event_loop.pump_app_events(Some(Duration::ZERO), &mut app);
+    assert_eq!(status, PumpStatus::Continue);
+    let window_id = simulator.window_ids()[0];
+    let state = simulator.window_state(window_id).unwrap();
+    assert_eq!(state.title, "headless");
+    assert_eq!(state.surface_size, PhysicalSize::new(800, 600));
+    assert!(state.focused);
+    assert_eq!(app.events, [WindowEvent::Focused(true), WindowEvent::RedrawRequested]);
+    assert_eq!(app.timers, [TimerId::from_raw(1)]);
+    assert!(app.future_done.get());
+    // Waking up the future doesn't go through the application's proxy.
+    assert_eq!(app.proxy_wake_ups, 0);
+}
+
+#[test]
+fn user_events_from_another_thread() {
+    let (mut event_loop, _) = headless_event_loop();
+    let mut app = App::new([]);
+    pump(&mut event_loop, &mut app);
+
+    let (sender, receiver) = event_loop.create_proxy().channel();
+    app.receiver = Some(receiver);
+    thread::spawn(move || (0..3).for_each(|i| sender.send(i).unwrap())).join().unwrap();
+    pump(&mut event_loop, &mut app);
+    assert_eq!(app.user_events, [0, 1, 2]);
+}
+
+#[test]
+fn window_events_update_the_state() {
+    let (mut event_loop, simulator) = headless_event_loop();
+    let mut app = App::new([WindowAttributes::default()]);
+    pump(&mut event_loop, &mut app);
+    let window_id = simulator.window_ids()[0];
+    app.take_events();
+
+    let size = PhysicalSize::new(320, 240);
+    let position = PhysicalPosition::new(10, 20);
+    simulator.send_window_event(window_id, WindowEvent::SurfaceResized(size));
+    simulator.send_window_event(window_id, WindowEvent::Moved(position));
+    pump(&mut event_loop, &mut app);
+    assert_eq!(app.take_events(), [
+        (window_id, WindowEvent::SurfaceResized(size)),
+        (window_id, WindowEvent::Moved(position)),
+    ]);
+    assert_eq!(app.windows[0].surface_size(), size);
+    assert_eq!(app.windows[0].outer_position().unwrap(), position);
+
+    // Requests from the application are reflected, and reported like a compositor would.
+    app.windows[0].set_title("renamed");
+    app.windows[0].request_redraw();
+    let _ = app.windows[0].request_surface_size(PhysicalSize::new(100, 50).into());
+    pump(&mut event_loop, &mut app);
+    let state = simulator.window_state(window_id).unwrap();
+    assert_eq!(state.title, "renamed");
+    assert_eq!(state.redraw_requests, 1);
+    assert_eq!(app.take_events(), [(
+        window_id,
+        WindowEvent::SurfaceResized(PhysicalSize::new(100, 50))
+    )]);
+}
+
+#[test]
+fn close_requested_exits() {
+    let (mut event_loop, simulator) = headless_event_loop();
+    let mut app = App::new([WindowAttributes::default()]);
+    pump(&mut event_loop, &mut app);
+    let window_id = simulator.window_ids()[0];
+    app.take_events();
+
+    simulator.send_window_event(window_id, WindowEvent::CloseRequested);
+    assert_eq!(pump(&mut event_loop, &mut app), PumpStatus::Exit(0));
+    assert_eq!(app.take_events(), [
+        (window_id, WindowEvent::CloseRequested),
+        (window_id, WindowEvent::Destroyed),
+    ]);
+    assert!(simulator.window_ids().is_empty());
+}
+
+#[test]
+fn scale_factor_keeps_the_logical_size() {
+    let (mut event_loop, simulator) = headless_event_loop();
+    let mut app = App::new([WindowAttributes::default()]);
+    pump(&mut event_loop, &mut app);
+    let window_id = simulator.window_ids()[0];
+    app.take_events();
+
+    simulator.set_scale_factor(window_id, 2.0);
+    pump(&mut event_loop, &mut app);
+    let events = app.take_events();
+    assert!(matches!(
+        events[0],
+        (id, WindowEvent::ScaleFactorChanged { scale_factor, .. })
+            if id == window_id && scale_factor == 2.0
+    ));
+    assert_eq!(events[1], (window_id, WindowEvent::SurfaceResized(PhysicalSize::new(1600, 1200))));
+    assert_eq!(events.len(), 2);
+    assert_eq!(app.windows[0].scale_factor(), 2.0);
+}
+
+#[test]
+fn focus_moves_between_windows() {
+    let (mut event_loop, simulator) = headless_event_loop();
+    let inactive = WindowAttributes::default().with_active(false);
+    let mut app = App::new([WindowAttributes::default(), inactive]);
+    pump(&mut event_loop, &mut app);
+    let [first, second] = simulator.window_ids()[..] else { panic!("expected two windows") };
+    assert_eq!(app.take_events(), [(first, WindowEvent::Focused(true))]);
+
+    simulator.set_focus(Some(second));
+    pump(&mut event_loop, &mut app);
+    assert_eq!(app.take_events(), [
+        (first, WindowEvent::Focused(false)),
+        (second, WindowEvent::Focused(true)),
+    ]);
+    assert!(app.windows[1].has_focus());
+
+    simulator.set_focus(None);
+    pump(&mut event_loop, &mut app);
+    assert_eq!(app.take_events(), [(second, WindowEvent::Focused(false))]);
+    assert!(!simulator.window_state(second).unwrap().focused);
+}
+
+#[test]
+fn monitors() {
+    let (mut event_loop, simulator) = headless_event_loop();
+    let video_mode = VideoMode::new(PhysicalSize::new(1920, 1080), None, None);
+    let left = simulator.add_monitor("left", PhysicalPosition::new(0, 0), video_mode, 1.0);
+    let right = simulator.add_monitor("right", PhysicalPosition::new(1920, 0), video_mode, 2.0);
+
+    // The window is created on the monitor containing its position.
+    let attributes = WindowAttributes::default().with_position(PhysicalPosition::new(2000, 10));
+    let mut app = App::new([attributes]);
+    pump(&mut event_loop, &mut app);
+    let window = &app.windows[0];
+    assert_eq!(window.scale_factor(), 2.0);
+    assert_eq!(window.current_monitor(), Some(right.clone()));
+    assert_eq!(window.primary_monitor(), Some(left.clone()));
+    assert_eq!(window.available_monitors().count(), 2);
+
+    assert!(simulator.remove_monitor(&left));
+    assert!(!simulator.remove_monitor(&left));
+    assert_eq!(window.primary_monitor(), Some(right));
+}
+
+#[test]
+fn device_events_are_filtered() {
+    let (mut event_loop, simulator) = headless_event_loop();
+    let mut app = App::new([WindowAttributes::default()]);
+    pump(&mut event_loop, &mut app);
+
+    let motion = DeviceEvent::PointerMotion { delta: (1.0, 2.0) };
+    event_loop.listen_device_events(DeviceEvents::Never);
+    simulator.send_device_event(None, motion);
+    pump(&mut event_loop, &mut app);
+    assert!(app.device_events.is_empty());
+
+    // The window was focused on creation.
+    event_loop.listen_device_events(DeviceEvents::WhenFocused);
+    simulator.send_device_event(None, motion);
+    pump(&mut event_loop, &mut app);
+    simulator.set_focus(None);
+    simulator.send_device_event(None, motion);
+    pump(&mut event_loop, &mut app);
+    assert_eq!(app.device_events, [motion]);
+
+    event_loop.listen_device_events(DeviceEvents::Always);
+    simulator.send_device_event(None, motion);
+    pump(&mut event_loop, &mut app);
+    assert_eq!(app.device_events, [motion, motion]);
+}
+
+#[test]
+fn event_loops_are_independent() {
+    let (mut first, first_simulator) = headless_event_loop();
+    let (mut second, second_simulator) = headless_event_loop();
+    let mut first_app = App::new([WindowAttributes::default()]);
+    let mut second_app = App::new([WindowAttributes::default(), WindowAttributes::default()]);
+    pump(&mut first, &mut first_app);
+    pump(&mut second, &mut second_app);
+    assert_eq!(first_simulator.window_ids().len(), 1);
+    assert_eq!(second_simulator.window_ids().len(), 2);
+
+    // Headless event loops can be created on any thread.
+    let window_count = thread::spawn(|| {
+        let (mut event_loop, simulator) = headless_event_loop();
+        let mut app = App::new([WindowAttributes::default()]);
+        pump(&mut event_loop, &mut app);
+        simulator.window_ids().len()
+    });
+    assert_eq!(window_count.join().unwrap(), 1);
+}
@@ -0,0 +1,64 @@
+#![cfg(all(feature = "record", headless_platform))]
+
+use std::time::Duration;
+
+use winit::application::ApplicationHandler;
+use winit::dpi::{PhysicalPosition, PhysicalSize};
+use winit::event::WindowEvent;
+use winit::event_loop::pump_events::{EventLoopExtPumpEvents, PumpStatus};
+use winit::event_loop::run_on_demand::EventLoopExtRunOnDemand;
+use winit::event_loop::{ActiveEventLoop, EventLoop};
+use winit::platform::headless::{EventLoopBuilderExtHeadless, EventLoopExtHeadless};
+use winit::record::{Recorder, Replayer};
+use winit::window::{Window, WindowId};
+
+#[derive(Default)]
+struct App {
+    window: Option<Box<dyn Window>>,
+    events: Vec<WindowEvent>,
+}
+
+impl ApplicationHandler for App {
+    fn can_create_surfaces(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.window = Some(event_loop.create_window(Default::default()).unwrap());
+    }
+
+    fn window_event(&mut self, event_loop: &dyn ActiveEventLoop, id: WindowId, event: WindowEvent) {
+        if event == WindowEvent::RedrawRequested {
+            return;
+        }
+
+        if event != WindowEvent::Destroyed {
+            assert_eq!(Some(id), self.window.as_ref().map(|window| window.id()));
+        }
+        if event == WindowEvent::CloseRequested {
+            self.window = None;
+            event_loop.exit();
+        }
+        self.events.push(event);
+    }
+}
+
+#[test]
+fn record_and_replay() {
+    let mut event_loop = EventLoop::builder().with_headless().build().unwrap();
+    let simulator = event_loop.simulator().unwrap();
+
+    let mut recording = Vec::new();
+    let mut recorder = Recorder::new(&mut recording, App::default());
+    event_loop.pump_app_events(Some(Duration::ZERO), &mut recorder);
+    let window_id = simulator.window_ids()[0];
+    simulator.send_window_event(window_id, WindowEvent::Moved(PhysicalPosition::new(10, 20)));
+    simulator.send_window_event(window_id, WindowEvent::SurfaceResized(PhysicalSize::new(64, 48)));
+    simulator.send_window_event(window_id, WindowEvent::CloseRequested);
+    let status = event_loop.pump_app_events(Some(Duration::ZERO), &mut recorder);
+    assert_eq!(status, PumpStatus::Exit(0));
+    let recorded = recorder.finish().unwrap().events;
+    assert_eq!(recorded.len(), 5);
+
+    let mut replayer = Replayer::from_reader(&recording[..], App::default()).unwrap();
+    replayer.exit_when_done(true);
+    event_loop.run_app_on_demand(&mut replayer).unwrap();
+    assert!(replayer.is_done());
+    assert_eq!(replayer.into_inner().events, recorded);
+}
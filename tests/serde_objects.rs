@@ -3,8 +3,14 @@
 use serde::{Deserialize, Serialize};
 use winit::cursor::CursorIcon;
 use winit::dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize};
-use winit::event::{ElementState, MouseButton, MouseScrollDelta, TouchPhase};
-use winit::keyboard::{Key, KeyCode, KeyLocation, ModifiersState, NamedKey, PhysicalKey};
+use winit::event::{
+    DeviceEvent, DeviceId, ElementState, KeyEvent, MouseButton, MouseScrollDelta, PreeditSegment,
+    PreeditStyle, ScrollSource, TouchPhase, WindowEvent,
+};
+use winit::keyboard::{
+    Key, KeyCode, KeyLocation, LockKeysState, ModifiersState, NamedKey, PhysicalKey,
+};
+use winit::window::WindowId;
 
 #[allow(dead_code)]
 fn needs_serde<S: Serialize + Deserialize<'static>>() {}
@@ -12,6 +18,7 @@ fn needs_serde<S: Serialize + Deserialize<'static>>() {}
 #[test]
 fn window_serde() {
     needs_serde::<CursorIcon>();
+    needs_serde::<WindowId>();
 }
 
 #[test]
@@ -20,12 +27,20 @@ fn events_serde() {
     needs_serde::<ElementState>();
     needs_serde::<MouseButton>();
     needs_serde::<MouseScrollDelta>();
+    needs_serde::<ScrollSource>();
     needs_serde::<Key>();
     needs_serde::<NamedKey>();
     needs_serde::<KeyCode>();
     needs_serde::<PhysicalKey>();
     needs_serde::<KeyLocation>();
     needs_serde::<ModifiersState>();
+    needs_serde::<LockKeysState>();
+    needs_serde::<PreeditSegment>();
+    needs_serde::<PreeditStyle>();
+    needs_serde::<WindowEvent>();
+    needs_serde::<DeviceEvent>();
+    needs_serde::<DeviceId>();
+    needs_serde::<KeyEvent>();
 }
 
 #[test]
@@ -1,6 +1,8 @@
 use std::cell::Cell;
 use std::fmt;
+use std::future::Future;
 use std::hash::Hash;
+use std::pin::Pin;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
@@ -11,6 +13,8 @@ use android_activity::{
 };
 use dpi::{PhysicalInsets, PhysicalPosition, PhysicalSize, Position, Size};
 use tracing::{debug, trace, warn};
+use winit_common::executor::Executor;
+use winit_common::timers::Timers;
 use winit_core::application::ApplicationHandler;
 use winit_core::cursor::{Cursor, CustomCursor, CustomCursorSource};
 use winit_core::error::{EventLoopError, NotSupportedError, RequestError};
@@ -19,12 +23,12 @@ use winit_core::event_loop::pump_events::PumpStatus;
 use winit_core::event_loop::{
     ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEvents,
     EventLoopProxy as CoreEventLoopProxy, EventLoopProxyProvider,
-    OwnedDisplayHandle as CoreOwnedDisplayHandle,
+    OwnedDisplayHandle as CoreOwnedDisplayHandle, TimerId,
 };
 use winit_core::monitor::{Fullscreen, MonitorHandle as CoreMonitorHandle};
 use winit_core::window::{
-    self, CursorGrabMode, ImePurpose, ResizeDirection, Theme, Window as CoreWindow,
-    WindowAttributes, WindowButtons, WindowId, WindowLevel,
+    self, CursorGrabMode, ImePurpose, PresentationHint, ResizeDirection, Theme,
+    Window as CoreWindow, WindowAttributes, WindowButtons, WindowId, WindowLevel,
 };
 
 use crate::keycodes;
@@ -134,6 +138,8 @@ impl EventLoop {
         );
 
         let event_loop_proxy = Arc::new(EventLoopProxy::new(android_app.create_waker()));
+        let executor_proxy =
+            CoreEventLoopProxy::new(Arc::new(ExecutorWaker { waker: android_app.create_waker() }));
 
         let redraw_flag = SharedFlag::new();
 
@@ -146,6 +152,9 @@ impl EventLoop {
                 exit: Cell::new(false),
                 redraw_requester: RedrawRequester::new(&redraw_flag, android_app.create_waker()),
                 event_loop_proxy,
+                executor_proxy,
+                timers: Timers::default(),
+                executor: Executor::default(),
             },
             redraw_flag,
             loop_running: false,
@@ -278,6 +287,11 @@ impl EventLoop {
             app.proxy_wake_up(&self.window_target);
         }
 
+        self.window_target.timers.dispatch(app, &self.window_target);
+
+        // Poll the futures which were woken up.
+        self.window_target.executor.poll();
+
         if self.running {
             if resized {
                 let size = if let Some(native_window) = self.android_app.native_window().as_ref() {
@@ -560,12 +574,14 @@ impl EventLoop {
 
         timeout = if self.running
             && (self.pending_redraw
-                || self.window_target.event_loop_proxy.wake_up.load(Ordering::Relaxed))
+                || self.window_target.event_loop_proxy.wake_up.load(Ordering::Relaxed)
+                || self.window_target.executor.is_ready())
         {
             // If we already have work to do then we don't want to block on the next poll
             Some(Duration::ZERO)
         } else {
-            let control_flow_timeout = match self.control_flow() {
+            let control_flow = self.window_target.timers.control_flow(self.control_flow());
+            let control_flow_timeout = match control_flow {
                 ControlFlow::Wait => None,
                 ControlFlow::Poll => Some(Duration::ZERO),
                 ControlFlow::WaitUntil(wait_deadline) => {
@@ -587,13 +603,14 @@ impl EventLoop {
                     // re-checking for pending events (assuming they cover all
                     // valid reasons for a wake up).
                     //
-                    // For now, user_events and redraw_requests are the only reasons to expect
-                    // a wake up here so we can ignore the wake up if there are no events/requests.
-                    // We also ignore wake ups while suspended.
+                    // For now, user_events, redraw_requests and woken up futures are the only
+                    // reasons to expect a wake up here so we can ignore the wake up if there are no
+                    // events/requests. We also ignore wake ups while suspended.
                     self.pending_redraw |= self.redraw_flag.get_and_reset();
                     if !self.running
                         || (!self.pending_redraw
-                            && !self.window_target.event_loop_proxy.wake_up.load(Ordering::Relaxed))
+                            && !self.window_target.event_loop_proxy.wake_up.load(Ordering::Relaxed)
+                            && !self.window_target.executor.is_ready())
                     {
                         return;
                     }
@@ -656,6 +673,23 @@ impl EventLoopProxyProvider for EventLoopProxy {
     }
 }
 
+/// Wakes up the event loop for the futures, without emitting [`ApplicationHandler::proxy_wake_up`].
+struct ExecutorWaker {
+    waker: AndroidAppWaker,
+}
+
+impl fmt::Debug for ExecutorWaker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExecutorWaker").finish_non_exhaustive()
+    }
+}
+
+impl EventLoopProxyProvider for ExecutorWaker {
+    fn wake_up(&self) {
+        self.waker.wake();
+    }
+}
+
 #[derive(Debug)]
 pub struct ActiveEventLoop {
     pub(crate) app: AndroidApp,
@@ -663,6 +697,9 @@ pub struct ActiveEventLoop {
     exit: Cell<bool>,
     redraw_requester: RedrawRequester,
     event_loop_proxy: Arc<EventLoopProxy>,
+    executor_proxy: CoreEventLoopProxy,
+    timers: Timers,
+    executor: Executor,
 }
 
 impl ActiveEventLoop {
@@ -712,6 +749,18 @@ impl RootActiveEventLoop for ActiveEventLoop {
         self.control_flow.get()
     }
 
+    fn schedule_timer(&self, deadline: Instant, timer_id: TimerId) {
+        self.timers.schedule(deadline, timer_id)
+    }
+
+    fn cancel_timer(&self, timer_id: TimerId) {
+        self.timers.cancel(timer_id)
+    }
+
+    fn spawn_local(&self, future: Pin<Box<dyn Future<Output = ()>>>) {
+        self.executor.spawn(future, self.executor_proxy.clone())
+    }
+
     fn exit(&self) {
         self.exit.set(true)
     }
@@ -836,6 +885,14 @@ impl CoreWindow for Window {
 
     fn pre_present_notify(&self) {}
 
+    fn set_frame_presented_events(&self, _enabled: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_frame_presented_events is not supported").into())
+    }
+
+    fn set_presentation_hint(&self, _hint: PresentationHint) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_presentation_hint is not supported").into())
+    }
+
     fn surface_position(&self) -> PhysicalPosition<i32> {
         (0, 0).into()
     }
@@ -926,6 +983,18 @@ impl CoreWindow for Window {
 
     fn set_window_level(&self, _level: WindowLevel) {}
 
+    fn set_workspace(&self, _workspace: u32) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_workspace is not supported").into())
+    }
+
+    fn workspace(&self) -> Option<u32> {
+        None
+    }
+
+    fn set_sticky(&self, _sticky: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_sticky is not supported").into())
+    }
+
     fn set_window_icon(&self, _window_icon: Option<winit_core::icon::Icon>) {}
 
     fn set_ime_cursor_area(&self, _position: Position, _size: Size) {}
@@ -940,6 +1009,8 @@ impl CoreWindow for Window {
 
     fn set_ime_purpose(&self, _purpose: ImePurpose) {}
 
+    fn set_ime_surrounding_text(&self, _text: &str, _cursor: usize, _anchor: usize) {}
+
     fn focus_window(&self) {}
 
     fn request_user_attention(&self, _request_type: Option<window::UserAttentionType>) {}
@@ -965,12 +1036,26 @@ impl CoreWindow for Window {
     }
 
     #[inline]
-    fn show_window_menu(&self, _position: Position) {}
+    fn show_window_menu(&self, _position: Position) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("show_window_menu is not supported").into())
+    }
 
     fn set_cursor_hittest(&self, _hittest: bool) -> Result<(), RequestError> {
         Err(NotSupportedError::new("set_cursor_hittest is not supported").into())
     }
 
+    fn set_input_region(&self, _region: Option<&[(Position, Size)]>) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_input_region is not supported").into())
+    }
+
+    fn set_opaque_region(&self, _region: Option<&[(Position, Size)]>) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_opaque_region is not supported").into())
+    }
+
+    fn set_shortcuts_inhibited(&self, _inhibited: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_shortcuts_inhibited is not supported").into())
+    }
+
     fn set_theme(&self, _theme: Option<Theme>) {}
 
     fn theme(&self) -> Option<Theme> {
@@ -979,6 +1064,10 @@ impl CoreWindow for Window {
 
     fn set_content_protected(&self, _protected: bool) {}
 
+    fn is_content_protected(&self) -> bool {
+        false
+    }
+
     fn has_focus(&self) -> bool {
         HAS_FOCUS.load(Ordering::Relaxed)
     }
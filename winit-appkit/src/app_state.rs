@@ -1,5 +1,7 @@
 use std::cell::{Cell, OnceCell, RefCell};
+use std::future::Future;
 use std::mem;
+use std::pin::Pin;
 use std::rc::Rc;
 use std::sync::Arc;
 use std::time::Instant;
@@ -10,9 +12,11 @@ use objc2_app_kit::{NSApplication, NSApplicationActivationPolicy, NSRunningAppli
 use objc2_foundation::NSNotification;
 use winit_common::core_foundation::EventLoopProxy;
 use winit_common::event_handler::EventHandler;
+use winit_common::executor::Executor;
+use winit_common::timers::Timers;
 use winit_core::application::ApplicationHandler;
 use winit_core::event::{StartCause, WindowEvent};
-use winit_core::event_loop::ControlFlow;
+use winit_core::event_loop::{ControlFlow, EventLoopProxy as CoreEventLoopProxy, TimerId};
 use winit_core::window::WindowId;
 
 use super::event_loop::{notify_windows_of_exit, stop_app_immediately, ActiveEventLoop};
@@ -27,6 +31,8 @@ pub(super) struct AppState {
     activate_ignoring_other_apps: bool,
     run_loop: RunLoop,
     event_loop_proxy: Arc<EventLoopProxy>,
+    /// Wakes up the run loop for the futures, without emitting a `proxy_wake_up`.
+    executor_proxy: Arc<EventLoopProxy>,
     event_handler: EventHandler,
     stop_on_launch: Cell<bool>,
     stop_before_wait: Cell<bool>,
@@ -39,6 +45,8 @@ pub(super) struct AppState {
     /// Whether the user has requested the event loop to exit.
     exit: Cell<bool>,
     control_flow: Cell<ControlFlow>,
+    timers: Timers,
+    executor: Executor,
     waker: RefCell<EventLoopWaker>,
     start_time: Cell<Option<Instant>>,
     wait_timeout: Cell<Option<Instant>>,
@@ -62,6 +70,8 @@ impl AppState {
         let event_loop_proxy = Arc::new(EventLoopProxy::new(mtm, move || {
             Self::get(mtm).with_handler(|app, event_loop| app.proxy_wake_up(event_loop));
         }));
+        // The futures are polled by the run loop observer before waiting.
+        let executor_proxy = Arc::new(EventLoopProxy::new(mtm, || {}));
 
         let this = Rc::new(Self {
             mtm,
@@ -70,6 +80,7 @@ impl AppState {
             activate_ignoring_other_apps,
             run_loop: RunLoop::main(mtm),
             event_loop_proxy,
+            executor_proxy,
             event_handler: EventHandler::new(),
             stop_on_launch: Cell::new(false),
             stop_before_wait: Cell::new(false),
@@ -79,6 +90,8 @@ impl AppState {
             is_running: Cell::new(false),
             exit: Cell::new(false),
             control_flow: Cell::new(ControlFlow::default()),
+            timers: Timers::default(),
+            executor: Executor::default(),
             waker: RefCell::new(EventLoopWaker::new()),
             start_time: Cell::new(None),
             wait_timeout: Cell::new(None),
@@ -243,6 +256,18 @@ impl AppState {
         self.control_flow.get()
     }
 
+    pub fn schedule_timer(&self, deadline: Instant, timer_id: TimerId) {
+        self.timers.schedule(deadline, timer_id)
+    }
+
+    pub fn cancel_timer(&self, timer_id: TimerId) {
+        self.timers.cancel(timer_id)
+    }
+
+    pub fn spawn_local(&self, future: Pin<Box<dyn Future<Output = ()>>>) {
+        self.executor.spawn(future, CoreEventLoopProxy::new(self.executor_proxy.clone()))
+    }
+
     pub fn handle_redraw(self: &Rc<Self>, window_id: WindowId) {
         // Redraw request might come out of order from the OS.
         // -> Don't go back into the event handler when our callstack originates from there
@@ -345,6 +370,11 @@ impl AppState {
             return;
         }
 
+        self.with_handler(|app, event_loop| self.timers.dispatch(app, event_loop));
+
+        // Poll the futures which were woken up.
+        self.executor.poll();
+
         let redraw = mem::take(&mut *self.pending_redraw.borrow_mut());
         for window_id in redraw {
             self.with_handler(|app, event_loop| {
@@ -367,7 +397,7 @@ impl AppState {
         }
         self.start_time.set(Some(Instant::now()));
         let wait_timeout = self.wait_timeout.get(); // configured by pump_events
-        let app_timeout = match self.control_flow() {
+        let app_timeout = match self.timers.control_flow(self.control_flow()) {
             ControlFlow::Wait => None,
             ControlFlow::Poll => Some(Instant::now()),
             ControlFlow::WaitUntil(instant) => Some(instant),
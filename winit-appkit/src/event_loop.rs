@@ -1,3 +1,5 @@
+use std::future::Future;
+use std::pin::Pin;
 use std::rc::Rc;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -17,7 +19,7 @@ use winit_core::error::{EventLoopError, RequestError};
 use winit_core::event_loop::pump_events::PumpStatus;
 use winit_core::event_loop::{
     ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEvents,
-    EventLoopProxy as CoreEventLoopProxy, OwnedDisplayHandle as CoreOwnedDisplayHandle,
+    EventLoopProxy as CoreEventLoopProxy, OwnedDisplayHandle as CoreOwnedDisplayHandle, TimerId,
 };
 use winit_core::monitor::MonitorHandle as CoreMonitorHandle;
 use winit_core::window::Theme;
@@ -109,6 +111,18 @@ impl RootActiveEventLoop for ActiveEventLoop {
         self.app_state.control_flow()
     }
 
+    fn schedule_timer(&self, deadline: Instant, timer_id: TimerId) {
+        self.app_state.schedule_timer(deadline, timer_id)
+    }
+
+    fn cancel_timer(&self, timer_id: TimerId) {
+        self.app_state.cancel_timer(timer_id)
+    }
+
+    fn spawn_local(&self, future: Pin<Box<dyn Future<Output = ()>>>) {
+        self.app_state.spawn_local(future)
+    }
+
     fn exit(&self) {
         self.app_state.exit()
     }
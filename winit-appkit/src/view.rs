@@ -18,7 +18,7 @@ use objc2_foundation::{
 };
 use winit_core::event::{
     DeviceEvent, ElementState, Ime, KeyEvent, Modifiers, MouseButton, MouseScrollDelta,
-    PointerKind, PointerSource, TouchPhase, WindowEvent,
+    PointerKind, PointerSource, ScrollSource, TouchPhase, WindowEvent,
 };
 use winit_core::keyboard::{Key, KeyCode, KeyLocation, ModifiersState, NamedKey};
 
@@ -311,7 +311,8 @@ define_class!(
             };
 
             // Send WindowEvent for updating marked text
-            self.queue_event(WindowEvent::Ime(Ime::Preedit(string.to_string(), cursor_range)));
+            let preedit = Ime::Preedit(string.to_string(), cursor_range, Vec::new());
+            self.queue_event(WindowEvent::Ime(preedit));
         }
 
         #[unsafe(method(unmarkText))]
@@ -322,7 +323,7 @@ define_class!(
             let input_context = self.inputContext().expect("input context");
             input_context.discardMarkedText();
 
-            self.queue_event(WindowEvent::Ime(Ime::Preedit(String::new(), None)));
+            self.queue_event(WindowEvent::Ime(Ime::Preedit(String::new(), None, Vec::new())));
             if self.is_ime_enabled() {
                 // Leave the Preedit self.ivars()
                 self.ivars().ime_state.set(ImeState::Ground);
@@ -383,7 +384,7 @@ define_class!(
 
             // Commit only if we have marked text.
             if unsafe { self.hasMarkedText() } && self.is_ime_enabled() && !is_control {
-                self.queue_event(WindowEvent::Ime(Ime::Preedit(String::new(), None)));
+                self.queue_event(WindowEvent::Ime(Ime::Preedit(String::new(), None, Vec::new())));
                 self.queue_event(WindowEvent::Ime(Ime::Commit(string)));
                 self.ivars().ime_state.set(ImeState::Committed);
             }
@@ -696,7 +697,13 @@ define_class!(
             self.ivars().app_state.maybe_queue_with_handler(move |app, event_loop| {
                 app.device_event(event_loop, None, DeviceEvent::MouseWheel { delta })
             });
-            self.queue_event(WindowEvent::MouseWheel { device_id: None, delta, phase });
+            self.queue_event(WindowEvent::MouseWheel {
+                device_id: None,
+                delta,
+                phase,
+                source: ScrollSource::Unknown,
+                value120: None,
+            });
         }
 
         #[unsafe(method(magnifyWithEvent:))]
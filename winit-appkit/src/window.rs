@@ -9,12 +9,12 @@ use objc2::{define_class, MainThreadMarker, Message};
 use objc2_app_kit::{NSPanel, NSResponder, NSWindow};
 use objc2_foundation::NSObject;
 use winit_core::cursor::Cursor;
-use winit_core::error::RequestError;
+use winit_core::error::{NotSupportedError, RequestError};
 use winit_core::icon::Icon;
 use winit_core::monitor::{Fullscreen, MonitorHandle as CoreMonitorHandle};
 use winit_core::window::{
-    ImePurpose, Theme, UserAttentionType, Window as CoreWindow, WindowAttributes, WindowButtons,
-    WindowId, WindowLevel,
+    ImePurpose, PresentationHint, Theme, UserAttentionType, Window as CoreWindow, WindowAttributes,
+    WindowButtons, WindowId, WindowLevel,
 };
 
 use super::event_loop::ActiveEventLoop;
@@ -109,6 +109,14 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.pre_present_notify());
     }
 
+    fn set_frame_presented_events(&self, _enabled: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_frame_presented_events is not supported").into())
+    }
+
+    fn set_presentation_hint(&self, _hint: PresentationHint) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_presentation_hint is not supported").into())
+    }
+
     fn reset_dead_keys(&self) {
         self.maybe_wait_on_main(|delegate| delegate.reset_dead_keys());
     }
@@ -229,6 +237,18 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.set_window_level(level));
     }
 
+    fn set_workspace(&self, _workspace: u32) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_workspace is not supported").into())
+    }
+
+    fn workspace(&self) -> Option<u32> {
+        None
+    }
+
+    fn set_sticky(&self, _sticky: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_sticky is not supported").into())
+    }
+
     fn set_window_icon(&self, window_icon: Option<Icon>) {
         self.maybe_wait_on_main(|delegate| delegate.set_window_icon(window_icon));
     }
@@ -245,6 +265,8 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.set_ime_purpose(purpose));
     }
 
+    fn set_ime_surrounding_text(&self, _text: &str, _cursor: usize, _anchor: usize) {}
+
     fn focus_window(&self) {
         self.maybe_wait_on_main(|delegate| delegate.focus_window());
     }
@@ -269,6 +291,10 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.set_content_protected(protected));
     }
 
+    fn is_content_protected(&self) -> bool {
+        self.maybe_wait_on_main(|delegate| delegate.is_content_protected())
+    }
+
     fn title(&self) -> String {
         self.maybe_wait_on_main(|delegate| delegate.title())
     }
@@ -303,8 +329,8 @@ impl CoreWindow for Window {
         Ok(self.maybe_wait_on_main(|delegate| delegate.drag_resize_window(direction))?)
     }
 
-    fn show_window_menu(&self, position: Position) {
-        self.maybe_wait_on_main(|delegate| delegate.show_window_menu(position))
+    fn show_window_menu(&self, position: Position) -> Result<(), RequestError> {
+        Ok(self.maybe_wait_on_main(|delegate| delegate.show_window_menu(position))?)
     }
 
     fn set_cursor_hittest(&self, hittest: bool) -> Result<(), RequestError> {
@@ -312,6 +338,18 @@ impl CoreWindow for Window {
         Ok(())
     }
 
+    fn set_input_region(&self, _region: Option<&[(Position, Size)]>) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_input_region is not supported").into())
+    }
+
+    fn set_opaque_region(&self, _region: Option<&[(Position, Size)]>) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_opaque_region is not supported").into())
+    }
+
+    fn set_shortcuts_inhibited(&self, _inhibited: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_shortcuts_inhibited is not supported").into())
+    }
+
     fn current_monitor(&self) -> Option<CoreMonitorHandle> {
         self.maybe_wait_on_main(|delegate| {
             delegate.current_monitor().map(|monitor| CoreMonitorHandle(Arc::new(monitor)))
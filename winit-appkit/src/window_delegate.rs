@@ -1295,7 +1295,9 @@ impl WindowDelegate {
     }
 
     #[inline]
-    pub fn show_window_menu(&self, _position: Position) {}
+    pub fn show_window_menu(&self, _position: Position) -> Result<(), NotSupportedError> {
+        Err(NotSupportedError::new("show_window_menu is not supported"))
+    }
 
     #[inline]
     pub fn set_cursor_hittest(&self, hittest: bool) {
@@ -1800,6 +1802,10 @@ impl WindowDelegate {
         })
     }
 
+    pub fn is_content_protected(&self) -> bool {
+        self.window().sharingType() == NSWindowSharingType::None
+    }
+
     pub fn title(&self) -> String {
         self.window().title().to_string()
     }
@@ -27,7 +27,8 @@ unsafe impl Sync for EventLoopProxy {}
 impl EventLoopProxy {
     /// Create a new proxy, registering it to be performed on the main thread.
     ///
-    /// The provided closure should call `proxy_wake_up` on the application.
+    /// The provided closure should call `proxy_wake_up` on the application, or do nothing to only
+    /// wake up the run loop.
     pub fn new<F: Fn() + 'static>(mtm: MainThreadMarker, signaller: F) -> Self {
         // We use an `Arc` here to make sure that the reference-counting of the signal container is
         // atomic (`Retained`/`CFRetained` would be valid alternatives too).
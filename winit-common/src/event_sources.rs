@@ -0,0 +1,185 @@
+//! File descriptors and timers inserted into a calloop event loop by the application.
+
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::os::fd::AsFd;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use calloop::generic::Generic;
+use calloop::timer::{TimeoutAction, Timer};
+use calloop::{Interest, LoopHandle, Mode, PostAction, RegistrationToken};
+use winit_core::application::unix::{EventSourceId, FdInterest, FdReadiness};
+use winit_core::application::ApplicationHandler;
+use winit_core::event_loop::ActiveEventLoop;
+
+#[derive(Debug, Clone, Copy)]
+enum ReadyEvent {
+    Fd(EventSourceId, FdReadiness),
+    Timer(EventSourceId, bool),
+}
+
+/// The application event sources of a calloop event loop.
+///
+/// The calloop callbacks only queue the events, they're delivered to the application with
+/// [`EventSources::dispatch`] from the event loop iteration.
+pub struct EventSources<Data: 'static> {
+    handle: LoopHandle<'static, Data>,
+    wake_up: fn(&mut Data),
+    tokens: RefCell<HashMap<EventSourceId, RegistrationToken>>,
+    next_id: Cell<u64>,
+    ready: Rc<RefCell<VecDeque<ReadyEvent>>>,
+}
+
+impl<Data: 'static> EventSources<Data> {
+    /// Create the event sources for the loop behind `handle`.
+    ///
+    /// `wake_up` is called on the loop data whenever an event got queued.
+    pub fn new(handle: LoopHandle<'static, Data>, wake_up: fn(&mut Data)) -> Self {
+        Self {
+            handle,
+            wake_up,
+            tokens: Default::default(),
+            next_id: Cell::new(0),
+            ready: Default::default(),
+        }
+    }
+
+    /// Insert a file descriptor, it's closed when the source is removed if `fd` owns it.
+    pub fn insert_fd<F: AsFd + 'static>(
+        &self,
+        fd: F,
+        interest: FdInterest,
+    ) -> Result<EventSourceId, calloop::Error> {
+        let id = self.next_id();
+        let ready = self.ready.clone();
+        let wake_up = self.wake_up;
+        let interest = Interest { readable: interest.readable, writable: interest.writable };
+        let source = Generic::new(fd, interest, Mode::Level);
+        let token = self
+            .handle
+            .insert_source(source, move |readiness, _, data| {
+                let readiness = FdReadiness {
+                    readable: readiness.readable,
+                    writable: readiness.writable,
+                    error: readiness.error,
+                };
+
+                // The source can fire several times before the events are delivered.
+                let mut ready = ready.borrow_mut();
+                let pending = ready.iter_mut().find_map(|event| match event {
+                    ReadyEvent::Fd(pending_id, pending) if *pending_id == id => Some(pending),
+                    _ => None,
+                });
+                match pending {
+                    Some(pending) => {
+                        pending.readable |= readiness.readable;
+                        pending.writable |= readiness.writable;
+                        pending.error |= readiness.error;
+                    },
+                    None => ready.push_back(ReadyEvent::Fd(id, readiness)),
+                }
+
+                wake_up(data);
+                Ok(PostAction::Continue)
+            })
+            .map_err(|err| err.error)?;
+        self.tokens.borrow_mut().insert(id, token);
+        Ok(id)
+    }
+
+    /// Insert a timer expiring at `deadline`, and then every `interval` when it's set.
+    pub fn insert_timer(
+        &self,
+        deadline: Instant,
+        interval: Option<Duration>,
+    ) -> Result<EventSourceId, calloop::Error> {
+        let id = self.next_id();
+        let ready = self.ready.clone();
+        let wake_up = self.wake_up;
+        let token = self
+            .handle
+            .insert_source(Timer::from_deadline(deadline), move |deadline, _, data| {
+                ready.borrow_mut().push_back(ReadyEvent::Timer(id, interval.is_some()));
+                wake_up(data);
+                match interval {
+                    // Don't try to catch up on the missed expirations.
+                    Some(interval) => {
+                        TimeoutAction::ToInstant((deadline + interval).max(Instant::now()))
+                    },
+                    None => TimeoutAction::Drop,
+                }
+            })
+            .map_err(|err| err.error)?;
+        self.tokens.borrow_mut().insert(id, token);
+        Ok(id)
+    }
+
+    /// Remove a source, the events it already queued are discarded.
+    pub fn remove(&self, id: EventSourceId) {
+        if let Some(token) = self.tokens.borrow_mut().remove(&id) {
+            self.handle.remove(token);
+        }
+    }
+
+    /// Whether there're events to deliver to the application.
+    pub fn has_pending(&self) -> bool {
+        !self.ready.borrow().is_empty()
+    }
+
+    /// Deliver the queued events to the application.
+    pub fn dispatch<A: ApplicationHandler + ?Sized>(
+        &self,
+        app: &mut A,
+        event_loop: &dyn ActiveEventLoop,
+    ) {
+        loop {
+            // NOTE: Release the borrows before calling into the application.
+            let event = self.ready.borrow_mut().pop_front();
+            let Some(event) = event else { break };
+
+            match event {
+                ReadyEvent::Fd(id, readiness) => {
+                    if !self.tokens.borrow().contains_key(&id) {
+                        continue;
+                    }
+
+                    if let Some(handler) = app.unix_handler() {
+                        handler.fd_ready(event_loop, id, readiness);
+                    }
+                },
+                ReadyEvent::Timer(id, repeating) => {
+                    let registered = if repeating {
+                        self.tokens.borrow().contains_key(&id)
+                    } else {
+                        // calloop already dropped the expired timer.
+                        self.tokens.borrow_mut().remove(&id).is_some()
+                    };
+                    if !registered {
+                        continue;
+                    }
+
+                    if let Some(handler) = app.unix_handler() {
+                        handler.timer_expired(event_loop, id);
+                    }
+                },
+            }
+        }
+    }
+
+    fn next_id(&self) -> EventSourceId {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+        EventSourceId::from_raw(id)
+    }
+}
+
+impl<Data: 'static> fmt::Debug for EventSources<Data> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventSources")
+            .field("sources", &self.tokens.borrow().len())
+            .field("ready", &self.ready.borrow().len())
+            .finish_non_exhaustive()
+    }
+}
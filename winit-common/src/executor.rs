@@ -0,0 +1,101 @@
+//! Futures spawned with [`ActiveEventLoop::spawn_local`].
+//!
+//! [`ActiveEventLoop::spawn_local`]: winit_core::event_loop::ActiveEventLoop::spawn_local
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Wake, Waker};
+use std::{fmt, mem};
+
+use winit_core::event_loop::EventLoopProxy;
+
+type LocalFuture = Pin<Box<dyn Future<Output = ()>>>;
+
+struct Task {
+    future: LocalFuture,
+    waker: Waker,
+}
+
+/// A local executor running the futures on the event loop thread.
+///
+/// The wakers queue their task and wake up the event loop through the [`EventLoopProxy`] given to
+/// [`Executor::spawn`], the backends then call [`Executor::poll`] in every event loop iteration.
+///
+/// That proxy must be internal to the backend, so that waking up a future doesn't emit
+/// [`ApplicationHandler::proxy_wake_up`].
+///
+/// [`ApplicationHandler::proxy_wake_up`]: winit_core::application::ApplicationHandler::proxy_wake_up
+#[derive(Default)]
+pub struct Executor {
+    tasks: RefCell<HashMap<u64, Task>>,
+    next_id: Cell<u64>,
+    ready: Arc<Mutex<Vec<u64>>>,
+}
+
+impl Executor {
+    /// Spawn a future, it's first polled in the next event loop iteration.
+    pub fn spawn(&self, future: LocalFuture, proxy: EventLoopProxy) {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+
+        let waker = Arc::new(TaskWaker { id, ready: self.ready.clone(), proxy });
+        self.tasks.borrow_mut().insert(id, Task { future, waker: Waker::from(waker.clone()) });
+        waker.wake();
+    }
+
+    /// Whether futures were woken up since the last [`Executor::poll`].
+    pub fn is_ready(&self) -> bool {
+        !self.ready.lock().unwrap().is_empty()
+    }
+
+    /// Poll the futures which were woken up.
+    ///
+    /// Futures woken up while polling are polled at the earliest in the next call, so a future
+    /// which keeps waking itself up can't starve the event loop.
+    pub fn poll(&self) {
+        let ready = mem::take(&mut *self.ready.lock().unwrap());
+        for id in ready {
+            // NOTE: Release the borrow while polling, the future can spawn other futures.
+            let task = self.tasks.borrow_mut().remove(&id);
+            let Some(mut task) = task else { continue };
+
+            let mut cx = Context::from_waker(&task.waker);
+            if task.future.as_mut().poll(&mut cx).is_pending() {
+                self.tasks.borrow_mut().insert(id, task);
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Executor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Executor")
+            .field("tasks", &self.tasks.borrow().len())
+            .finish_non_exhaustive()
+    }
+}
+
+struct TaskWaker {
+    id: u64,
+    ready: Arc<Mutex<Vec<u64>>>,
+    proxy: EventLoopProxy,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref()
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        // Queue the task before waking up, so it's polled in the iteration that wakes up.
+        let mut ready = self.ready.lock().unwrap();
+        if !ready.contains(&self.id) {
+            ready.push(self.id);
+        }
+        drop(ready);
+        self.proxy.wake_up();
+    }
+}
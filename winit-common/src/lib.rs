@@ -4,5 +4,11 @@
 pub mod core_foundation;
 #[cfg(feature = "event-handler")]
 pub mod event_handler;
+#[cfg(feature = "event-sources")]
+pub mod event_sources;
+#[cfg(feature = "executor")]
+pub mod executor;
+#[cfg(feature = "timers")]
+pub mod timers;
 #[cfg(feature = "xkb")]
 pub mod xkb;
@@ -0,0 +1,83 @@
+//! Timers scheduled with [`ActiveEventLoop::schedule_timer`].
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+use std::time::Instant;
+
+#[cfg(all(target_family = "wasm", target_os = "unknown"))]
+use web_time::Instant;
+use winit_core::application::ApplicationHandler;
+use winit_core::event_loop::{ActiveEventLoop, ControlFlow, TimerId};
+
+/// The timers of an event loop.
+///
+/// The backends wait with [`Timers::control_flow`] instead of the application's
+/// [`ControlFlow`], and call [`Timers::dispatch`] in every event loop iteration.
+#[derive(Debug, Default)]
+pub struct Timers {
+    deadlines: RefCell<HashMap<TimerId, Instant>>,
+}
+
+impl Timers {
+    pub fn schedule(&self, deadline: Instant, timer_id: TimerId) {
+        self.deadlines.borrow_mut().insert(timer_id, deadline);
+    }
+
+    pub fn cancel(&self, timer_id: TimerId) {
+        self.deadlines.borrow_mut().remove(&timer_id);
+    }
+
+    /// The deadline of the earliest timer.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.deadlines.borrow().values().min().copied()
+    }
+
+    /// The [`ControlFlow`] to wait with, so the event loop also wakes up for the timers.
+    pub fn control_flow(&self, control_flow: ControlFlow) -> ControlFlow {
+        match (control_flow, self.next_deadline()) {
+            (ControlFlow::Poll, _) | (_, None) => control_flow,
+            (ControlFlow::Wait, Some(deadline)) => ControlFlow::WaitUntil(deadline),
+            (ControlFlow::WaitUntil(wait_deadline), Some(deadline)) => {
+                ControlFlow::WaitUntil(wait_deadline.min(deadline))
+            },
+        }
+    }
+
+    /// Whether a timer is due.
+    pub fn has_expired(&self) -> bool {
+        self.next_deadline().is_some_and(|deadline| deadline <= Instant::now())
+    }
+
+    /// Fire the timers which are due, in the order of their deadlines.
+    ///
+    /// Timers scheduled again from [`ApplicationHandler::timer_fired`] fire at the earliest in the
+    /// next call.
+    pub fn dispatch<A: ApplicationHandler + ?Sized>(
+        &self,
+        app: &mut A,
+        event_loop: &dyn ActiveEventLoop,
+    ) {
+        let now = Instant::now();
+        let mut expired: Vec<_> = self
+            .deadlines
+            .borrow()
+            .iter()
+            .filter(|(_, deadline)| **deadline <= now)
+            .map(|(timer_id, deadline)| (*deadline, *timer_id))
+            .collect();
+        expired.sort_unstable();
+
+        for (deadline, timer_id) in expired {
+            // The application could've cancelled or rescheduled it from a previous callback.
+            let mut deadlines = self.deadlines.borrow_mut();
+            if deadlines.get(&timer_id) != Some(&deadline) {
+                continue;
+            }
+            deadlines.remove(&timer_id);
+            drop(deadlines);
+
+            app.timer_fired(event_loop, timer_id);
+        }
+    }
+}
@@ -117,29 +117,64 @@ impl XkbState {
             )
         };
 
-        if mask.contains(xkb_state_component::XKB_STATE_MODS_EFFECTIVE) {
-            // Effective value of mods have changed, we need to update our state.
+        if mask.intersects(
+            xkb_state_component::XKB_STATE_MODS_EFFECTIVE
+                | xkb_state_component::XKB_STATE_MODS_LATCHED
+                | xkb_state_component::XKB_STATE_MODS_LOCKED,
+        ) {
+            // Effective value of mods or their sticky parts have changed, we need to update our
+            // state.
             self.reload_modifiers();
         }
     }
 
     /// Reload the modifiers.
     fn reload_modifiers(&mut self) {
-        self.modifiers.ctrl = self.mod_name_is_active(xkb::XKB_MOD_NAME_CTRL);
-        self.modifiers.alt = self.mod_name_is_active(xkb::XKB_MOD_NAME_ALT);
-        self.modifiers.shift = self.mod_name_is_active(xkb::XKB_MOD_NAME_SHIFT);
-        self.modifiers.caps_lock = self.mod_name_is_active(xkb::XKB_MOD_NAME_CAPS);
-        self.modifiers.logo = self.mod_name_is_active(xkb::XKB_MOD_NAME_LOGO);
-        self.modifiers.num_lock = self.mod_name_is_active(xkb::XKB_MOD_NAME_NUM);
+        let effective = &xkb_state_component::XKB_STATE_MODS_EFFECTIVE;
+        self.modifiers.ctrl = self.mod_name_is_active(xkb::XKB_MOD_NAME_CTRL, effective);
+        self.modifiers.alt = self.mod_name_is_active(xkb::XKB_MOD_NAME_ALT, effective);
+        self.modifiers.shift = self.mod_name_is_active(xkb::XKB_MOD_NAME_SHIFT, effective);
+        self.modifiers.caps_lock = self.mod_name_is_active(xkb::XKB_MOD_NAME_CAPS, effective);
+        self.modifiers.logo = self.mod_name_is_active(xkb::XKB_MOD_NAME_LOGO, effective);
+        self.modifiers.num_lock = self.mod_name_is_active(xkb::XKB_MOD_NAME_NUM, effective);
+        // Scroll Lock is a virtual modifier, it's only present when the keymap maps it.
+        self.modifiers.scroll_lock = self.mod_name_is_active(b"ScrollLock\0", effective);
+        self.modifiers.latched = self.core_modifiers(&xkb_state_component::XKB_STATE_MODS_LATCHED);
+        self.modifiers.locked = self.core_modifiers(&xkb_state_component::XKB_STATE_MODS_LOCKED);
+    }
+
+    /// Build the winit modifiers active within the given `component` of the xkb state.
+    fn core_modifiers(
+        &mut self,
+        component: &xkb_state_component,
+    ) -> winit_core::keyboard::ModifiersState {
+        let mut mods = winit_core::keyboard::ModifiersState::empty();
+        mods.set(
+            winit_core::keyboard::ModifiersState::SHIFT,
+            self.mod_name_is_active(xkb::XKB_MOD_NAME_SHIFT, component),
+        );
+        mods.set(
+            winit_core::keyboard::ModifiersState::CONTROL,
+            self.mod_name_is_active(xkb::XKB_MOD_NAME_CTRL, component),
+        );
+        mods.set(
+            winit_core::keyboard::ModifiersState::ALT,
+            self.mod_name_is_active(xkb::XKB_MOD_NAME_ALT, component),
+        );
+        mods.set(
+            winit_core::keyboard::ModifiersState::META,
+            self.mod_name_is_active(xkb::XKB_MOD_NAME_LOGO, component),
+        );
+        mods
     }
 
     /// Check if the modifier is active within xkb.
-    fn mod_name_is_active(&mut self, name: &[u8]) -> bool {
+    fn mod_name_is_active(&mut self, name: &[u8], component: &xkb_state_component) -> bool {
         unsafe {
             (XKBH.xkb_state_mod_name_is_active)(
                 self.state.as_ptr(),
                 name.as_ptr() as *const c_char,
-                xkb_state_component::XKB_STATE_MODS_EFFECTIVE,
+                xkb_state_component::from_bits_retain(component.bits()),
             ) > 0
         }
     }
@@ -176,6 +211,12 @@ pub struct ModifiersState {
     pub logo: bool,
     /// The "Num lock" key
     pub num_lock: bool,
+    /// The "Scroll lock" key
+    pub scroll_lock: bool,
+    /// Modifiers which are latched, like a sticky "shift"
+    pub latched: winit_core::keyboard::ModifiersState,
+    /// Modifiers which are locked, like a sticky "shift" pressed twice
+    pub locked: winit_core::keyboard::ModifiersState,
 }
 
 impl From<ModifiersState> for winit_core::keyboard::ModifiersState {
@@ -188,3 +229,15 @@ impl From<ModifiersState> for winit_core::keyboard::ModifiersState {
         to_mods
     }
 }
+
+impl From<ModifiersState> for winit_core::event::Modifiers {
+    fn from(mods: ModifiersState) -> winit_core::event::Modifiers {
+        let mut lock_keys = winit_core::keyboard::LockKeysState::empty();
+        lock_keys.set(winit_core::keyboard::LockKeysState::CAPS_LOCK, mods.caps_lock);
+        lock_keys.set(winit_core::keyboard::LockKeysState::NUM_LOCK, mods.num_lock);
+        lock_keys.set(winit_core::keyboard::LockKeysState::SCROLL_LOCK, mods.scroll_lock);
+        winit_core::event::Modifiers::from(winit_core::keyboard::ModifiersState::from(mods))
+            .with_sticky_state(mods.latched, mods.locked)
+            .with_lock_keys(lock_keys)
+    }
+}
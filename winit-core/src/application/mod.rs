@@ -1,10 +1,11 @@
 //! End user application handling.
 
 use crate::event::{DeviceEvent, DeviceId, StartCause, WindowEvent};
-use crate::event_loop::ActiveEventLoop;
+use crate::event_loop::{ActiveEventLoop, TimerId};
 use crate::window::WindowId;
 
 pub mod macos;
+pub mod unix;
 
 /// The handler of application-level events.
 pub trait ApplicationHandler {
@@ -118,19 +119,21 @@ pub trait ApplicationHandler {
     ///
     /// # Example
     ///
-    /// Use a [`std::sync::mpsc`] channel to handle events from a different thread.
+    /// Use a channel created with [`EventLoopProxy::channel()`] to handle events from a different
+    /// thread.
+    ///
+    /// [`EventLoopProxy::channel()`]: crate::event_loop::EventLoopProxy::channel
     ///
     /// ```no_run
-    /// use std::sync::mpsc;
     /// use std::thread;
     /// use std::time::Duration;
     ///
     /// use winit::event_loop::EventLoop;
     /// use winit_core::application::ApplicationHandler;
-    /// use winit_core::event_loop::ActiveEventLoop;
+    /// use winit_core::event_loop::{ActiveEventLoop, UserEventReceiver};
     ///
     /// struct MyApp {
-    ///     receiver: mpsc::Receiver<u64>,
+    ///     receiver: UserEventReceiver<u64>,
     /// }
     ///
     /// impl ApplicationHandler for MyApp {
@@ -146,9 +149,6 @@ pub trait ApplicationHandler {
     ///     #
     ///     fn proxy_wake_up(&mut self, _event_loop: &dyn ActiveEventLoop) {
     ///         // Iterate current events, since wake-ups may have been merged.
-    ///         //
-    ///         // Note: We take care not to use `recv` or `iter` here, as those are blocking,
-    ///         // and that would be bad for performance and might lead to a deadlock.
     ///         for i in self.receiver.try_iter() {
     ///             println!("received: {i}");
     ///         }
@@ -160,10 +160,10 @@ pub trait ApplicationHandler {
     /// fn main() -> Result<(), Box<dyn std::error::Error>> {
     ///     let event_loop = EventLoop::new()?;
     ///
-    ///     let (sender, receiver) = mpsc::channel();
+    ///     // Sending an event also wakes up the event loop.
+    ///     let (sender, receiver) = event_loop.create_proxy().channel();
     ///
     ///     // Send an event in a loop
-    ///     let proxy = event_loop.create_proxy();
     ///     let background_thread = thread::spawn(move || {
     ///         let mut i = 0;
     ///         loop {
@@ -172,9 +172,6 @@ pub trait ApplicationHandler {
     ///                 // Stop sending once the receiver is dropped
     ///                 break;
     ///             }
-    ///             // Trigger the wake-up _after_ we placed the event in the channel.
-    ///             // Otherwise, `proxy_wake_up` might be triggered prematurely.
-    ///             proxy.wake_up();
     ///             i += 1;
     ///             thread::sleep(Duration::from_secs(1));
     ///         }
@@ -191,6 +188,14 @@ pub trait ApplicationHandler {
         let _ = event_loop;
     }
 
+    /// Emitted when a timer scheduled with [`ActiveEventLoop::schedule_timer`] is reached.
+    ///
+    /// The timer is removed before this is emitted, so it can be scheduled again from here to
+    /// make it repeat.
+    fn timer_fired(&mut self, event_loop: &dyn ActiveEventLoop, timer_id: TimerId) {
+        let _ = (event_loop, timer_id);
+    }
+
     /// Emitted when the OS sends an event to a winit window.
     fn window_event(
         &mut self,
@@ -343,6 +348,14 @@ pub trait ApplicationHandler {
     fn macos_handler(&mut self) -> Option<&mut dyn macos::ApplicationHandlerExtMacOS> {
         None
     }
+
+    /// The handler for the events specific to Unix platforms running on X11 or Wayland.
+    ///
+    /// The return value from this should not change at runtime.
+    #[inline(always)]
+    fn unix_handler(&mut self) -> Option<&mut dyn unix::ApplicationHandlerExtUnix> {
+        None
+    }
 }
 
 #[deny(clippy::missing_trait_methods)]
@@ -367,6 +380,11 @@ impl<A: ?Sized + ApplicationHandler> ApplicationHandler for &mut A {
         (**self).proxy_wake_up(event_loop);
     }
 
+    #[inline]
+    fn timer_fired(&mut self, event_loop: &dyn ActiveEventLoop, timer_id: TimerId) {
+        (**self).timer_fired(event_loop, timer_id);
+    }
+
     #[inline]
     fn window_event(
         &mut self,
@@ -411,6 +429,11 @@ impl<A: ?Sized + ApplicationHandler> ApplicationHandler for &mut A {
     fn macos_handler(&mut self) -> Option<&mut dyn macos::ApplicationHandlerExtMacOS> {
         (**self).macos_handler()
     }
+
+    #[inline]
+    fn unix_handler(&mut self) -> Option<&mut dyn unix::ApplicationHandlerExtUnix> {
+        (**self).unix_handler()
+    }
 }
 
 #[deny(clippy::missing_trait_methods)]
@@ -435,6 +458,11 @@ impl<A: ?Sized + ApplicationHandler> ApplicationHandler for Box<A> {
         (**self).proxy_wake_up(event_loop);
     }
 
+    #[inline]
+    fn timer_fired(&mut self, event_loop: &dyn ActiveEventLoop, timer_id: TimerId) {
+        (**self).timer_fired(event_loop, timer_id);
+    }
+
     #[inline]
     fn window_event(
         &mut self,
@@ -479,4 +507,9 @@ impl<A: ?Sized + ApplicationHandler> ApplicationHandler for Box<A> {
     fn macos_handler(&mut self) -> Option<&mut dyn macos::ApplicationHandlerExtMacOS> {
         (**self).macos_handler()
     }
+
+    #[inline]
+    fn unix_handler(&mut self) -> Option<&mut dyn unix::ApplicationHandlerExtUnix> {
+        (**self).unix_handler()
+    }
 }
@@ -0,0 +1,96 @@
+use crate::application::ApplicationHandler;
+use crate::event_loop::ActiveEventLoop;
+
+/// Additional events on [`ApplicationHandler`] that are specific to the Unix platforms running
+/// on X11 or Wayland.
+///
+/// This can be registered with [`ApplicationHandler::unix_handler`].
+pub trait ApplicationHandlerExtUnix: ApplicationHandler {
+    /// A file descriptor inserted into the event loop is ready.
+    ///
+    /// File descriptors are level-triggered, this is emitted on every event loop iteration for as
+    /// long as the file descriptor stays ready, so the application should consume what's
+    /// available before returning.
+    fn fd_ready(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        source: EventSourceId,
+        readiness: FdReadiness,
+    ) {
+        let _ = event_loop;
+        let _ = source;
+        let _ = readiness;
+    }
+
+    /// A timer inserted into the event loop expired.
+    ///
+    /// One-shot timers are removed from the event loop before this is emitted.
+    fn timer_expired(&mut self, event_loop: &dyn ActiveEventLoop, source: EventSourceId) {
+        let _ = event_loop;
+        let _ = source;
+    }
+
+    /// The connection to the X server or the Wayland compositor was lost.
+    ///
+    /// The windows can't be used anymore and should be dropped. Afterwards, the event loop exits
+    /// with [`EventLoopError::ConnectionLost`], and [`PumpStatus::ConnectionLost`] is returned
+    /// when pumping events.
+    ///
+    /// When reconnecting is enabled on Wayland, [`ApplicationHandler::destroy_surfaces`] is
+    /// emitted instead, and winit connects to the compositor again. If that succeeds,
+    /// [`ApplicationHandler::can_create_surfaces`] is emitted and the event loop keeps running.
+    ///
+    /// [`EventLoopError::ConnectionLost`]: crate::error::EventLoopError::ConnectionLost
+    /// [`PumpStatus::ConnectionLost`]: crate::event_loop::pump_events::PumpStatus::ConnectionLost
+    fn connection_lost(&mut self, event_loop: &dyn ActiveEventLoop) {
+        let _ = event_loop;
+    }
+}
+
+/// Identifier of a file descriptor or a timer inserted into the event loop.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EventSourceId(u64);
+
+impl EventSourceId {
+    /// Convert the [`EventSourceId`] into the underlying integer.
+    pub const fn into_raw(self) -> u64 {
+        self.0
+    }
+
+    /// Construct an [`EventSourceId`] from the underlying integer.
+    ///
+    /// This should only be called with integers returned from [`EventSourceId::into_raw`].
+    pub const fn from_raw(id: u64) -> Self {
+        Self(id)
+    }
+}
+
+/// The events to wait for on a file descriptor.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct FdInterest {
+    /// Wait for the file descriptor to be readable.
+    pub readable: bool,
+    /// Wait for the file descriptor to be writable.
+    pub writable: bool,
+}
+
+impl FdInterest {
+    /// Wait for the file descriptor to be readable.
+    pub const READ: Self = Self { readable: true, writable: false };
+    /// Wait for the file descriptor to be writable.
+    pub const WRITE: Self = Self { readable: false, writable: true };
+    /// Wait for the file descriptor to be readable or writable.
+    pub const BOTH: Self = Self { readable: true, writable: true };
+}
+
+/// The readiness of a file descriptor.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct FdReadiness {
+    /// The file descriptor is readable.
+    pub readable: bool,
+    /// The file descriptor is writable.
+    pub writable: bool,
+    /// An error condition is pending on the file descriptor, e.g. the other end of a pipe was
+    /// closed.
+    pub error: bool,
+}
@@ -14,6 +14,8 @@ pub enum EventLoopError {
     Os(OsError),
     /// Creating the event loop with the requested configuration is not supported.
     NotSupported(NotSupportedError),
+    /// The connection to the display server was lost.
+    ConnectionLost(OsError),
 }
 
 impl fmt::Display for EventLoopError {
@@ -23,13 +25,14 @@ impl fmt::Display for EventLoopError {
             Self::Os(err) => err.fmt(f),
             Self::ExitFailure(status) => write!(f, "Exit Failure: {status}"),
             Self::NotSupported(err) => err.fmt(f),
+            Self::ConnectionLost(err) => write!(f, "Connection to the display server lost: {err}"),
         }
     }
 }
 
 impl Error for EventLoopError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
-        if let Self::Os(err) = self {
+        if let Self::Os(err) | Self::ConnectionLost(err) = self {
             err.source()
         } else {
             None
@@ -1,9 +1,11 @@
 //! The event enums and assorted supporting types.
 use std::path::PathBuf;
 use std::sync::{Mutex, Weak};
+use std::time::Duration;
 #[cfg(not(web_platform))]
 use std::time::Instant;
 
+use bitflags::bitflags;
 use dpi::{PhysicalPosition, PhysicalSize};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -13,7 +15,7 @@ use web_time::Instant;
 
 use crate::error::RequestError;
 use crate::event_loop::AsyncRequestSerial;
-use crate::keyboard::{self, ModifiersKeyState, ModifiersKeys, ModifiersState};
+use crate::keyboard::{self, LockKeysState, ModifiersKeyState, ModifiersKeys, ModifiersState};
 #[cfg(doc)]
 use crate::window::Window;
 use crate::window::{ActivationToken, Theme};
@@ -44,6 +46,7 @@ pub enum StartCause {
 
 /// Describes an event from a [`Window`].
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum WindowEvent {
     /// The activation token was delivered back and now could be used.
     ActivationTokenDone { serial: AsyncRequestSerial, token: ActivationToken },
@@ -235,7 +238,43 @@ pub enum WindowEvent {
     },
 
     /// A mouse wheel movement or touchpad scroll occurred.
-    MouseWheel { device_id: Option<DeviceId>, delta: MouseScrollDelta, phase: TouchPhase },
+    ///
+    /// A [`TouchPhase::Ended`] phase marks the end of a continuous scroll sequence, like when the
+    /// fingers are lifted from a touchpad, and can be used to start kinetic scrolling.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The scroll sequence is never ended.
+    MouseWheel {
+        device_id: Option<DeviceId>,
+        delta: MouseScrollDelta,
+        phase: TouchPhase,
+
+        /// The kind of device that produced the scroll.
+        ///
+        /// ## Platform-specific
+        ///
+        /// - **X11:** Only known for the core wheel buttons, smooth scrolling is
+        ///   [`ScrollSource::Unknown`].
+        /// - **Android / iOS / macOS / Orbital / Web / Windows:** Always
+        ///   [`ScrollSource::Unknown`].
+        source: ScrollSource,
+
+        /// The high-resolution wheel movement in the horizontal and vertical directions, where
+        /// `120` is one notch of the wheel, using the same sign convention as `delta`.
+        ///
+        /// High-resolution wheels send fractions of a notch, which should be accumulated by the
+        /// application.
+        ///
+        /// ## Platform-specific
+        ///
+        /// - **Wayland:** Only whole notches are reported when the compositor doesn't support
+        ///   `wl_seat` version 8.
+        /// - **X11:** Also reported for the smooth scrolling of other devices, like touchpads,
+        ///   since XInput 2 doesn't tell them apart from the wheels.
+        /// - **Android / iOS / macOS / Orbital / Web:** Always `None`.
+        value120: Option<(i32, i32)>,
+    },
 
     /// An mouse button press has been received.
     PointerButton {
@@ -365,6 +404,9 @@ pub enum WindowEvent {
         /// Handle to update surface size during scale changes.
         ///
         /// See [`SurfaceSizeWriter`] docs for more details.
+        ///
+        /// The handle isn't serialized, a deserialized handle is not connected to any window.
+        #[cfg_attr(feature = "serde", serde(skip, default = "SurfaceSizeWriter::detached"))]
         surface_size_writer: SurfaceSizeWriter,
     },
 
@@ -409,6 +451,42 @@ pub enum WindowEvent {
     /// [`transform`]: https://developer.mozilla.org/en-US/docs/Web/CSS/transform
     Occluded(bool),
 
+    /// The system keyboard shortcuts inhibition of the window has changed.
+    ///
+    /// Emitted after [`Window::set_shortcuts_inhibited`] once the system granted, refused or
+    /// revoked the inhibition. The value is `true` when the shortcuts are inhibited and the key
+    /// events are delivered to the window.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android / iOS / macOS / Orbital / Web / Windows:** Unsupported.
+    ///
+    /// [`Window::set_shortcuts_inhibited`]: crate::window::Window::set_shortcuts_inhibited
+    ShortcutsInhibited(bool),
+
+    /// A frame of the window was presented on the screen.
+    ///
+    /// Only emitted once enabled with [`Window::set_frame_presented_events`], which lets
+    /// applications synchronize audio and video or pace their frames.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Only emitted for the frames announced with [`Window::pre_present_notify`].
+    /// - **X11:** Only emitted for the frames presented with the Present extension, which is what
+    ///   Mesa uses for OpenGL and Vulkan.
+    /// - **Android / iOS / macOS / Orbital / Web / Windows:** Unsupported.
+    ///
+    /// [`Window::set_frame_presented_events`]: crate::window::Window::set_frame_presented_events
+    /// [`Window::pre_present_notify`]: crate::window::Window::pre_present_notify
+    FramePresented {
+        /// The time at which the frame turned into light, measured on `CLOCK_MONOTONIC`.
+        presented_at: Duration,
+        /// The duration until the next refresh of the output, if known.
+        refresh_interval: Option<Duration>,
+        /// How the frame was presented.
+        flags: PresentationFlags,
+    },
+
     /// Emitted when a window should be redrawn.
     ///
     /// This gets triggered in a few scenarios:
@@ -423,6 +501,22 @@ pub enum WindowEvent {
     RedrawRequested,
 }
 
+bitflags! {
+    /// How a frame was presented, see [`WindowEvent::FramePresented`].
+    #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub struct PresentationFlags: u8 {
+        /// The presentation was synchronized to the vertical retrace of the display.
+        const VSYNC = 0b0001;
+        /// The timestamp comes from the display hardware, instead of being sampled in software.
+        const HW_CLOCK = 0b0010;
+        /// The display hardware signalled the completion of the presentation.
+        const HW_COMPLETION = 0b0100;
+        /// The buffer was scanned out directly, without being copied by the compositor.
+        const ZERO_COPY = 0b1000;
+    }
+}
+
 /// Represents the kind type of a pointer event.
 ///
 /// ## Platform-specific
@@ -430,6 +524,7 @@ pub enum WindowEvent {
 /// **Wayland/X11:** [`Unknown`](Self::Unknown) device types are converted to known variants by the
 /// system.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum PointerKind {
     Mouse,
     /// See [`PointerSource::Touch`] for more details.
@@ -446,6 +541,7 @@ pub enum PointerKind {
 /// **Wayland/X11:** [`Unknown`](Self::Unknown) device types are converted to known variants by the
 /// system.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum PointerSource {
     Mouse,
     /// Represents a touch event.
@@ -506,6 +602,7 @@ impl From<PointerSource> for PointerKind {
 /// **Wayland/X11:** [`Unknown`](Self::Unknown) device types are converted to known variants by the
 /// system.
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ButtonSource {
     Mouse(MouseButton),
     /// See [`PointerSource::Touch`] for more details.
@@ -553,6 +650,7 @@ impl From<MouseButton> for ButtonSource {
 /// on-screen cursor and keyboard focus) or physical. Virtual devices typically aggregate inputs
 /// from multiple physical devices.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DeviceId(i64);
 
 impl DeviceId {
@@ -576,6 +674,7 @@ impl DeviceId {
 /// Whenever a touch event is received it contains a `FingerId` which uniquely identifies the finger
 /// used for the current interaction.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct FingerId(pub(crate) usize);
 
 impl FingerId {
@@ -605,6 +704,7 @@ impl FingerId {
 ///
 /// [window events]: WindowEvent
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum DeviceEvent {
     /// Change in physical position of a pointing device.
     ///
@@ -653,6 +753,7 @@ pub struct RawKeyEvent {
 
 /// Describes a keyboard input targeting a window.
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct KeyEvent {
     /// Represents the position of a key independent of the currently active layout.
     ///
@@ -812,12 +913,31 @@ pub struct Modifiers {
     //
     // The field providing a metadata, it shouldn't be used as a source of truth.
     pub(crate) pressed_mods: ModifiersKeys,
+
+    pub(crate) latched: ModifiersState,
+
+    pub(crate) locked: ModifiersState,
+
+    pub(crate) lock_keys: LockKeysState,
 }
 
 impl Modifiers {
     /// Create a new modifiers from state and pressed mods.
     pub fn new(state: ModifiersState, pressed_mods: ModifiersKeys) -> Self {
-        Self { state, pressed_mods }
+        Self { state, pressed_mods, ..Default::default() }
+    }
+
+    /// Set the latched and locked parts of the modifiers state.
+    pub fn with_sticky_state(mut self, latched: ModifiersState, locked: ModifiersState) -> Self {
+        self.latched = latched;
+        self.locked = locked;
+        self
+    }
+
+    /// Set the state of the lock keys.
+    pub fn with_lock_keys(mut self, lock_keys: LockKeysState) -> Self {
+        self.lock_keys = lock_keys;
+        self
     }
 
     /// The logical state of the modifiers.
@@ -825,6 +945,39 @@ impl Modifiers {
         self.state
     }
 
+    /// The modifiers which are latched, i.e. that will be released after the next non-modifier
+    /// key press, as is the case with sticky keys.
+    ///
+    /// Latched modifiers are also part of [`Modifiers::state`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android / iOS / macOS / Orbital / Web / Windows:** Unsupported, always empty.
+    pub fn latched_state(&self) -> ModifiersState {
+        self.latched
+    }
+
+    /// The modifiers which are locked, i.e. that stay active until pressed again.
+    ///
+    /// Locked modifiers are also part of [`Modifiers::state`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android / iOS / macOS / Orbital / Web / Windows:** Unsupported, always empty.
+    pub fn locked_state(&self) -> ModifiersState {
+        self.locked
+    }
+
+    /// The state of the lock keys, like Caps Lock.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android / iOS / macOS / Orbital / Web / Windows:** Unsupported, always empty.
+    /// - **Wayland / X11:** Scroll Lock is only reported when the keymap maps it to a modifier.
+    pub fn lock_keys(&self) -> LockKeysState {
+        self.lock_keys
+    }
+
     /// The logical state of the left shift key.
     pub fn lshift_state(&self) -> ModifiersKeyState {
         self.mod_state(ModifiersKeys::LSHIFT)
@@ -876,7 +1029,7 @@ impl Modifiers {
 
 impl From<ModifiersState> for Modifiers {
     fn from(value: ModifiersState) -> Self {
-        Self { state: value, pressed_mods: Default::default() }
+        Self { state: value, ..Default::default() }
     }
 }
 
@@ -896,9 +1049,9 @@ impl From<ModifiersState> for Modifiers {
 ///
 /// ```ignore
 /// // Press "`" key
-/// Ime::Preedit("`", Some((0, 0)))
+/// Ime::Preedit("`", Some((0, 0)), vec![])
 /// // Press "E" key
-/// Ime::Preedit("", None) // Synthetic event generated by winit to clear preedit.
+/// Ime::Preedit("", None, vec![]) // Synthetic event generated by winit to clear preedit.
 /// Ime::Commit("é")
 /// ```
 ///
@@ -911,15 +1064,15 @@ impl From<ModifiersState> for Modifiers {
 ///
 /// ```ignore
 /// // Press "A" key
-/// Ime::Preedit("a", Some((1, 1)))
+/// Ime::Preedit("a", Some((1, 1)), vec![])
 /// // Press "B" key
-/// Ime::Preedit("a b", Some((3, 3)))
+/// Ime::Preedit("a b", Some((3, 3)), vec![])
 /// // Press left arrow key
-/// Ime::Preedit("a b", Some((1, 1)))
+/// Ime::Preedit("a b", Some((1, 1)), vec![])
 /// // Press space key
-/// Ime::Preedit("啊b", Some((3, 3)))
+/// Ime::Preedit("啊b", Some((3, 3)), vec![])
 /// // Press space key
-/// Ime::Preedit("", None) // Synthetic event generated by winit to clear preedit.
+/// Ime::Preedit("", None, vec![]) // Synthetic event generated by winit to clear preedit.
 /// Ime::Commit("啊不")
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -934,18 +1087,43 @@ pub enum Ime {
 
     /// Notifies when a new composing text should be set at the cursor position.
     ///
-    /// The value represents a pair of the preedit string and the cursor begin position and end
-    /// position. When it's `None`, the cursor should be hidden. When `String` is an empty string
-    /// this indicates that preedit was cleared.
+    /// The value represents the preedit string, the cursor begin position and end position, and
+    /// the styled segments of the preedit. When the cursor is `None`, it should be hidden. When
+    /// `String` is an empty string this indicates that preedit was cleared.
     ///
-    /// The cursor position is byte-wise indexed.
-    Preedit(String, Option<(usize, usize)>),
+    /// The cursor position and the segments are byte-wise indexed. The segments are sorted and
+    /// don't overlap; text outside of them should be drawn without any particular style.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Segments are populated from the XIM feedback of the preedit text.
+    /// - **Wayland:** The protocol carries no styling, so the whole preedit is underlined and the
+    ///   cursor range, when not empty, is marked as selected.
+    /// - **Android / iOS / macOS / Orbital / Web / Windows:** Segments are always empty.
+    Preedit(String, Option<(usize, usize)>, Vec<PreeditSegment>),
 
     /// Notifies when text should be inserted into the editor widget.
     ///
     /// Right before this event winit will send empty [`Self::Preedit`] event.
     Commit(String),
 
+    /// Notifies when the text around the cursor should be deleted.
+    ///
+    /// The values are the lengths in bytes of the text to delete `before` and `after` the cursor,
+    /// relative to the text provided with [`Window::set_ime_surrounding_text`]. When there's a
+    /// selection, the lengths are relative to its start and end, and the selection itself should
+    /// be deleted as well. The preedit text is not part of the deleted text.
+    ///
+    /// When this event is sent along with [`Self::Commit`], it's sent first.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Sent when the input method requests the substitution of the surrounding text.
+    /// - **Android / iOS / macOS / Orbital / Web / Windows:** Unsupported.
+    ///
+    /// [`Window::set_ime_surrounding_text`]: crate::window::Window::set_ime_surrounding_text
+    DeleteSurrounding { before: usize, after: usize },
+
     /// Notifies when the IME was disabled.
     ///
     /// After receiving this event you won't get any more [`Preedit`][Self::Preedit] or
@@ -955,6 +1133,35 @@ pub enum Ime {
     Disabled,
 }
 
+/// A styled range of the [`Ime::Preedit`] text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PreeditSegment {
+    /// The byte-wise start of the segment.
+    pub start: usize,
+    /// The byte-wise end of the segment, exclusive.
+    pub end: usize,
+    /// The style to draw the segment with.
+    pub style: PreeditStyle,
+}
+
+bitflags! {
+    /// The style of a [`PreeditSegment`].
+    ///
+    /// Input methods usually mark the whole preedit as underlined and the clause being
+    /// converted as selected.
+    #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub struct PreeditStyle: u8 {
+        /// The text should be underlined.
+        const UNDERLINE = 0b001;
+        /// The text is selected, and should usually be drawn in reverse video.
+        const SELECTED = 0b010;
+        /// The text should be highlighted.
+        const HIGHLIGHTED = 0b100;
+    }
+}
+
 /// Describes touch-screen input state.
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -1045,6 +1252,27 @@ pub enum MouseButton {
     Other(u16),
 }
 
+/// Describes the kind of device that produced a [`WindowEvent::MouseWheel`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ScrollSource {
+    /// The source isn't known.
+    #[default]
+    Unknown,
+
+    /// A mouse wheel, scrolling in discrete steps.
+    Wheel,
+
+    /// A finger on a touch surface, like a touchpad.
+    Finger,
+
+    /// A continuous device without discrete steps, like a trackpoint with scrolling enabled.
+    Continuous,
+
+    /// A mouse wheel being tilted sideways.
+    WheelTilt,
+}
+
 /// Describes a difference in the mouse scroll wheel state.
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -1083,6 +1311,11 @@ impl SurfaceSizeWriter {
         Self { new_surface_size }
     }
 
+    #[cfg(feature = "serde")]
+    fn detached() -> Self {
+        Self::new(Weak::new())
+    }
+
     /// Try to request surface size which will be set synchronously on the window.
     pub fn request_surface_size(
         &mut self,
@@ -1117,6 +1350,7 @@ impl Eq for SurfaceSizeWriter {}
 #[cfg(test)]
 mod tests {
     use std::collections::{BTreeSet, HashSet};
+    use std::time::Duration;
 
     use dpi::PhysicalPosition;
 
@@ -1169,6 +1403,8 @@ mod tests {
                 device_id: None,
                 delta: event::MouseScrollDelta::LineDelta(0.0, 0.0),
                 phase: event::TouchPhase::Started,
+                source: event::ScrollSource::Wheel,
+                value120: Some((0, 120)),
             });
             with_window_event(PointerButton {
                 device_id: None,
@@ -1206,6 +1442,12 @@ mod tests {
             with_window_event(TouchpadPressure { device_id: None, pressure: 0.0, stage: 0 });
             with_window_event(ThemeChanged(crate::window::Theme::Light));
             with_window_event(Occluded(true));
+            with_window_event(ShortcutsInhibited(true));
+            with_window_event(FramePresented {
+                presented_at: Duration::ZERO,
+                refresh_interval: None,
+                flags: event::PresentationFlags::VSYNC,
+            });
         }};
         (device: $closure:expr) => {{
             use event::DeviceEvent::*;
@@ -2,12 +2,17 @@ pub mod pump_events;
 pub mod run_on_demand;
 
 use std::fmt::{self, Debug};
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, SendError, TryIter};
 use std::sync::Arc;
 #[cfg(not(web_platform))]
 use std::time::{Duration, Instant};
 
 use rwh_06::{DisplayHandle, HandleError, HasDisplayHandle};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 #[cfg(web_platform)]
 use web_time::{Duration, Instant};
 
@@ -90,6 +95,36 @@ pub trait ActiveEventLoop: AsAny + fmt::Debug {
     /// Gets the current [`ControlFlow`].
     fn control_flow(&self) -> ControlFlow;
 
+    /// Schedule a timer which calls [`ApplicationHandler::timer_fired`] once `deadline` is
+    /// reached.
+    ///
+    /// Scheduling a timer with the same [`TimerId`] again replaces its deadline. The timers are
+    /// independent from the [`ControlFlow`], the event loop wakes up for the earliest of them.
+    ///
+    /// [`ApplicationHandler::timer_fired`]: crate::application::ApplicationHandler::timer_fired
+    fn schedule_timer(&self, deadline: Instant, timer_id: TimerId);
+
+    /// Cancel a timer scheduled with [`schedule_timer()`][Self::schedule_timer].
+    ///
+    /// Does nothing if the timer already fired or was never scheduled.
+    fn cancel_timer(&self, timer_id: TimerId);
+
+    /// Run a future on the event loop thread.
+    ///
+    /// The future is polled in between the events delivered to the [`ApplicationHandler`], and
+    /// its waker can be used from any thread to wake up the event loop, without calling
+    /// [`ApplicationHandler::proxy_wake_up`]. The futures which didn't complete are dropped when
+    /// the event loop is.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** The future runs on the JavaScript microtask queue with
+    ///   `wasm_bindgen_futures::spawn_local`.
+    ///
+    /// [`ApplicationHandler`]: crate::application::ApplicationHandler
+    /// [`ApplicationHandler::proxy_wake_up`]: crate::application::ApplicationHandler::proxy_wake_up
+    fn spawn_local(&self, future: Pin<Box<dyn Future<Output = ()>>>);
+
     /// Stop the event loop.
     ///
     /// ## Platform-specific
@@ -155,6 +190,68 @@ impl EventLoopProxy {
     pub fn new(proxy: Arc<dyn EventLoopProxyProvider>) -> Self {
         Self { proxy }
     }
+
+    /// Create a channel to send user events of type `T` to the [`ActiveEventLoop`].
+    ///
+    /// Every event sent with [`UserEventSender::send`] wakes up the event loop, and is available
+    /// from the [`UserEventReceiver`] by the time [`ApplicationHandler::proxy_wake_up()`] is
+    /// called. The events from a single sender are received in the order they were sent.
+    ///
+    /// [`ApplicationHandler::proxy_wake_up()`]: crate::application::ApplicationHandler::proxy_wake_up
+    pub fn channel<T>(&self) -> (UserEventSender<T>, UserEventReceiver<T>) {
+        let (sender, receiver) = mpsc::channel();
+        (UserEventSender { sender, proxy: self.clone() }, UserEventReceiver { receiver })
+    }
+}
+
+/// The sending half of a channel created with [`EventLoopProxy::channel`].
+///
+/// This can be cloned and sent to other threads.
+#[derive(Debug)]
+pub struct UserEventSender<T> {
+    sender: mpsc::Sender<T>,
+    proxy: EventLoopProxy,
+}
+
+impl<T> UserEventSender<T> {
+    /// Send an event to the event loop, and wake it up.
+    ///
+    /// Returns the event back when the [`UserEventReceiver`] was dropped.
+    pub fn send(&self, event: T) -> Result<(), SendError<T>> {
+        self.sender.send(event)?;
+        // Wake up after the event is in the channel, so it's observed by `proxy_wake_up`.
+        self.proxy.wake_up();
+        Ok(())
+    }
+}
+
+impl<T> Clone for UserEventSender<T> {
+    fn clone(&self) -> Self {
+        Self { sender: self.sender.clone(), proxy: self.proxy.clone() }
+    }
+}
+
+/// The receiving half of a channel created with [`EventLoopProxy::channel`].
+///
+/// This should be drained from [`ApplicationHandler::proxy_wake_up()`], since the wake-ups of
+/// several events can be coalesced into a single call.
+///
+/// [`ApplicationHandler::proxy_wake_up()`]: crate::application::ApplicationHandler::proxy_wake_up
+#[derive(Debug)]
+pub struct UserEventReceiver<T> {
+    receiver: mpsc::Receiver<T>,
+}
+
+impl<T> UserEventReceiver<T> {
+    /// Take the next pending event, without blocking.
+    pub fn try_recv(&self) -> Option<T> {
+        self.receiver.try_recv().ok()
+    }
+
+    /// Iterate over the pending events, without blocking.
+    pub fn try_iter(&self) -> TryIter<'_, T> {
+        self.receiver.try_iter()
+    }
 }
 
 pub trait EventLoopProxyProvider: Send + Sync + Debug {
@@ -253,6 +350,25 @@ impl ControlFlow {
     }
 }
 
+/// Identifier of a timer scheduled with [`ActiveEventLoop::schedule_timer`].
+///
+/// The identifiers are chosen by the application.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TimerId(u64);
+
+impl TimerId {
+    /// Convert the `TimerId` into the underlying integer.
+    pub const fn into_raw(self) -> u64 {
+        self.0
+    }
+
+    /// Construct a `TimerId` from an integer chosen by the application.
+    pub const fn from_raw(id: u64) -> Self {
+        Self(id)
+    }
+}
+
 /// Control when device events are captured.
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -276,6 +392,7 @@ pub enum DeviceEvents {
 /// Then once event is arriving the working list is being traversed and a job
 /// executed and removed from the list.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct AsyncRequestSerial {
     serial: usize,
 }
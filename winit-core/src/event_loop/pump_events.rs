@@ -113,4 +113,6 @@ pub enum PumpStatus {
     Continue,
     /// Exit external loop.
     Exit(i32),
+    /// The connection to the display server was lost, exit external loop.
+    ConnectionLost,
 }
@@ -1732,6 +1732,40 @@ impl ModifiersState {
     }
 }
 
+bitflags! {
+    /// Represents the current state of the keyboard lock keys.
+    ///
+    /// Each flag represents a lock and is set if this lock is engaged, regardless of the
+    /// physical state of the corresponding key.
+    #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub struct LockKeysState: u8 {
+        /// The "Caps Lock" key.
+        const CAPS_LOCK = 0b001;
+        /// The "Num Lock" key.
+        const NUM_LOCK = 0b010;
+        /// The "Scroll Lock" key.
+        const SCROLL_LOCK = 0b100;
+    }
+}
+
+impl LockKeysState {
+    /// Returns whether Caps Lock is engaged.
+    pub fn caps_lock(&self) -> bool {
+        self.intersects(Self::CAPS_LOCK)
+    }
+
+    /// Returns whether Num Lock is engaged.
+    pub fn num_lock(&self) -> bool {
+        self.intersects(Self::NUM_LOCK)
+    }
+
+    /// Returns whether Scroll Lock is engaged.
+    pub fn scroll_lock(&self) -> bool {
+        self.intersects(Self::SCROLL_LOCK)
+    }
+}
+
 /// The logical state of the particular modifiers key.
 ///
 /// NOTE: while the modifier can only be in a binary active/inactive state, it might be helpful to
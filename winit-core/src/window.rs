@@ -19,6 +19,7 @@ use crate::monitor::{Fullscreen, MonitorHandle};
 /// Whenever you receive an event specific to a window, this event contains a `WindowId` which you
 /// can then compare to the ids of your windows.
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct WindowId(usize);
 
 impl WindowId {
@@ -596,12 +597,43 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     ///
     /// ## Platform-specific
     ///
-    /// - **Android / iOS / X11 / Web / Windows / macOS / Orbital:** Unsupported.
+    /// - **Android / iOS / Web / Windows / macOS / Orbital:** Unsupported.
     /// - **Wayland:** Schedules a frame callback to throttle [`WindowEvent::RedrawRequested`].
+    /// - **X11:** Starts a frame of the extended `_NET_WM_SYNC_REQUEST` protocol, the following
+    ///   [`WindowEvent::RedrawRequested`] are throttled until the window manager reports the frame
+    ///   as drawn.
     ///
     /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
     fn pre_present_notify(&self);
 
+    /// Enable or disable the [`WindowEvent::FramePresented`] events.
+    ///
+    /// The events are disabled by default.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Requires the `wp_presentation` protocol, the presentation feedback is
+    ///   requested by [`Window::pre_present_notify`].
+    /// - **X11:** Requires the Present extension.
+    /// - **Android / iOS / macOS / Orbital / Web / Windows:** Unsupported.
+    ///
+    /// [`WindowEvent::FramePresented`]: crate::event::WindowEvent::FramePresented
+    fn set_frame_presented_events(&self, enabled: bool) -> Result<(), RequestError>;
+
+    /// Hint the compositor about how to present the window.
+    ///
+    /// The hint applies to the frames presented afterwards.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Uses the `wp_tearing_control_v1` and `wp_content_type_v1` protocols, and
+    ///   returns an error when the hint needs one which isn't available, or for the surfaces
+    ///   adopted from the host application, which may already own those objects.
+    /// - **X11:** Asks the compositor to bypass compositing with `_NET_WM_BYPASS_COMPOSITOR` while
+    ///   the window is fullscreen, when tearing is allowed or the content is a video or a game.
+    /// - **Android / iOS / macOS / Orbital / Web / Windows:** Unsupported.
+    fn set_presentation_hint(&self, hint: PresentationHint) -> Result<(), RequestError>;
+
     /// Reset the dead key state of the keyboard.
     ///
     /// This is useful when a dead key is bound to trigger an action. Then
@@ -867,8 +899,8 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     ///
     /// - **macOS:** This will reset the window's background color.
     /// - **Web / iOS / Android:** Unsupported.
-    /// - **X11:** Can only be set while building the window, with
-    ///   [`WindowAttributes::with_transparent`].
+    /// - **X11:** The window must have been built with [`WindowAttributes::with_transparent`] to
+    ///   have an alpha channel, otherwise it stays opaque.
     fn set_transparent(&self, transparent: bool);
 
     /// Change the window blur state.
@@ -877,8 +909,10 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     ///
     /// ## Platform-specific
     ///
-    /// - **Android / iOS / X11 / Web / Windows:** Unsupported.
+    /// - **Android / iOS / Web / Windows:** Unsupported.
     /// - **Wayland:** Only works with org_kde_kwin_blur_manager protocol.
+    /// - **X11:** Only works with compositors supporting `_KDE_NET_WM_BLUR_BEHIND_REGION`, and is
+    ///   only visible on windows built with [`WindowAttributes::with_transparent`].
     fn set_blur(&self, blur: bool);
 
     /// Modifies the window's visibility.
@@ -930,7 +964,11 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     ///
     /// ## Platform-specific
     ///
-    /// - **Wayland / X11 / Orbital:** Not implemented.
+    /// - **X11:** Relies on the window manager honoring the Motif hints. The maximize button is
+    ///   also disabled when the window isn't resizable.
+    /// - **Wayland:** Only applies to client-side decorations. The close button is always shown,
+    ///   but has no effect when disabled.
+    /// - **Orbital:** Not implemented.
     /// - **Web / iOS / Android:** Unsupported.
     fn set_enabled_buttons(&self, buttons: WindowButtons);
 
@@ -938,7 +976,11 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     ///
     /// ## Platform-specific
     ///
-    /// - **Wayland / X11 / Orbital:** Not implemented. Always returns [`WindowButtons::all`].
+    /// - **X11:** The maximize button is never reported when the window isn't resizable, nor the
+    ///   buttons for the actions missing from `_NET_WM_ALLOWED_ACTIONS` when the window manager
+    ///   sets it.
+    /// - **Wayland:** The buttons the compositor doesn't support are never reported.
+    /// - **Orbital:** Not implemented. Always returns [`WindowButtons::all`].
     /// - **Web / iOS / Android:** Unsupported. Always returns [`WindowButtons::all`].
     fn enabled_buttons(&self) -> WindowButtons;
 
@@ -1039,6 +1081,33 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     /// See [`WindowLevel`] for details.
     fn set_window_level(&self, level: WindowLevel);
 
+    /// Moves the window to the workspace, also known as virtual desktop, at the given index.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Requires a window manager supporting `_NET_WM_DESKTOP`.
+    /// - **Android / iOS / macOS / Orbital / Wayland / Web / Windows:** Always returns an
+    ///   [`RequestError::NotSupported`].
+    fn set_workspace(&self, workspace: u32) -> Result<(), RequestError>;
+
+    /// Returns the index of the workspace the window is on.
+    ///
+    /// Returns `None` when the window is on all the workspaces, see [`Window::set_sticky`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android / iOS / macOS / Orbital / Wayland / Web / Windows:** Always returns `None`.
+    fn workspace(&self) -> Option<u32>;
+
+    /// Pins the window to all the workspaces.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Requires a window manager supporting `_NET_WM_STATE_STICKY`.
+    /// - **Android / iOS / macOS / Orbital / Wayland / Web / Windows:** Always returns an
+    ///   [`RequestError::NotSupported`].
+    fn set_sticky(&self, sticky: bool) -> Result<(), RequestError>;
+
     /// Sets the window icon.
     ///
     /// On Windows and X11, this is typically the small icon in the top-left
@@ -1127,6 +1196,28 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     /// - **iOS / Android / Web / Windows / X11 / macOS / Orbital:** Unsupported.
     fn set_ime_purpose(&self, purpose: ImePurpose);
 
+    /// Sets the text surrounding the IME cursor.
+    ///
+    /// Input methods use it as a context, for example to compose Korean or Thai syllables with the
+    /// already typed characters, or to offer predictions on on-screen keyboards. The `text` should
+    /// be the paragraph or the line being edited without the preedit, while `cursor` and `anchor`
+    /// are byte offsets into it, with `anchor` being equal to `cursor` when nothing is selected.
+    /// Offsets not lying on a char boundary are ignored.
+    ///
+    /// The surrounding text should be updated whenever the text or the cursor changes while IME
+    /// is enabled. The input method could request to delete parts of it with
+    /// [`Ime::DeleteSurrounding`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Long text is trimmed around the cursor to fit into the protocol limits.
+    /// - **X11:** Best effort, only provided to the input methods using XIM string conversion. The
+    ///   `anchor` is ignored.
+    /// - **Android / iOS / macOS / Orbital / Web / Windows:** Unsupported.
+    ///
+    /// [`Ime::DeleteSurrounding`]: crate::event::Ime::DeleteSurrounding
+    fn set_ime_surrounding_text(&self, text: &str, cursor: usize, anchor: usize);
+
     /// Brings the window to the front and sets input focus. Has no effect if the window is
     /// already in focus, minimized, or not visible.
     ///
@@ -1186,15 +1277,29 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
 
     /// Prevents the window contents from being captured by other apps.
     ///
+    /// Use [`Window::is_content_protected`] to know whether the protection is active.
+    ///
     /// ## Platform-specific
     ///
     /// - **macOS**: if `false`, [`NSWindowSharingNone`] is used but doesn't completely prevent all
     ///   apps from reading the window content, for instance, QuickTime.
-    /// - **iOS / Android / x11 / Wayland / Web / Orbital:** Unsupported.
+    /// - **X11 / Wayland:** Unsupported, neither the X server nor the compositors provide a way to
+    ///   exclude a window from being captured.
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
     ///
     /// [`NSWindowSharingNone`]: https://developer.apple.com/documentation/appkit/nswindowsharingtype/nswindowsharingnone
     fn set_content_protected(&self, protected: bool);
 
+    /// Returns whether the window contents are protected from being captured by other apps.
+    ///
+    /// This is `false` when the protection was requested with [`Window::set_content_protected`]
+    /// but the platform doesn't support it.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / X11 / Wayland / Web / Orbital:** Always returns `false`.
+    fn is_content_protected(&self) -> bool;
+
     /// Gets the current title of the window.
     ///
     /// ## Platform-specific
@@ -1291,11 +1396,19 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     /// This is the context menu that is normally shown when interacting with
     /// the title bar. This is useful when implementing custom decorations.
     ///
+    /// Returns an error when no menu could be shown.
+    ///
     /// ## Platform-specific
-    /// **Android / iOS / macOS / Orbital / Wayland / Web / X11:** Unsupported.
+    ///
+    /// - **X11:** Requires a window manager supporting `_GTK_SHOW_WINDOW_MENU`, otherwise returns
+    ///   [`RequestError::NotSupported`].
+    /// - **Wayland:** Returns [`RequestError::NotSupported`] when the compositor doesn't provide a
+    ///   window menu, and [`RequestError::Ignored`] when no pointer is over the window.
+    /// - **Android / iOS / macOS / Orbital / Web:** Always returns an
+    ///   [`RequestError::NotSupported`].
     ///
     /// [window menu]: https://en.wikipedia.org/wiki/Common_menus_in_Microsoft_Windows#System_menu
-    fn show_window_menu(&self, position: Position);
+    fn show_window_menu(&self, position: Position) -> Result<(), RequestError>;
 
     /// Modifies whether the window catches cursor events.
     ///
@@ -1308,6 +1421,58 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     /// - **iOS / Android / Web / Orbital:** Always returns an [`RequestError::NotSupported`].
     fn set_cursor_hittest(&self, hittest: bool) -> Result<(), RequestError>;
 
+    /// Restricts the pointer and touch input to the given rectangles of the surface.
+    ///
+    /// The input outside of the region is passed through the window to the ones behind it, which
+    /// is useful for overlays and irregularly shaped windows. `None` resets the region to the
+    /// whole surface. Disabling the hittest with [`Window::set_cursor_hittest`] takes precedence
+    /// over the region.
+    ///
+    /// The region doesn't mark parts of the surface as the caption or the resize edges of
+    /// undecorated windows, call [`Window::drag_window`] or [`Window::drag_resize_window`] from the
+    /// pointer button events over those parts instead.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android / iOS / macOS / Orbital / Web / Windows:** Always returns an
+    ///   [`RequestError::NotSupported`].
+    fn set_input_region(&self, region: Option<&[(Position, Size)]>) -> Result<(), RequestError>;
+
+    /// Hints the compositor that the given rectangles of the surface are opaque.
+    ///
+    /// The compositor can skip drawing what's behind the opaque region. `None` restores the
+    /// default, where the whole surface is opaque unless the window is transparent, see
+    /// [`Window::set_transparent`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Only applies to windows created with an alpha channel, the others are always
+    ///   opaque.
+    /// - **Android / iOS / macOS / Orbital / Web / Windows:** Always returns an
+    ///   [`RequestError::NotSupported`].
+    fn set_opaque_region(&self, region: Option<&[(Position, Size)]>) -> Result<(), RequestError>;
+
+    /// Inhibits the system keyboard shortcuts while the window is focused.
+    ///
+    /// When inhibited, key combinations normally handled by the system or the compositor, like
+    /// <kbd>Alt</kbd>+<kbd>Tab</kbd> or <kbd>Super</kbd>, are delivered to the window instead,
+    /// which is useful for remote desktop clients or virtual machine viewers.
+    ///
+    /// The system could refuse the request or revoke it at any time, thus
+    /// [`WindowEvent::ShortcutsInhibited`] is emitted to report whether the inhibition is actually
+    /// active. The request persists while the window is unfocused and is applied again once it
+    /// gains focus.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Requires the `zwp_keyboard_shortcuts_inhibit_manager_v1` protocol.
+    /// - **X11:** Implemented by grabbing the keyboard while the window is focused.
+    /// - **Android / iOS / macOS / Orbital / Web / Windows:** Always returns an
+    ///   [`RequestError::NotSupported`].
+    ///
+    /// [`WindowEvent::ShortcutsInhibited`]: crate::event::WindowEvent::ShortcutsInhibited
+    fn set_shortcuts_inhibited(&self, inhibited: bool) -> Result<(), RequestError>;
+
     /// Returns the monitor on which the window currently resides.
     ///
     /// Returns `None` if current monitor can't be detected.
@@ -1396,8 +1561,12 @@ pub enum CursorGrabMode {
     ///
     /// ## Platform-specific
     ///
-    /// - **X11:** Not implemented. Always returns [`RequestError::NotSupported`] for now.
+    /// - **X11:** The cursor is confined to the window, hidden and moved back to the center of the
+    ///   window whenever it moves, and restored to its original position when unlocked. Use
+    ///   [`DeviceEvent::PointerMotion`] to track the motion.
     /// - **iOS / Android:** Always returns an [`RequestError::NotSupported`].
+    ///
+    /// [`DeviceEvent::PointerMotion`]: crate::event::DeviceEvent::PointerMotion
     Locked,
 }
 
@@ -1500,6 +1669,36 @@ pub enum WindowLevel {
     AlwaysOnTop,
 }
 
+/// Hints the compositor about how to present the window, for use in
+/// [`Window::set_presentation_hint`].
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PresentationHint {
+    /// Present the frames as soon as possible, even if that causes tearing, to reduce the latency.
+    pub allow_tearing: bool,
+    /// The kind of content shown in the window.
+    pub content_type: ContentType,
+}
+
+/// The kind of content shown in a window, which the compositor may use to adjust the
+/// presentation, like the scaling or the refresh rate.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ContentType {
+    /// No specific kind of content.
+    #[default]
+    None,
+
+    /// Still pictures, which benefit from an accurate presentation.
+    Photo,
+
+    /// Moving pictures, which benefit from a stable frame rate.
+    Video,
+
+    /// Interactive content, which benefits from a low latency.
+    Game,
+}
+
 /// Generic IME purposes for use in [`Window::set_ime_purpose`].
 ///
 /// The purpose may improve UX by optimizing the IME for the specific use case,
@@ -1532,6 +1731,7 @@ impl Default for ImePurpose {
 ///
 /// [`Window`]: crate::window::Window
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ActivationToken {
     pub(crate) token: String,
 }
@@ -0,0 +1,561 @@
+use std::cell::Cell;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+#[cfg(unix)]
+use std::io::{Read, Write};
+#[cfg(unix)]
+use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, RawFd};
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+use std::pin::Pin;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use dpi::PhysicalSize;
+use winit_common::executor::Executor;
+use winit_common::timers::Timers;
+use winit_core::application::ApplicationHandler;
+use winit_core::cursor::{CustomCursor, CustomCursorSource};
+use winit_core::error::{EventLoopError, NotSupportedError, RequestError};
+use winit_core::event::{DeviceEvent, DeviceId, StartCause, SurfaceSizeWriter, WindowEvent};
+use winit_core::event_loop::pump_events::PumpStatus;
+use winit_core::event_loop::{
+    ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEvents,
+    EventLoopProxy as CoreEventLoopProxy, EventLoopProxyProvider,
+    OwnedDisplayHandle as CoreOwnedDisplayHandle, TimerId,
+};
+use winit_core::monitor::MonitorHandle as CoreMonitorHandle;
+use winit_core::window::{Theme, Window as CoreWindow, WindowAttributes, WindowId};
+
+use crate::monitor::MonitorHandle;
+use crate::simulator::Simulator;
+use crate::window::{Window, WindowState};
+
+/// An event waiting to be dispatched to the application.
+#[derive(Debug)]
+pub(crate) enum QueuedEvent {
+    Window(WindowId, WindowEvent),
+    Device(Option<DeviceId>, DeviceEvent),
+    ScaleFactor(WindowId, f64),
+}
+
+/// The state shared between the event loop, the windows and the simulator.
+#[derive(Debug, Default)]
+pub(crate) struct State {
+    pub(crate) windows: HashMap<WindowId, WindowState>,
+    pub(crate) monitors: Vec<Arc<MonitorHandle>>,
+    pub(crate) events: VecDeque<QueuedEvent>,
+    pub(crate) redraws: Vec<WindowId>,
+    pub(crate) proxy_wake_up: bool,
+    pub(crate) executor_wake_up: bool,
+    pub(crate) next_window_id: usize,
+    pub(crate) next_monitor_id: u128,
+}
+
+impl State {
+    fn has_pending(&self) -> bool {
+        !self.events.is_empty()
+            || !self.redraws.is_empty()
+            || self.proxy_wake_up
+            || self.executor_wake_up
+    }
+
+    pub(crate) fn primary_monitor(&self) -> Option<CoreMonitorHandle> {
+        self.monitors.first().map(|monitor| CoreMonitorHandle(monitor.clone()))
+    }
+
+    pub(crate) fn available_monitors(&self) -> Vec<CoreMonitorHandle> {
+        self.monitors.iter().map(|monitor| CoreMonitorHandle(monitor.clone())).collect()
+    }
+
+    /// Queue a window event, dropping it if the window is already gone.
+    pub(crate) fn push_window_event(&mut self, window_id: WindowId, event: WindowEvent) {
+        if self.windows.contains_key(&window_id) {
+            self.events.push_back(QueuedEvent::Window(window_id, event));
+        }
+    }
+
+    pub(crate) fn push_redraw(&mut self, window_id: WindowId) {
+        if !self.redraws.contains(&window_id) {
+            self.redraws.push(window_id);
+        }
+    }
+
+    /// Move the focus to the given window, or clear it.
+    pub(crate) fn set_focus(&mut self, focused: Option<WindowId>) {
+        let mut events = Vec::new();
+        for (&window_id, window) in self.windows.iter_mut() {
+            let has_focus = Some(window_id) == focused;
+            if window.focused != has_focus {
+                window.focused = has_focus;
+                events.push((window_id, WindowEvent::Focused(has_focus)));
+            }
+        }
+
+        // Send the unfocus before the focus.
+        events.sort_by_key(|(_, event)| *event == WindowEvent::Focused(true));
+        for (window_id, event) in events {
+            self.events.push_back(QueuedEvent::Window(window_id, event));
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct Shared {
+    state: Mutex<State>,
+    condvar: Condvar,
+    #[cfg(unix)]
+    waker: (UnixStream, UnixStream),
+}
+
+impl Shared {
+    fn new() -> Result<Self, EventLoopError> {
+        #[cfg(unix)]
+        let waker = {
+            let (reader, writer) = UnixStream::pair().map_err(|err| os_error!(err))?;
+            reader.set_nonblocking(true).map_err(|err| os_error!(err))?;
+            writer.set_nonblocking(true).map_err(|err| os_error!(err))?;
+            (reader, writer)
+        };
+
+        Ok(Self {
+            state: Mutex::new(State::default()),
+            condvar: Condvar::new(),
+            #[cfg(unix)]
+            waker,
+        })
+    }
+
+    pub(crate) fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap()
+    }
+
+    /// Wake up the event loop, after modifying the state.
+    pub(crate) fn wake_up(&self) {
+        self.condvar.notify_all();
+        // The event loop is already going to wake up when the socket is full.
+        #[cfg(unix)]
+        let _ = (&self.waker.1).write(&[0]);
+    }
+
+    /// Block until there's something to dispatch, or until the timeout expires.
+    fn wait(&self, timeout: Option<Duration>) {
+        let state = self.state();
+        let condition = |state: &mut State| !state.has_pending();
+        match timeout {
+            Some(timeout) => drop(self.condvar.wait_timeout_while(state, timeout, condition)),
+            None => drop(self.condvar.wait_while(state, condition)),
+        }
+    }
+
+    fn clear_wake_up(&self) {
+        #[cfg(unix)]
+        {
+            let mut buf = [0; 64];
+            while matches!((&self.waker.0).read(&mut buf), Ok(len) if len > 0) {}
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct EventLoop {
+    loop_running: bool,
+    window_target: ActiveEventLoop,
+}
+
+impl EventLoop {
+    pub fn new() -> Result<Self, EventLoopError> {
+        let shared = Arc::new(Shared::new()?);
+        let proxy = CoreEventLoopProxy::new(Arc::new(EventLoopProxy { shared: shared.clone() }));
+        let executor_proxy =
+            CoreEventLoopProxy::new(Arc::new(ExecutorWaker { shared: shared.clone() }));
+
+        Ok(Self {
+            loop_running: false,
+            window_target: ActiveEventLoop {
+                shared,
+                proxy,
+                executor_proxy,
+                control_flow: Cell::new(ControlFlow::default()),
+                exit: Cell::new(None),
+                device_events: Cell::new(DeviceEvents::default()),
+                timers: Timers::default(),
+                executor: Executor::default(),
+            },
+        })
+    }
+
+    /// Returns the [`Simulator`] to script this event loop.
+    pub fn simulator(&self) -> Simulator {
+        self.window_target.simulator()
+    }
+
+    pub fn run_app<A: ApplicationHandler>(mut self, app: A) -> Result<(), EventLoopError> {
+        self.run_app_on_demand(app)
+    }
+
+    pub fn run_app_on_demand<A: ApplicationHandler>(
+        &mut self,
+        mut app: A,
+    ) -> Result<(), EventLoopError> {
+        self.window_target.clear_exit();
+        loop {
+            match self.pump_app_events(None, &mut app) {
+                PumpStatus::Exit(0) => break Ok(()),
+                PumpStatus::Exit(code) => break Err(EventLoopError::ExitFailure(code)),
+                PumpStatus::Continue => continue,
+                // There's no display server to lose the connection to.
+                PumpStatus::ConnectionLost => unreachable!(),
+            }
+        }
+    }
+
+    pub fn pump_app_events<A: ApplicationHandler>(
+        &mut self,
+        timeout: Option<Duration>,
+        mut app: A,
+    ) -> PumpStatus {
+        if !self.loop_running {
+            self.loop_running = true;
+
+            // Run the initial loop iteration.
+            self.single_iteration(&mut app, StartCause::Init);
+        }
+
+        // Consider the possibility that the `StartCause::Init` iteration could
+        // request to Exit.
+        if !self.window_target.exiting() {
+            self.poll_events_with_timeout(timeout, &mut app);
+        }
+
+        if let Some(code) = self.window_target.exit.get() {
+            self.loop_running = false;
+            PumpStatus::Exit(code)
+        } else {
+            PumpStatus::Continue
+        }
+    }
+
+    pub fn window_target(&self) -> &dyn RootActiveEventLoop {
+        &self.window_target
+    }
+
+    fn poll_events_with_timeout<A: ApplicationHandler>(
+        &mut self,
+        timeout: Option<Duration>,
+        app: &mut A,
+    ) {
+        let start = Instant::now();
+        let control_flow =
+            self.window_target.timers.control_flow(self.window_target.control_flow());
+        let control_flow_timeout = match control_flow {
+            ControlFlow::Wait => None,
+            ControlFlow::Poll => Some(Duration::ZERO),
+            ControlFlow::WaitUntil(deadline) => Some(deadline.saturating_duration_since(start)),
+        };
+        let timeout = match (control_flow_timeout, timeout) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+
+        self.window_target.shared.wait(timeout);
+
+        let cause = match self.window_target.control_flow() {
+            ControlFlow::Poll => StartCause::Poll,
+            ControlFlow::Wait => StartCause::WaitCancelled { start, requested_resume: None },
+            ControlFlow::WaitUntil(deadline) => {
+                if Instant::now() < deadline {
+                    StartCause::WaitCancelled { start, requested_resume: Some(deadline) }
+                } else {
+                    StartCause::ResumeTimeReached { start, requested_resume: deadline }
+                }
+            },
+        };
+
+        // Don't run an iteration when we timed out without anything to do.
+        if !self.window_target.shared.state().has_pending()
+            && !self.window_target.timers.has_expired()
+            && !matches!(cause, StartCause::ResumeTimeReached { .. } | StartCause::Poll)
+        {
+            return;
+        }
+
+        self.single_iteration(app, cause);
+    }
+
+    fn single_iteration<A: ApplicationHandler>(&mut self, app: &mut A, cause: StartCause) {
+        let target = &self.window_target;
+        target.shared.clear_wake_up();
+
+        app.new_events(target, cause);
+
+        if cause == StartCause::Init {
+            app.can_create_surfaces(target);
+        }
+
+        // The lock is not held while dispatching, so that the application can use its windows.
+        loop {
+            let event = target.shared.state().events.pop_front();
+            match event {
+                Some(QueuedEvent::Window(window_id, event)) => {
+                    if target.apply_window_event(window_id, &event) {
+                        app.window_event(target, window_id, event);
+                    }
+                },
+                Some(QueuedEvent::Device(device_id, event)) => {
+                    if target.device_events_allowed() {
+                        app.device_event(target, device_id, event);
+                    }
+                },
+                Some(QueuedEvent::ScaleFactor(window_id, scale_factor)) => {
+                    target.dispatch_scale_factor(app, window_id, scale_factor);
+                },
+                None => break,
+            }
+        }
+
+        if std::mem::take(&mut target.shared.state().proxy_wake_up) {
+            app.proxy_wake_up(target);
+        }
+
+        target.timers.dispatch(app, target);
+
+        // Poll the futures which were woken up.
+        target.shared.state().executor_wake_up = false;
+        target.executor.poll();
+
+        let redraws = std::mem::take(&mut target.shared.state().redraws);
+        for window_id in redraws {
+            app.window_event(target, window_id, WindowEvent::RedrawRequested);
+        }
+
+        // This is always the last event we dispatch before waiting again.
+        app.about_to_wait(target);
+    }
+}
+
+#[cfg(unix)]
+impl AsFd for EventLoop {
+    /// The file descriptor becomes readable when the event loop is woken up.
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.window_target.shared.waker.0.as_fd()
+    }
+}
+
+#[cfg(unix)]
+impl AsRawFd for EventLoop {
+    fn as_raw_fd(&self) -> RawFd {
+        self.window_target.shared.waker.0.as_raw_fd()
+    }
+}
+
+#[derive(Debug)]
+pub struct EventLoopProxy {
+    shared: Arc<Shared>,
+}
+
+impl EventLoopProxyProvider for EventLoopProxy {
+    fn wake_up(&self) {
+        self.shared.state().proxy_wake_up = true;
+        self.shared.wake_up();
+    }
+}
+
+/// Wakes up the event loop for the futures, without emitting [`ApplicationHandler::proxy_wake_up`].
+#[derive(Debug)]
+struct ExecutorWaker {
+    shared: Arc<Shared>,
+}
+
+impl EventLoopProxyProvider for ExecutorWaker {
+    fn wake_up(&self) {
+        self.shared.state().executor_wake_up = true;
+        self.shared.wake_up();
+    }
+}
+
+#[derive(Debug)]
+pub struct ActiveEventLoop {
+    pub(crate) shared: Arc<Shared>,
+    proxy: CoreEventLoopProxy,
+    executor_proxy: CoreEventLoopProxy,
+    control_flow: Cell<ControlFlow>,
+    exit: Cell<Option<i32>>,
+    device_events: Cell<DeviceEvents>,
+    timers: Timers,
+    executor: Executor,
+}
+
+impl ActiveEventLoop {
+    /// Returns the [`Simulator`] to script this event loop.
+    pub fn simulator(&self) -> Simulator {
+        Simulator { shared: self.shared.clone() }
+    }
+
+    fn clear_exit(&self) {
+        self.exit.set(None)
+    }
+
+    fn device_events_allowed(&self) -> bool {
+        match self.device_events.get() {
+            DeviceEvents::Always => true,
+            DeviceEvents::WhenFocused => {
+                self.shared.state().windows.values().any(|window| window.focused)
+            },
+            DeviceEvents::Never => false,
+        }
+    }
+
+    /// Reflect the event in the window state, returns `false` if the window is gone.
+    fn apply_window_event(&self, window_id: WindowId, event: &WindowEvent) -> bool {
+        let mut state = self.shared.state();
+        if *event == WindowEvent::Focused(true) {
+            // Only a single window can be focused at a time.
+            for (&id, window) in state.windows.iter_mut() {
+                window.focused = id == window_id;
+            }
+        }
+
+        let window = match state.windows.get_mut(&window_id) {
+            Some(window) => window,
+            // `Destroyed` is sent after the window was dropped.
+            None => return *event == WindowEvent::Destroyed,
+        };
+
+        match *event {
+            WindowEvent::SurfaceResized(size) => window.surface_size = size,
+            WindowEvent::Moved(position) => window.outer_position = position,
+            WindowEvent::Focused(focused) => window.focused = focused,
+            WindowEvent::Occluded(occluded) => window.occluded = occluded,
+            WindowEvent::ThemeChanged(theme) => window.theme = Some(theme),
+            WindowEvent::ShortcutsInhibited(inhibited) => window.shortcuts_inhibited = inhibited,
+            _ => (),
+        }
+
+        true
+    }
+
+    fn dispatch_scale_factor<A: ApplicationHandler>(
+        &self,
+        app: &mut A,
+        window_id: WindowId,
+        scale_factor: f64,
+    ) {
+        let (old_surface_size, new_surface_size) = {
+            let mut state = self.shared.state();
+            let window = match state.windows.get_mut(&window_id) {
+                Some(window) => window,
+                None => return,
+            };
+
+            // Keep the logical size, as most platforms do.
+            let old_surface_size = window.surface_size;
+            let logical_size = old_surface_size.to_logical::<f64>(window.scale_factor);
+            window.scale_factor = scale_factor;
+            window.surface_size = logical_size.to_physical(scale_factor);
+            (old_surface_size, window.surface_size)
+        };
+
+        let new_surface_size = Arc::new(Mutex::new(new_surface_size));
+        let event = WindowEvent::ScaleFactorChanged {
+            scale_factor,
+            surface_size_writer: SurfaceSizeWriter::new(Arc::downgrade(&new_surface_size)),
+        };
+        app.window_event(self, window_id, event);
+
+        let new_surface_size: PhysicalSize<u32> = *new_surface_size.lock().unwrap();
+        if let Some(window) = self.shared.state().windows.get_mut(&window_id) {
+            window.surface_size = new_surface_size;
+        } else {
+            return;
+        }
+
+        if new_surface_size != old_surface_size {
+            app.window_event(self, window_id, WindowEvent::SurfaceResized(new_surface_size));
+        }
+    }
+}
+
+impl RootActiveEventLoop for ActiveEventLoop {
+    fn create_proxy(&self) -> CoreEventLoopProxy {
+        self.proxy.clone()
+    }
+
+    fn create_window(
+        &self,
+        window_attributes: WindowAttributes,
+    ) -> Result<Box<dyn CoreWindow>, RequestError> {
+        Ok(Box::new(Window::new(self, window_attributes)))
+    }
+
+    fn create_custom_cursor(&self, _: CustomCursorSource) -> Result<CustomCursor, RequestError> {
+        Err(NotSupportedError::new("create_custom_cursor is not supported").into())
+    }
+
+    fn available_monitors(&self) -> Box<dyn Iterator<Item = CoreMonitorHandle>> {
+        Box::new(self.shared.state().available_monitors().into_iter())
+    }
+
+    fn primary_monitor(&self) -> Option<CoreMonitorHandle> {
+        self.shared.state().primary_monitor()
+    }
+
+    fn listen_device_events(&self, allowed: DeviceEvents) {
+        self.device_events.set(allowed);
+    }
+
+    fn system_theme(&self) -> Option<Theme> {
+        None
+    }
+
+    fn set_control_flow(&self, control_flow: ControlFlow) {
+        self.control_flow.set(control_flow)
+    }
+
+    fn control_flow(&self) -> ControlFlow {
+        self.control_flow.get()
+    }
+
+    fn schedule_timer(&self, deadline: Instant, timer_id: TimerId) {
+        self.timers.schedule(deadline, timer_id)
+    }
+
+    fn cancel_timer(&self, timer_id: TimerId) {
+        self.timers.cancel(timer_id)
+    }
+
+    fn spawn_local(&self, future: Pin<Box<dyn Future<Output = ()>>>) {
+        self.executor.spawn(future, self.executor_proxy.clone())
+    }
+
+    fn exit(&self) {
+        self.exit.set(Some(0))
+    }
+
+    fn exiting(&self) -> bool {
+        self.exit.get().is_some()
+    }
+
+    fn owned_display_handle(&self) -> CoreOwnedDisplayHandle {
+        CoreOwnedDisplayHandle::new(Arc::new(OwnedDisplayHandle))
+    }
+
+    fn rwh_06_handle(&self) -> &dyn rwh_06::HasDisplayHandle {
+        self
+    }
+}
+
+impl rwh_06::HasDisplayHandle for ActiveEventLoop {
+    fn display_handle(&self) -> Result<rwh_06::DisplayHandle<'_>, rwh_06::HandleError> {
+        Err(rwh_06::HandleError::NotSupported)
+    }
+}
+
+#[derive(Clone)]
+struct OwnedDisplayHandle;
+
+impl rwh_06::HasDisplayHandle for OwnedDisplayHandle {
+    fn display_handle(&self) -> Result<rwh_06::DisplayHandle<'_>, rwh_06::HandleError> {
+        Err(rwh_06::HandleError::NotSupported)
+    }
+}
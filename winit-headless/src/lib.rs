@@ -0,0 +1,84 @@
+//! # Headless
+//!
+//! An in-memory backend that doesn't connect to any display server, meant for driving
+//! [`ApplicationHandler`] implementations from automated tests.
+//!
+//! Windows created on this backend only record the requests made to them, and events are only
+//! delivered when injected through a [`Simulator`]. The [`Simulator`] also allows to simulate
+//! monitors and scale factor changes, and to inspect the [`WindowState`] of every window.
+//!
+//! ```
+//! # use winit_core::application::ApplicationHandler;
+//! # use winit_core::event::WindowEvent;
+//! # use winit_core::event_loop::pump_events::PumpStatus;
+//! # use winit_core::event_loop::ActiveEventLoop;
+//! # use winit_core::window::{Window, WindowAttributes, WindowId};
+//! # #[derive(Default)]
+//! # struct App { window: Option<Box<dyn Window>> }
+//! # impl ApplicationHandler for App {
+//! #     fn can_create_surfaces(&mut self, event_loop: &dyn ActiveEventLoop) {
+//! #         self.window = event_loop.create_window(WindowAttributes::default()).ok();
+//! #     }
+//! #     fn window_event(&mut self, event_loop: &dyn ActiveEventLoop, _: WindowId, event: WindowEvent) {
+//! #         if event == WindowEvent::CloseRequested {
+//! #             event_loop.exit();
+//! #         }
+//! #     }
+//! # }
+//! use std::time::Duration;
+//!
+//! let mut event_loop = winit_headless::EventLoop::new().unwrap();
+//! let simulator = event_loop.simulator();
+//! let mut app = App::default();
+//!
+//! // The first iteration creates the window.
+//! event_loop.pump_app_events(Some(Duration::ZERO), &mut app);
+//! let window_id = simulator.window_ids()[0];
+//! assert_eq!(simulator.window_state(window_id).unwrap().title, "winit window");
+//!
+//! simulator.send_window_event(window_id, WindowEvent::CloseRequested);
+//! let status = event_loop.pump_app_events(Some(Duration::ZERO), &mut app);
+//! assert_eq!(status, PumpStatus::Exit(0));
+//! ```
+//!
+//! [`ApplicationHandler`]: winit_core::application::ApplicationHandler
+
+pub use self::event_loop::{ActiveEventLoop, EventLoop, EventLoopProxy};
+pub use self::monitor::MonitorHandle;
+pub use self::simulator::Simulator;
+pub use self::window::{Window, WindowState};
+
+macro_rules! os_error {
+    ($error:expr) => {{
+        winit_core::error::OsError::new(line!(), file!(), $error)
+    }};
+}
+
+mod event_loop;
+mod monitor;
+mod simulator;
+mod window;
+
+/// Additional methods on [`EventLoop`] that are specific to the headless backend.
+///
+/// [`EventLoop`]: winit_core::event_loop
+pub trait EventLoopExtHeadless {
+    /// True if the [`EventLoop`] uses the headless backend.
+    ///
+    /// [`EventLoop`]: winit_core::event_loop
+    fn is_headless(&self) -> bool;
+
+    /// Returns the [`Simulator`] driving the event loop, if it uses the headless backend.
+    fn simulator(&self) -> Option<Simulator>;
+}
+
+/// Additional methods on [`EventLoopBuilder`] that are specific to the headless backend.
+///
+/// [`EventLoopBuilder`]: winit_core::event_loop
+pub trait EventLoopBuilderExtHeadless {
+    /// Force using the headless backend.
+    ///
+    /// Unlike the other backends, the headless event loop can be created on any thread, and any
+    /// number of times, e.g. once per test.
+    fn with_headless(&mut self) -> &mut Self;
+}
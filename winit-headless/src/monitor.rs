@@ -0,0 +1,57 @@
+use std::borrow::Cow;
+use std::iter;
+
+use dpi::{PhysicalPosition, PhysicalSize};
+use winit_core::monitor::{MonitorHandleProvider, VideoMode};
+
+/// A simulated monitor, added with [`Simulator::add_monitor`].
+///
+/// [`Simulator::add_monitor`]: crate::Simulator::add_monitor
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitorHandle {
+    pub(crate) id: u128,
+    pub(crate) name: String,
+    pub(crate) position: PhysicalPosition<i32>,
+    pub(crate) video_mode: VideoMode,
+    pub(crate) scale_factor: f64,
+}
+
+impl MonitorHandle {
+    /// Whether the point lies within the bounds of this monitor.
+    pub(crate) fn contains(&self, point: PhysicalPosition<i32>) -> bool {
+        let PhysicalSize { width, height } = self.video_mode.size();
+        let x = i64::from(point.x) - i64::from(self.position.x);
+        let y = i64::from(point.y) - i64::from(self.position.y);
+        (0..i64::from(width)).contains(&x) && (0..i64::from(height)).contains(&y)
+    }
+}
+
+impl MonitorHandleProvider for MonitorHandle {
+    fn id(&self) -> u128 {
+        self.id
+    }
+
+    fn native_id(&self) -> u64 {
+        self.id as u64
+    }
+
+    fn name(&self) -> Option<Cow<'_, str>> {
+        Some(Cow::Borrowed(&self.name))
+    }
+
+    fn position(&self) -> Option<PhysicalPosition<i32>> {
+        Some(self.position)
+    }
+
+    fn scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+
+    fn current_video_mode(&self) -> Option<VideoMode> {
+        Some(self.video_mode)
+    }
+
+    fn video_modes(&self) -> Box<dyn Iterator<Item = VideoMode>> {
+        Box::new(iter::once(self.video_mode))
+    }
+}
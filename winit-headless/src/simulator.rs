@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use dpi::PhysicalPosition;
+use winit_core::event::{DeviceEvent, DeviceId, WindowEvent};
+use winit_core::monitor::{MonitorHandle as CoreMonitorHandle, VideoMode};
+use winit_core::window::WindowId;
+
+use crate::event_loop::{QueuedEvent, Shared};
+use crate::monitor::MonitorHandle;
+use crate::window::WindowState;
+
+/// Injects events into a headless event loop, and inspects its windows.
+///
+/// The simulator can be cloned and sent to other threads, the events are dispatched by the event
+/// loop in the order they were sent.
+#[derive(Debug, Clone)]
+pub struct Simulator {
+    pub(crate) shared: Arc<Shared>,
+}
+
+impl Simulator {
+    /// Queue an event for the given window.
+    ///
+    /// The event is dropped if the window doesn't exist when it's sent. Events that reflect a
+    /// change of the window, like [`WindowEvent::SurfaceResized`] or [`WindowEvent::Focused`],
+    /// also update its [`WindowState`] before being dispatched.
+    pub fn send_window_event(&self, window_id: WindowId, event: WindowEvent) {
+        self.shared.state().push_window_event(window_id, event);
+        self.shared.wake_up();
+    }
+
+    /// Queue a device event.
+    ///
+    /// Device events are filtered according to [`ActiveEventLoop::listen_device_events`].
+    ///
+    /// [`ActiveEventLoop::listen_device_events`]: winit_core::event_loop::ActiveEventLoop::listen_device_events
+    pub fn send_device_event(&self, device_id: Option<DeviceId>, event: DeviceEvent) {
+        self.shared.state().events.push_back(QueuedEvent::Device(device_id, event));
+        self.shared.wake_up();
+    }
+
+    /// Change the scale factor of the window.
+    ///
+    /// This dispatches [`WindowEvent::ScaleFactorChanged`], followed by
+    /// [`WindowEvent::SurfaceResized`] when the surface size changed.
+    pub fn set_scale_factor(&self, window_id: WindowId, scale_factor: f64) {
+        let mut state = self.shared.state();
+        if state.windows.contains_key(&window_id) {
+            state.events.push_back(QueuedEvent::ScaleFactor(window_id, scale_factor));
+        }
+        drop(state);
+        self.shared.wake_up();
+    }
+
+    /// Give the focus to the given window, or remove it from all windows with `None`.
+    pub fn set_focus(&self, window_id: Option<WindowId>) {
+        self.shared.state().set_focus(window_id);
+        self.shared.wake_up();
+    }
+
+    /// Add a monitor, the first monitor is the primary monitor.
+    pub fn add_monitor(
+        &self,
+        name: &str,
+        position: PhysicalPosition<i32>,
+        video_mode: VideoMode,
+        scale_factor: f64,
+    ) -> CoreMonitorHandle {
+        let mut state = self.shared.state();
+        let monitor = Arc::new(MonitorHandle {
+            id: state.next_monitor_id,
+            name: name.to_owned(),
+            position,
+            video_mode,
+            scale_factor,
+        });
+        state.next_monitor_id += 1;
+        state.monitors.push(monitor.clone());
+        CoreMonitorHandle(monitor)
+    }
+
+    /// Remove a monitor previously added with [`Simulator::add_monitor`].
+    ///
+    /// Returns `false` if the monitor wasn't found.
+    pub fn remove_monitor(&self, monitor: &CoreMonitorHandle) -> bool {
+        let mut state = self.shared.state();
+        let len = state.monitors.len();
+        state.monitors.retain(|handle| handle.id != monitor.id());
+        state.monitors.len() != len
+    }
+
+    /// The identifiers of the windows that are currently alive, in creation order.
+    pub fn window_ids(&self) -> Vec<WindowId> {
+        let mut window_ids: Vec<_> = self.shared.state().windows.keys().copied().collect();
+        window_ids.sort_by_key(|window_id| window_id.into_raw());
+        window_ids
+    }
+
+    /// A snapshot of the state of the window, `None` if the window doesn't exist.
+    pub fn window_state(&self, window_id: WindowId) -> Option<WindowState> {
+        self.shared.state().windows.get(&window_id).cloned()
+    }
+}
@@ -0,0 +1,555 @@
+use std::sync::Arc;
+
+use dpi::{LogicalSize, PhysicalInsets, PhysicalPosition, PhysicalSize, Pixel, Position, Size};
+use winit_core::cursor::Cursor;
+use winit_core::error::RequestError;
+use winit_core::event::WindowEvent;
+use winit_core::icon::Icon;
+use winit_core::monitor::{Fullscreen, MonitorHandle as CoreMonitorHandle};
+use winit_core::window::{
+    CursorGrabMode, ImePurpose, PresentationHint, ResizeDirection, Theme, UserAttentionType,
+    Window as CoreWindow, WindowAttributes, WindowButtons, WindowId, WindowLevel,
+};
+
+use crate::event_loop::{ActiveEventLoop, QueuedEvent, Shared};
+
+/// The state of a headless [`Window`], as requested by the application or changed through the
+/// [`Simulator`].
+///
+/// Sizes and positions are in physical pixels, using the scale factor of the window.
+///
+/// [`Simulator`]: crate::Simulator
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct WindowState {
+    pub title: String,
+    pub scale_factor: f64,
+    pub surface_size: PhysicalSize<u32>,
+    pub min_surface_size: Option<PhysicalSize<u32>>,
+    pub max_surface_size: Option<PhysicalSize<u32>>,
+    pub surface_resize_increments: Option<PhysicalSize<u32>>,
+    pub outer_position: PhysicalPosition<i32>,
+    pub visible: bool,
+    pub resizable: bool,
+    pub enabled_buttons: WindowButtons,
+    pub minimized: bool,
+    pub maximized: bool,
+    pub fullscreen: Option<Fullscreen>,
+    pub decorated: bool,
+    pub transparent: bool,
+    pub blur: bool,
+    pub window_level: WindowLevel,
+    pub workspace: u32,
+    pub sticky: bool,
+    pub window_icon: Option<Icon>,
+    pub focused: bool,
+    pub occluded: bool,
+    pub theme: Option<Theme>,
+    pub content_protected: bool,
+    pub user_attention: Option<UserAttentionType>,
+    pub cursor: Cursor,
+    pub cursor_position: Option<PhysicalPosition<i32>>,
+    pub cursor_grab: CursorGrabMode,
+    pub cursor_visible: bool,
+    pub cursor_hittest: bool,
+    pub input_region: Option<Vec<(PhysicalPosition<i32>, PhysicalSize<u32>)>>,
+    pub opaque_region: Option<Vec<(PhysicalPosition<i32>, PhysicalSize<u32>)>>,
+    pub ime_allowed: bool,
+    pub ime_purpose: ImePurpose,
+    pub ime_cursor_area: Option<(PhysicalPosition<i32>, PhysicalSize<u32>)>,
+    pub ime_surrounding_text: Option<(String, usize, usize)>,
+    pub shortcuts_inhibited: bool,
+    pub frame_presented_events: bool,
+    pub presentation_hint: PresentationHint,
+    /// The number of calls to [`Window::request_redraw`].
+    ///
+    /// [`Window::request_redraw`]: winit_core::window::Window::request_redraw
+    pub redraw_requests: usize,
+}
+
+impl WindowState {
+    fn to_physical_region(
+        &self,
+        region: Option<&[(Position, Size)]>,
+    ) -> Option<Vec<(PhysicalPosition<i32>, PhysicalSize<u32>)>> {
+        let region = region?.iter().map(|(position, size)| {
+            (position.to_physical(self.scale_factor), size.to_physical(self.scale_factor))
+        });
+        Some(region.collect())
+    }
+
+    fn clamp_surface_size(&self, size: PhysicalSize<u32>) -> PhysicalSize<u32> {
+        let min = self.min_surface_size.unwrap_or(PhysicalSize::new(0, 0));
+        let max = self.max_surface_size.unwrap_or(PhysicalSize::new(u32::MAX, u32::MAX));
+        PhysicalSize::new(
+            size.width.clamp(min.width, max.width.max(min.width)),
+            size.height.clamp(min.height, max.height.max(min.height)),
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct Window {
+    id: WindowId,
+    shared: Arc<Shared>,
+}
+
+impl Window {
+    pub(crate) fn new(event_loop: &ActiveEventLoop, attrs: WindowAttributes) -> Self {
+        let shared = event_loop.shared.clone();
+        let mut state = shared.state();
+
+        let id = WindowId::from_raw(state.next_window_id);
+        state.next_window_id += 1;
+
+        let scale_factor = match (&attrs.fullscreen, attrs.position) {
+            (Some(Fullscreen::Borderless(Some(monitor))), _)
+            | (Some(Fullscreen::Exclusive(monitor, _)), _) => monitor.scale_factor(),
+            (_, Some(position)) => {
+                let position = position.to_physical(1.0);
+                let monitor = state.monitors.iter().find(|monitor| monitor.contains(position));
+                monitor.map_or(1.0, |monitor| monitor.scale_factor)
+            },
+            _ => state.monitors.first().map_or(1.0, |monitor| monitor.scale_factor),
+        };
+        let physical = |size: Option<Size>| size.map(|size| size.to_physical(scale_factor));
+
+        let window = WindowState {
+            title: attrs.title,
+            scale_factor,
+            surface_size: PhysicalSize::new(0, 0),
+            min_surface_size: physical(attrs.min_surface_size),
+            max_surface_size: physical(attrs.max_surface_size),
+            surface_resize_increments: physical(attrs.surface_resize_increments),
+            outer_position: attrs
+                .position
+                .map_or(PhysicalPosition::new(0, 0), |position| position.to_physical(scale_factor)),
+            visible: attrs.visible,
+            resizable: attrs.resizable,
+            enabled_buttons: attrs.enabled_buttons,
+            minimized: false,
+            maximized: attrs.maximized,
+            fullscreen: attrs.fullscreen,
+            decorated: attrs.decorations,
+            transparent: attrs.transparent,
+            blur: attrs.blur,
+            window_level: attrs.window_level,
+            workspace: 0,
+            sticky: false,
+            window_icon: attrs.window_icon,
+            focused: false,
+            occluded: false,
+            theme: attrs.preferred_theme,
+            content_protected: attrs.content_protected,
+            user_attention: None,
+            cursor: attrs.cursor,
+            cursor_position: None,
+            cursor_grab: CursorGrabMode::None,
+            cursor_visible: true,
+            cursor_hittest: true,
+            input_region: None,
+            opaque_region: None,
+            ime_allowed: false,
+            ime_purpose: ImePurpose::default(),
+            ime_cursor_area: None,
+            ime_surrounding_text: None,
+            shortcuts_inhibited: false,
+            frame_presented_events: false,
+            presentation_hint: PresentationHint::default(),
+            redraw_requests: 0,
+        };
+        let surface_size = attrs
+            .surface_size
+            .unwrap_or(LogicalSize::new(800, 600).into())
+            .to_physical(scale_factor);
+        let surface_size = window.clamp_surface_size(surface_size);
+        state.windows.insert(id, WindowState { surface_size, ..window });
+
+        // Like on other platforms, newly created windows get an initial redraw.
+        state.push_redraw(id);
+        if attrs.active && attrs.visible {
+            state.set_focus(Some(id));
+        }
+
+        drop(state);
+        shared.wake_up();
+
+        Self { id, shared }
+    }
+
+    fn with_state<R>(&self, f: impl FnOnce(&mut WindowState) -> R) -> R {
+        let mut state = self.shared.state();
+        f(state.windows.get_mut(&self.id).expect("window state removed before the window"))
+    }
+
+    /// Apply a change that results in an event, when the value differs.
+    fn update<T: PartialEq>(
+        &self,
+        value: T,
+        field: impl FnOnce(&mut WindowState) -> &mut T,
+        event: impl FnOnce(&T) -> WindowEvent,
+    ) {
+        let mut state = self.shared.state();
+        let window =
+            state.windows.get_mut(&self.id).expect("window state removed before the window");
+        let current = field(window);
+        if *current == value {
+            return;
+        }
+
+        let event = event(&value);
+        *current = value;
+        state.push_window_event(self.id, event);
+        drop(state);
+        self.shared.wake_up();
+    }
+
+    fn to_physical<P: Pixel>(&self, position: Position) -> PhysicalPosition<P> {
+        position.to_physical(self.scale_factor())
+    }
+}
+
+impl Drop for Window {
+    fn drop(&mut self) {
+        let mut state = self.shared.state();
+        state.windows.remove(&self.id);
+        state.redraws.retain(|&window_id| window_id != self.id);
+        state.events.push_back(QueuedEvent::Window(self.id, WindowEvent::Destroyed));
+        drop(state);
+        self.shared.wake_up();
+    }
+}
+
+impl CoreWindow for Window {
+    fn id(&self) -> WindowId {
+        self.id
+    }
+
+    fn scale_factor(&self) -> f64 {
+        self.with_state(|window| window.scale_factor)
+    }
+
+    fn request_redraw(&self) {
+        let mut state = self.shared.state();
+        if let Some(window) = state.windows.get_mut(&self.id) {
+            window.redraw_requests += 1;
+        }
+        state.push_redraw(self.id);
+        drop(state);
+        self.shared.wake_up();
+    }
+
+    fn pre_present_notify(&self) {}
+
+    fn set_frame_presented_events(&self, enabled: bool) -> Result<(), RequestError> {
+        self.with_state(|window| window.frame_presented_events = enabled);
+        Ok(())
+    }
+
+    fn set_presentation_hint(&self, hint: PresentationHint) -> Result<(), RequestError> {
+        self.with_state(|window| window.presentation_hint = hint);
+        Ok(())
+    }
+
+    fn reset_dead_keys(&self) {}
+
+    fn surface_position(&self) -> PhysicalPosition<i32> {
+        PhysicalPosition::new(0, 0)
+    }
+
+    fn outer_position(&self) -> Result<PhysicalPosition<i32>, RequestError> {
+        Ok(self.with_state(|window| window.outer_position))
+    }
+
+    fn set_outer_position(&self, position: Position) {
+        let position = self.to_physical(position);
+        self.update(
+            position,
+            |window| &mut window.outer_position,
+            |&position| WindowEvent::Moved(position),
+        );
+    }
+
+    fn surface_size(&self) -> PhysicalSize<u32> {
+        self.with_state(|window| window.surface_size)
+    }
+
+    fn request_surface_size(&self, size: Size) -> Option<PhysicalSize<u32>> {
+        let size = self
+            .with_state(|window| window.clamp_surface_size(size.to_physical(window.scale_factor)));
+        self.update(
+            size,
+            |window| &mut window.surface_size,
+            |&size| WindowEvent::SurfaceResized(size),
+        );
+        Some(size)
+    }
+
+    fn outer_size(&self) -> PhysicalSize<u32> {
+        self.surface_size()
+    }
+
+    fn safe_area(&self) -> PhysicalInsets<u32> {
+        PhysicalInsets::new(0, 0, 0, 0)
+    }
+
+    fn set_min_surface_size(&self, min_size: Option<Size>) {
+        self.with_state(|window| {
+            window.min_surface_size = min_size.map(|size| size.to_physical(window.scale_factor))
+        });
+    }
+
+    fn set_max_surface_size(&self, max_size: Option<Size>) {
+        self.with_state(|window| {
+            window.max_surface_size = max_size.map(|size| size.to_physical(window.scale_factor))
+        });
+    }
+
+    fn surface_resize_increments(&self) -> Option<PhysicalSize<u32>> {
+        self.with_state(|window| window.surface_resize_increments)
+    }
+
+    fn set_surface_resize_increments(&self, increments: Option<Size>) {
+        self.with_state(|window| {
+            window.surface_resize_increments =
+                increments.map(|size| size.to_physical(window.scale_factor))
+        });
+    }
+
+    fn set_title(&self, title: &str) {
+        self.with_state(|window| window.title = title.to_owned());
+    }
+
+    fn set_transparent(&self, transparent: bool) {
+        self.with_state(|window| window.transparent = transparent);
+    }
+
+    fn set_blur(&self, blur: bool) {
+        self.with_state(|window| window.blur = blur);
+    }
+
+    fn set_visible(&self, visible: bool) {
+        self.with_state(|window| window.visible = visible);
+    }
+
+    fn is_visible(&self) -> Option<bool> {
+        Some(self.with_state(|window| window.visible))
+    }
+
+    fn set_resizable(&self, resizable: bool) {
+        self.with_state(|window| window.resizable = resizable);
+    }
+
+    fn is_resizable(&self) -> bool {
+        self.with_state(|window| window.resizable)
+    }
+
+    fn set_enabled_buttons(&self, buttons: WindowButtons) {
+        self.with_state(|window| window.enabled_buttons = buttons);
+    }
+
+    fn enabled_buttons(&self) -> WindowButtons {
+        self.with_state(|window| window.enabled_buttons)
+    }
+
+    fn set_minimized(&self, minimized: bool) {
+        self.with_state(|window| window.minimized = minimized);
+    }
+
+    fn is_minimized(&self) -> Option<bool> {
+        Some(self.with_state(|window| window.minimized))
+    }
+
+    fn set_maximized(&self, maximized: bool) {
+        self.with_state(|window| window.maximized = maximized);
+    }
+
+    fn is_maximized(&self) -> bool {
+        self.with_state(|window| window.maximized)
+    }
+
+    fn set_fullscreen(&self, fullscreen: Option<Fullscreen>) {
+        self.with_state(|window| window.fullscreen = fullscreen);
+    }
+
+    fn fullscreen(&self) -> Option<Fullscreen> {
+        self.with_state(|window| window.fullscreen.clone())
+    }
+
+    fn set_decorations(&self, decorations: bool) {
+        self.with_state(|window| window.decorated = decorations);
+    }
+
+    fn is_decorated(&self) -> bool {
+        self.with_state(|window| window.decorated)
+    }
+
+    fn set_window_level(&self, level: WindowLevel) {
+        self.with_state(|window| window.window_level = level);
+    }
+
+    fn set_workspace(&self, workspace: u32) -> Result<(), RequestError> {
+        self.with_state(|window| window.workspace = workspace);
+        Ok(())
+    }
+
+    fn workspace(&self) -> Option<u32> {
+        self.with_state(|window| (!window.sticky).then_some(window.workspace))
+    }
+
+    fn set_sticky(&self, sticky: bool) -> Result<(), RequestError> {
+        self.with_state(|window| window.sticky = sticky);
+        Ok(())
+    }
+
+    fn set_window_icon(&self, window_icon: Option<Icon>) {
+        self.with_state(|window| window.window_icon = window_icon);
+    }
+
+    fn set_ime_cursor_area(&self, position: Position, size: Size) {
+        self.with_state(|window| {
+            let position = position.to_physical(window.scale_factor);
+            let size = size.to_physical(window.scale_factor);
+            window.ime_cursor_area = Some((position, size));
+        });
+    }
+
+    fn set_ime_allowed(&self, allowed: bool) {
+        self.with_state(|window| window.ime_allowed = allowed);
+    }
+
+    fn set_ime_purpose(&self, purpose: ImePurpose) {
+        self.with_state(|window| window.ime_purpose = purpose);
+    }
+
+    fn set_ime_surrounding_text(&self, text: &str, cursor: usize, anchor: usize) {
+        self.with_state(|window| {
+            window.ime_surrounding_text = Some((text.to_owned(), cursor, anchor))
+        });
+    }
+
+    fn focus_window(&self) {
+        self.shared.state().set_focus(Some(self.id));
+        self.shared.wake_up();
+    }
+
+    fn has_focus(&self) -> bool {
+        self.with_state(|window| window.focused)
+    }
+
+    fn request_user_attention(&self, request_type: Option<UserAttentionType>) {
+        self.with_state(|window| window.user_attention = request_type);
+    }
+
+    fn set_theme(&self, theme: Option<Theme>) {
+        self.with_state(|window| window.theme = theme);
+    }
+
+    fn theme(&self) -> Option<Theme> {
+        self.with_state(|window| window.theme)
+    }
+
+    fn set_content_protected(&self, protected: bool) {
+        self.with_state(|window| window.content_protected = protected);
+    }
+
+    fn is_content_protected(&self) -> bool {
+        self.with_state(|window| window.content_protected)
+    }
+
+    fn title(&self) -> String {
+        self.with_state(|window| window.title.clone())
+    }
+
+    fn set_cursor(&self, cursor: Cursor) {
+        self.with_state(|window| window.cursor = cursor);
+    }
+
+    fn set_cursor_position(&self, position: Position) -> Result<(), RequestError> {
+        let position = self.to_physical(position);
+        self.with_state(|window| window.cursor_position = Some(position));
+        Ok(())
+    }
+
+    fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), RequestError> {
+        self.with_state(|window| window.cursor_grab = mode);
+        Ok(())
+    }
+
+    fn set_cursor_visible(&self, visible: bool) {
+        self.with_state(|window| window.cursor_visible = visible);
+    }
+
+    fn drag_window(&self) -> Result<(), RequestError> {
+        Ok(())
+    }
+
+    fn drag_resize_window(&self, _direction: ResizeDirection) -> Result<(), RequestError> {
+        Ok(())
+    }
+
+    fn show_window_menu(&self, _position: Position) -> Result<(), RequestError> {
+        Ok(())
+    }
+
+    fn set_cursor_hittest(&self, hittest: bool) -> Result<(), RequestError> {
+        self.with_state(|window| window.cursor_hittest = hittest);
+        Ok(())
+    }
+
+    fn set_input_region(&self, region: Option<&[(Position, Size)]>) -> Result<(), RequestError> {
+        self.with_state(|window| window.input_region = window.to_physical_region(region));
+        Ok(())
+    }
+
+    fn set_opaque_region(&self, region: Option<&[(Position, Size)]>) -> Result<(), RequestError> {
+        self.with_state(|window| window.opaque_region = window.to_physical_region(region));
+        Ok(())
+    }
+
+    fn set_shortcuts_inhibited(&self, inhibited: bool) -> Result<(), RequestError> {
+        self.update(
+            inhibited,
+            |window| &mut window.shortcuts_inhibited,
+            |&inhibited| WindowEvent::ShortcutsInhibited(inhibited),
+        );
+        Ok(())
+    }
+
+    fn current_monitor(&self) -> Option<CoreMonitorHandle> {
+        let state = self.shared.state();
+        let position = state.windows[&self.id].outer_position;
+        match state.monitors.iter().find(|monitor| monitor.contains(position)) {
+            Some(monitor) => Some(CoreMonitorHandle(monitor.clone())),
+            None => state.primary_monitor(),
+        }
+    }
+
+    fn available_monitors(&self) -> Box<dyn Iterator<Item = CoreMonitorHandle>> {
+        Box::new(self.shared.state().available_monitors().into_iter())
+    }
+
+    fn primary_monitor(&self) -> Option<CoreMonitorHandle> {
+        self.shared.state().primary_monitor()
+    }
+
+    fn rwh_06_display_handle(&self) -> &dyn rwh_06::HasDisplayHandle {
+        self
+    }
+
+    fn rwh_06_window_handle(&self) -> &dyn rwh_06::HasWindowHandle {
+        self
+    }
+}
+
+impl rwh_06::HasDisplayHandle for Window {
+    fn display_handle(&self) -> Result<rwh_06::DisplayHandle<'_>, rwh_06::HandleError> {
+        Err(rwh_06::HandleError::NotSupported)
+    }
+}
+
+impl rwh_06::HasWindowHandle for Window {
+    fn window_handle(&self) -> Result<rwh_06::WindowHandle<'_>, rwh_06::HandleError> {
+        Err(rwh_06::HandleError::NotSupported)
+    }
+}
@@ -1,5 +1,7 @@
 use std::cell::Cell;
 use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::{mpsc, Arc, Mutex};
 use std::time::Instant;
 use std::{iter, mem, slice};
@@ -10,6 +12,8 @@ use orbclient::{
     MoveEvent, QuitEvent, ResizeEvent, ScrollEvent, TextInputEvent,
 };
 use smol_str::SmolStr;
+use winit_common::executor::Executor;
+use winit_common::timers::Timers;
 use winit_core::application::ApplicationHandler;
 use winit_core::cursor::{CustomCursor, CustomCursorSource};
 use winit_core::error::{EventLoopError, NotSupportedError, RequestError};
@@ -17,7 +21,7 @@ use winit_core::event::{self, Ime, Modifiers, StartCause};
 use winit_core::event_loop::{
     ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEvents,
     EventLoopProxy as CoreEventLoopProxy, EventLoopProxyProvider,
-    OwnedDisplayHandle as CoreOwnedDisplayHandle,
+    OwnedDisplayHandle as CoreOwnedDisplayHandle, TimerId,
 };
 use winit_core::keyboard::{
     Key, KeyCode, KeyLocation, ModifiersKeys, ModifiersState, NamedKey, NativeKey, NativeKeyCode,
@@ -297,6 +301,11 @@ impl EventLoop {
             })
             .map_err(|error| os_error!(format!("{error}")))?;
 
+        let event_loop_proxy = Arc::new(EventLoopProxy { wake_socket, user_events_sender });
+        let executor_proxy = CoreEventLoopProxy::new(Arc::new(ExecutorWaker {
+            event_loop_proxy: event_loop_proxy.clone(),
+        }));
+
         Ok(Self {
             windows: Vec::new(),
             window_target: ActiveEventLoop {
@@ -306,7 +315,10 @@ impl EventLoop {
                 redraws: Arc::new(Mutex::new(VecDeque::new())),
                 destroys: Arc::new(Mutex::new(VecDeque::new())),
                 event_socket,
-                event_loop_proxy: Arc::new(EventLoopProxy { wake_socket, user_events_sender }),
+                event_loop_proxy,
+                executor_proxy,
+                timers: Timers::default(),
+                executor: Executor::default(),
             },
             user_events_receiver,
         })
@@ -390,7 +402,7 @@ impl EventLoop {
                 app.window_event(
                     window_target,
                     window_id,
-                    event::WindowEvent::Ime(Ime::Preedit("".into(), None)),
+                    event::WindowEvent::Ime(Ime::Preedit("".into(), None, Vec::new())),
                 );
                 app.window_event(
                     window_target,
@@ -427,6 +439,8 @@ impl EventLoop {
                     device_id: None,
                     delta: event::MouseScrollDelta::LineDelta(x as f32, y as f32),
                     phase: event::TouchPhase::Moved,
+                    source: event::ScrollSource::Unknown,
+                    value120: None,
                 });
             },
             EventOption::Quit(QuitEvent {}) => {
@@ -574,6 +588,11 @@ impl EventLoop {
                 app.proxy_wake_up(&self.window_target);
             }
 
+            self.window_target.timers.dispatch(&mut app, &self.window_target);
+
+            // Poll the futures which were woken up.
+            self.window_target.executor.poll();
+
             // To avoid deadlocks the redraws lock is not held during event processing.
             while let Some(window_id) = {
                 let mut redraws = self.window_target.redraws.lock().unwrap();
@@ -601,6 +620,13 @@ impl EventLoop {
                 ControlFlow::WaitUntil(instant) => Some(instant),
             };
 
+            // Also wake up for the earliest timer.
+            let wake_up_at =
+                match self.window_target.timers.control_flow(self.window_target.control_flow()) {
+                    ControlFlow::WaitUntil(instant) => Some(instant),
+                    ControlFlow::Poll | ControlFlow::Wait => None,
+                };
+
             // Re-using wake socket caused extra wake events before because there were leftover
             // timeouts, and then new timeouts were added each time a spurious timeout expired.
             let timeout_socket = TimeSocket::open().unwrap();
@@ -615,7 +641,7 @@ impl EventLoop {
                 .unwrap();
 
             let start = Instant::now();
-            if let Some(instant) = requested_resume {
+            if let Some(instant) = wake_up_at {
                 let mut time = timeout_socket.current_time().unwrap();
 
                 if let Some(duration) = instant.checked_duration_since(start) {
@@ -637,7 +663,9 @@ impl EventLoop {
 
             // TODO: handle spurious wakeups (redraw caused wakeup but redraw already handled)
             match requested_resume {
-                Some(requested_resume) if event.id == timeout_socket.0.fd => {
+                Some(requested_resume)
+                    if event.id == timeout_socket.0.fd && Instant::now() >= requested_resume =>
+                {
                     // If the event is from the special timeout socket, report that resume
                     // time was reached.
                     start_cause = StartCause::ResumeTimeReached { start, requested_resume };
@@ -675,6 +703,18 @@ impl EventLoopProxyProvider for EventLoopProxy {
 
 impl Unpin for EventLoopProxy {}
 
+/// Wakes up the event loop for the futures, without emitting [`ApplicationHandler::proxy_wake_up`].
+#[derive(Debug)]
+struct ExecutorWaker {
+    event_loop_proxy: Arc<EventLoopProxy>,
+}
+
+impl EventLoopProxyProvider for ExecutorWaker {
+    fn wake_up(&self) {
+        self.event_loop_proxy.wake_socket.wake().unwrap();
+    }
+}
+
 #[derive(Debug)]
 pub struct ActiveEventLoop {
     control_flow: Cell<ControlFlow>,
@@ -684,6 +724,9 @@ pub struct ActiveEventLoop {
     pub(super) destroys: Arc<Mutex<VecDeque<WindowId>>>,
     pub(super) event_socket: Arc<RedoxSocket>,
     pub(super) event_loop_proxy: Arc<EventLoopProxy>,
+    executor_proxy: CoreEventLoopProxy,
+    timers: Timers,
+    executor: Executor,
 }
 
 impl RootActiveEventLoop for ActiveEventLoop {
@@ -724,6 +767,18 @@ impl RootActiveEventLoop for ActiveEventLoop {
         self.control_flow.get()
     }
 
+    fn schedule_timer(&self, deadline: Instant, timer_id: TimerId) {
+        self.timers.schedule(deadline, timer_id)
+    }
+
+    fn cancel_timer(&self, timer_id: TimerId) {
+        self.timers.cancel(timer_id)
+    }
+
+    fn spawn_local(&self, future: Pin<Box<dyn Future<Output = ()>>>) {
+        self.executor.spawn(future, self.executor_proxy.clone())
+    }
+
     fn exit(&self) {
         self.exit.set(true);
     }
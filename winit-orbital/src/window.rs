@@ -195,6 +195,14 @@ impl CoreWindow for Window {
     #[inline]
     fn pre_present_notify(&self) {}
 
+    fn set_frame_presented_events(&self, _enabled: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_frame_presented_events is not supported").into())
+    }
+
+    fn set_presentation_hint(&self, _hint: window::PresentationHint) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_presentation_hint is not supported").into())
+    }
+
     #[inline]
     fn reset_dead_keys(&self) {
         // TODO?
@@ -351,6 +359,18 @@ impl CoreWindow for Window {
         }
     }
 
+    fn set_workspace(&self, _workspace: u32) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_workspace is not supported").into())
+    }
+
+    fn workspace(&self) -> Option<u32> {
+        None
+    }
+
+    fn set_sticky(&self, _sticky: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_sticky is not supported").into())
+    }
+
     #[inline]
     fn set_window_icon(&self, _window_icon: Option<winit_core::icon::Icon>) {}
 
@@ -363,6 +383,9 @@ impl CoreWindow for Window {
     #[inline]
     fn set_ime_purpose(&self, _purpose: ImePurpose) {}
 
+    #[inline]
+    fn set_ime_surrounding_text(&self, _text: &str, _cursor: usize, _anchor: usize) {}
+
     #[inline]
     fn focus_window(&self) {}
 
@@ -423,13 +446,27 @@ impl CoreWindow for Window {
     }
 
     #[inline]
-    fn show_window_menu(&self, _position: Position) {}
+    fn show_window_menu(&self, _position: Position) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("show_window_menu is not supported").into())
+    }
 
     #[inline]
     fn set_cursor_hittest(&self, _hittest: bool) -> Result<(), RequestError> {
         Err(NotSupportedError::new("set_cursor_hittest is not supported").into())
     }
 
+    fn set_input_region(&self, _region: Option<&[(Position, Size)]>) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_input_region is not supported").into())
+    }
+
+    fn set_opaque_region(&self, _region: Option<&[(Position, Size)]>) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_opaque_region is not supported").into())
+    }
+
+    fn set_shortcuts_inhibited(&self, _inhibited: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_shortcuts_inhibited is not supported").into())
+    }
+
     #[inline]
     fn set_enabled_buttons(&self, _buttons: window::WindowButtons) {}
 
@@ -453,6 +490,10 @@ impl CoreWindow for Window {
 
     fn set_content_protected(&self, _protected: bool) {}
 
+    fn is_content_protected(&self) -> bool {
+        false
+    }
+
     fn rwh_06_window_handle(&self) -> &dyn rwh_06::HasWindowHandle {
         self
     }
@@ -18,6 +18,8 @@ use objc2_core_foundation::{
 use objc2_ui_kit::{UIApplication, UICoordinateSpace, UIView};
 use winit_common::core_foundation::EventLoopProxy;
 use winit_common::event_handler::EventHandler;
+use winit_common::executor::Executor;
+use winit_common::timers::Timers;
 use winit_core::application::ApplicationHandler;
 use winit_core::event::{StartCause, SurfaceSizeWriter, WindowEvent};
 use winit_core::event_loop::ControlFlow;
@@ -51,6 +53,31 @@ fn get_handler(mtm: MainThreadMarker) -> &'static EventHandler {
     GLOBAL.get(mtm).get_or_init(EventHandler::new)
 }
 
+/// Get the timers scheduled by the application.
+///
+/// This is stored separately from AppState, since the timers are scheduled while the handler is
+/// executing.
+pub(crate) fn get_timers(mtm: MainThreadMarker) -> &'static Timers {
+    // SAFETY: Creating `StaticMainThreadBound` in a `const` context, where there is no concept
+    // of the main thread.
+    static GLOBAL: MainThreadBound<OnceCell<Timers>> =
+        MainThreadBound::new(OnceCell::new(), unsafe { MainThreadMarker::new_unchecked() });
+
+    GLOBAL.get(mtm).get_or_init(Timers::default)
+}
+
+/// Get the futures spawned by the application.
+///
+/// Stored separately from AppState for the same reason as the timers.
+pub(crate) fn get_executor(mtm: MainThreadMarker) -> &'static Executor {
+    // SAFETY: Creating `StaticMainThreadBound` in a `const` context, where there is no concept
+    // of the main thread.
+    static GLOBAL: MainThreadBound<OnceCell<Executor>> =
+        MainThreadBound::new(OnceCell::new(), unsafe { MainThreadMarker::new_unchecked() });
+
+    GLOBAL.get(mtm).get_or_init(Executor::default)
+}
+
 #[derive(Debug)]
 pub(crate) enum EventWrapper {
     Window { window_id: WindowId, event: WindowEvent },
@@ -97,6 +124,8 @@ pub(crate) struct AppState {
     control_flow: ControlFlow,
     waker: EventLoopWaker,
     event_loop_proxy: Arc<EventLoopProxy>,
+    /// Wakes up the run loop for the futures, without emitting a `proxy_wake_up`.
+    executor_proxy: Arc<EventLoopProxy>,
     queued_events: Vec<EventWrapper>,
 }
 
@@ -118,12 +147,15 @@ impl AppState {
                 let event_loop_proxy = Arc::new(EventLoopProxy::new(mtm, move || {
                     get_handler(mtm).handle(|app| app.proxy_wake_up(&ActiveEventLoop { mtm }));
                 }));
+                // The futures are polled by the run loop observer before waiting.
+                let executor_proxy = Arc::new(EventLoopProxy::new(mtm, || {}));
 
                 **guard = Some(AppState {
                     app_state: Some(AppStateImpl::Initial { queued_gpu_redraws: HashSet::new() }),
                     control_flow: ControlFlow::default(),
                     waker,
                     event_loop_proxy,
+                    executor_proxy,
                     queued_events: Vec::new(),
                 });
             }
@@ -228,7 +260,7 @@ impl AppState {
         queued_gpu_redraws
     }
 
-    fn events_cleared_transition(&mut self) {
+    fn events_cleared_transition(&mut self, timers: &Timers) {
         if !self.has_launched() || self.has_terminated() {
             return;
         }
@@ -237,7 +269,8 @@ impl AppState {
             s => bug!("unexpected state {:?}", s),
         };
 
-        let new = self.control_flow;
+        // Also wake up for the earliest timer.
+        let new = timers.control_flow(self.control_flow);
         match (old, new) {
             (ControlFlow::Wait, ControlFlow::Wait) => {
                 let start = Instant::now();
@@ -279,6 +312,10 @@ impl AppState {
         &self.event_loop_proxy
     }
 
+    pub fn executor_proxy(&self) -> &Arc<EventLoopProxy> {
+        &self.executor_proxy
+    }
+
     pub(crate) fn set_control_flow(&mut self, control_flow: ControlFlow) {
         self.control_flow = control_flow;
     }
@@ -446,6 +483,10 @@ pub fn handle_main_events_cleared(mtm: MainThreadMarker) {
 
     handle_user_events(mtm);
 
+    get_handler(mtm).handle(|app| get_timers(mtm).dispatch(app, &ActiveEventLoop { mtm }));
+    get_executor(mtm).poll();
+    handle_nonuser_events(mtm, []);
+
     let mut this = AppState::get_mut(mtm);
     let redraw_events: Vec<EventWrapper> = this
         .main_events_cleared_transition()
@@ -463,7 +504,7 @@ pub fn handle_main_events_cleared(mtm: MainThreadMarker) {
 }
 
 pub fn handle_events_cleared(mtm: MainThreadMarker) {
-    AppState::get_mut(mtm).events_cleared_transition();
+    AppState::get_mut(mtm).events_cleared_transition(get_timers(mtm));
 }
 
 pub(crate) fn handle_resumed(mtm: MainThreadMarker) {
@@ -500,6 +541,7 @@ pub(crate) fn terminated(application: &UIApplication) {
     this.terminated_transition();
     // Prevent EventLoopProxy from firing again.
     this.event_loop_proxy.invalidate();
+    this.executor_proxy.invalidate();
     drop(this);
 
     get_handler(mtm).terminate();
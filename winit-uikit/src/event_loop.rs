@@ -1,6 +1,9 @@
 use std::ffi::c_void;
+use std::future::Future;
+use std::pin::Pin;
 use std::ptr;
 use std::sync::Arc;
+use std::time::Instant;
 
 use objc2::rc::Retained;
 use objc2::runtime::ProtocolObject;
@@ -21,12 +24,12 @@ use winit_core::cursor::{CustomCursor, CustomCursorSource};
 use winit_core::error::{EventLoopError, NotSupportedError, RequestError};
 use winit_core::event_loop::{
     ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEvents,
-    EventLoopProxy as CoreEventLoopProxy, OwnedDisplayHandle as CoreOwnedDisplayHandle,
+    EventLoopProxy as CoreEventLoopProxy, OwnedDisplayHandle as CoreOwnedDisplayHandle, TimerId,
 };
 use winit_core::monitor::MonitorHandle as CoreMonitorHandle;
 use winit_core::window::{Theme, Window as CoreWindow};
 
-use super::app_state::{send_occluded_event_for_all_windows, AppState};
+use super::app_state::{get_executor, get_timers, send_occluded_event_for_all_windows, AppState};
 use super::notification_center::create_observer;
 use crate::monitor::MonitorHandle;
 use crate::window::Window;
@@ -84,6 +87,19 @@ impl RootActiveEventLoop for ActiveEventLoop {
         AppState::get_mut(self.mtm).control_flow()
     }
 
+    fn schedule_timer(&self, deadline: Instant, timer_id: TimerId) {
+        get_timers(self.mtm).schedule(deadline, timer_id)
+    }
+
+    fn cancel_timer(&self, timer_id: TimerId) {
+        get_timers(self.mtm).cancel(timer_id)
+    }
+
+    fn spawn_local(&self, future: Pin<Box<dyn Future<Output = ()>>>) {
+        let executor_proxy = AppState::get_mut(self.mtm).executor_proxy().clone();
+        get_executor(self.mtm).spawn(future, CoreEventLoopProxy::new(executor_proxy))
+    }
+
     fn exit(&self) {
         // https://developer.apple.com/library/archive/qa/qa1561/_index.html
         // it is not possible to quit an iOS app gracefully and programmatically
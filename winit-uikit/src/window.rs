@@ -23,8 +23,8 @@ use winit_core::event::WindowEvent;
 use winit_core::icon::Icon;
 use winit_core::monitor::{Fullscreen, MonitorHandle as CoreMonitorHandle};
 use winit_core::window::{
-    CursorGrabMode, ImePurpose, ResizeDirection, Theme, UserAttentionType, Window as CoreWindow,
-    WindowAttributes, WindowButtons, WindowId, WindowLevel,
+    CursorGrabMode, ImePurpose, PresentationHint, ResizeDirection, Theme, UserAttentionType,
+    Window as CoreWindow, WindowAttributes, WindowButtons, WindowId, WindowLevel,
 };
 
 use super::app_state::EventWrapper;
@@ -280,7 +280,9 @@ impl Inner {
     }
 
     #[inline]
-    pub fn show_window_menu(&self, _position: Position) {}
+    pub fn show_window_menu(&self, _position: Position) -> Result<(), NotSupportedError> {
+        Err(NotSupportedError::new("show_window_menu is not supported"))
+    }
 
     pub fn set_cursor_hittest(&self, _hittest: bool) -> Result<(), NotSupportedError> {
         Err(NotSupportedError::new("set_cursor_hittest is not supported"))
@@ -587,6 +589,14 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.pre_present_notify());
     }
 
+    fn set_frame_presented_events(&self, _enabled: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_frame_presented_events is not supported").into())
+    }
+
+    fn set_presentation_hint(&self, _hint: PresentationHint) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_presentation_hint is not supported").into())
+    }
+
     fn reset_dead_keys(&self) {
         self.maybe_wait_on_main(|delegate| delegate.reset_dead_keys());
     }
@@ -707,6 +717,18 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.set_window_level(level));
     }
 
+    fn set_workspace(&self, _workspace: u32) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_workspace is not supported").into())
+    }
+
+    fn workspace(&self) -> Option<u32> {
+        None
+    }
+
+    fn set_sticky(&self, _sticky: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_sticky is not supported").into())
+    }
+
     fn set_window_icon(&self, window_icon: Option<Icon>) {
         self.maybe_wait_on_main(|delegate| delegate.set_window_icon(window_icon));
     }
@@ -723,6 +745,10 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.set_ime_purpose(purpose));
     }
 
+    fn set_ime_surrounding_text(&self, _text: &str, _cursor: usize, _anchor: usize) {
+        warn!("`Window::set_ime_surrounding_text` is ignored on iOS")
+    }
+
     fn focus_window(&self) {
         self.maybe_wait_on_main(|delegate| delegate.focus_window());
     }
@@ -747,6 +773,10 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.set_content_protected(protected));
     }
 
+    fn is_content_protected(&self) -> bool {
+        false
+    }
+
     fn title(&self) -> String {
         self.maybe_wait_on_main(|delegate| delegate.title())
     }
@@ -781,14 +811,26 @@ impl CoreWindow for Window {
         Ok(self.maybe_wait_on_main(|delegate| delegate.drag_resize_window(direction))?)
     }
 
-    fn show_window_menu(&self, position: Position) {
-        self.maybe_wait_on_main(|delegate| delegate.show_window_menu(position))
+    fn show_window_menu(&self, position: Position) -> Result<(), RequestError> {
+        Ok(self.maybe_wait_on_main(|delegate| delegate.show_window_menu(position))?)
     }
 
     fn set_cursor_hittest(&self, hittest: bool) -> Result<(), RequestError> {
         Ok(self.maybe_wait_on_main(|delegate| delegate.set_cursor_hittest(hittest))?)
     }
 
+    fn set_input_region(&self, _region: Option<&[(Position, Size)]>) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_input_region is not supported").into())
+    }
+
+    fn set_opaque_region(&self, _region: Option<&[(Position, Size)]>) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_opaque_region is not supported").into())
+    }
+
+    fn set_shortcuts_inhibited(&self, _inhibited: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_shortcuts_inhibited is not supported").into())
+    }
+
     fn current_monitor(&self) -> Option<CoreMonitorHandle> {
         self.maybe_wait_on_main(|delegate| {
             delegate.current_monitor().map(|monitor| CoreMonitorHandle(Arc::new(monitor)))
@@ -1,33 +1,43 @@
 //! The event-loop routines.
 
 use std::cell::{Cell, RefCell};
+use std::ffi::c_void;
+use std::future::Future;
 use std::io::Result as IOResult;
 use std::mem;
 use std::os::fd::OwnedFd;
 use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, RawFd};
+use std::pin::Pin;
+use std::ptr::NonNull;
 use std::sync::atomic::Ordering;
 use std::sync::{Arc, Condvar, Mutex};
 use std::thread::JoinHandle;
 use std::time::{Duration, Instant};
 
 use calloop::ping::Ping;
+use calloop::{LoopHandle, RegistrationToken};
 use dpi::LogicalSize;
 use rustix::event::{PollFd, PollFlags};
 use rustix::pipe::{self, PipeFlags};
 use sctk::reexports::calloop_wayland_source::WaylandSource;
+use sctk::reexports::client::backend::Backend;
 use sctk::reexports::client::{globals, Connection, QueueHandle};
 use tracing::warn;
+use winit_common::event_sources::EventSources;
+use winit_common::executor::Executor;
+use winit_common::timers::Timers;
+use winit_core::application::unix::{EventSourceId, FdInterest};
 use winit_core::application::ApplicationHandler;
 use winit_core::cursor::{CustomCursor as CoreCustomCursor, CustomCursorSource};
 use winit_core::error::{EventLoopError, NotSupportedError, OsError, RequestError};
-use winit_core::event::{DeviceEvent, StartCause, SurfaceSizeWriter, WindowEvent};
+use winit_core::event::{DeviceEvent, Modifiers, StartCause, SurfaceSizeWriter, WindowEvent};
 use winit_core::event_loop::pump_events::PumpStatus;
 use winit_core::event_loop::{
     ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEvents,
-    OwnedDisplayHandle as CoreOwnedDisplayHandle,
+    OwnedDisplayHandle as CoreOwnedDisplayHandle, TimerId,
 };
 use winit_core::monitor::MonitorHandle as CoreMonitorHandle;
-use winit_core::window::Theme;
+use winit_core::window::{Theme, Window as CoreWindow};
 
 use crate::types::cursor::WaylandCustomCursor;
 
@@ -65,9 +75,18 @@ pub struct EventLoop {
     /// when creating a new window.
     wayland_dispatcher: WaylandDispatcher,
 
+    /// The registration of the Wayland source in the event loop.
+    wayland_token: RegistrationToken,
+
     /// Connection to the wayland server.
     handle: Arc<OwnedDisplayHandle>,
 
+    /// Whether to connect to the compositor again when the connection is lost.
+    reconnect: bool,
+
+    /// The reason the connection to the compositor was lost.
+    connection_error: Option<OsError>,
+
     /// Event loop window target.
     active_event_loop: ActiveEventLoop,
 
@@ -81,38 +100,27 @@ pub struct EventLoop {
 impl EventLoop {
     pub fn new() -> Result<EventLoop, EventLoopError> {
         let connection = Connection::connect_to_env().map_err(|err| os_error!(err))?;
+        Self::with_connection(connection)
+    }
 
-        let (globals, mut event_queue) =
-            globals::registry_queue_init(&connection).map_err(|err| os_error!(err))?;
-        let queue_handle = event_queue.handle();
+    /// Create the event loop on the `wl_display` of the host application.
+    ///
+    /// # Safety
+    ///
+    /// `display` must point to a valid `wl_display`, which outlives the event loop.
+    pub unsafe fn from_display(display: NonNull<c_void>) -> Result<EventLoop, EventLoopError> {
+        // The connection is in "guest" mode, it's not closed when dropped. Winit only dispatches
+        // its own event queue, so the host's queues aren't affected.
+        let backend = unsafe { Backend::from_foreign_display(display.as_ptr().cast()) };
+        Self::with_connection(Connection::from_backend(backend))
+    }
 
+    fn with_connection(connection: Connection) -> Result<EventLoop, EventLoopError> {
         let event_loop =
             calloop::EventLoop::<WinitState>::try_new().map_err(|err| os_error!(err))?;
 
-        let mut winit_state = WinitState::new(&globals, &queue_handle, event_loop.handle())?;
-
-        // NOTE: do a roundtrip after binding the globals to prevent potential
-        // races with the server.
-        event_queue.roundtrip(&mut winit_state).map_err(|err| os_error!(err))?;
-
-        // Register Wayland source.
-        let wayland_source = WaylandSource::new(connection.clone(), event_queue);
-        let wayland_dispatcher =
-            calloop::Dispatcher::new(wayland_source, |_, queue, winit_state: &mut WinitState| {
-                let result = queue.dispatch_pending(winit_state);
-                if result.is_ok()
-                    && (!winit_state.events_sink.is_empty()
-                        || !winit_state.window_compositor_updates.is_empty())
-                {
-                    winit_state.dispatched_events = true;
-                }
-                result
-            });
-
-        event_loop
-            .handle()
-            .register_dispatcher(wayland_dispatcher.clone())
-            .map_err(|err| os_error!(err))?;
+        let (winit_state, queue_handle, wayland_dispatcher, wayland_token) =
+            Self::register_connection(&connection, event_loop.handle())?;
 
         // Setup the user proxy.
         let (ping, ping_source) = calloop::ping::make_ping().unwrap();
@@ -140,12 +148,18 @@ impl EventLoop {
         let active_event_loop = ActiveEventLoop {
             handle: handle.clone(),
             wayland_dispatcher: wayland_dispatcher.clone(),
+            executor_proxy: EventLoopProxy::new(event_loop_awakener.clone()).into(),
             event_loop_awakener,
             event_loop_proxy: EventLoopProxy::new(ping).into(),
             queue_handle,
             control_flow: Cell::new(ControlFlow::default()),
             exit: Cell::new(None),
             state: RefCell::new(winit_state),
+            event_sources: EventSources::new(event_loop.handle(), |winit_state| {
+                winit_state.dispatched_events = true;
+            }),
+            timers: Timers::default(),
+            executor: Executor::default(),
         };
 
         let event_loop = Self {
@@ -155,6 +169,9 @@ impl EventLoop {
             window_ids: Vec::new(),
             handle,
             wayland_dispatcher,
+            wayland_token,
+            reconnect: false,
+            connection_error: None,
             event_loop,
             active_event_loop,
             pump_event_notifier: None,
@@ -163,6 +180,74 @@ impl EventLoop {
         Ok(event_loop)
     }
 
+    /// Bind the globals of the connection and dispatch its events from the event loop.
+    fn register_connection(
+        connection: &Connection,
+        loop_handle: LoopHandle<'static, WinitState>,
+    ) -> Result<
+        (WinitState, QueueHandle<WinitState>, WaylandDispatcher, RegistrationToken),
+        EventLoopError,
+    > {
+        let (globals, mut event_queue) =
+            globals::registry_queue_init(connection).map_err(|err| os_error!(err))?;
+        let queue_handle = event_queue.handle();
+
+        let mut winit_state = WinitState::new(&globals, &queue_handle, loop_handle.clone())?;
+
+        // NOTE: do a roundtrip after binding the globals to prevent potential
+        // races with the server.
+        event_queue.roundtrip(&mut winit_state).map_err(|err| os_error!(err))?;
+
+        // Register Wayland source.
+        let wayland_source = WaylandSource::new(connection.clone(), event_queue);
+        let wayland_dispatcher =
+            calloop::Dispatcher::new(wayland_source, |_, queue, winit_state: &mut WinitState| {
+                let result = queue.dispatch_pending(winit_state);
+                if result.is_ok()
+                    && (!winit_state.events_sink.is_empty()
+                        || !winit_state.window_compositor_updates.is_empty())
+                {
+                    winit_state.dispatched_events = true;
+                }
+                result
+            });
+
+        let wayland_token = loop_handle
+            .register_dispatcher(wayland_dispatcher.clone())
+            .map_err(|err| os_error!(err))?;
+
+        Ok((winit_state, queue_handle, wayland_dispatcher, wayland_token))
+    }
+
+    /// Connect to the compositor again after losing the connection.
+    ///
+    /// The state tied to the old connection, including the windows, is dropped.
+    fn reconnect(&mut self) -> Result<(), EventLoopError> {
+        // The wake-up thread is monitoring the old connection.
+        self.pump_event_notifier = None;
+        self.event_loop.handle().remove(self.wayland_token);
+
+        let connection = Connection::connect_to_env().map_err(|err| os_error!(err))?;
+        let (winit_state, queue_handle, wayland_dispatcher, wayland_token) =
+            Self::register_connection(&connection, self.event_loop.handle())?;
+
+        self.handle = Arc::new(OwnedDisplayHandle::new(connection));
+        self.wayland_dispatcher = wayland_dispatcher.clone();
+        self.wayland_token = wayland_token;
+
+        self.active_event_loop.handle = self.handle.clone();
+        self.active_event_loop.wayland_dispatcher = wayland_dispatcher;
+        self.active_event_loop.queue_handle = queue_handle;
+        *self.active_event_loop.state.get_mut() = winit_state;
+
+        Ok(())
+    }
+
+    /// Whether to connect to the compositor again when the connection is lost.
+    pub fn set_reconnect(&mut self, reconnect: bool) {
+        self.reconnect = reconnect;
+    }
+
     pub fn run_app<A: ApplicationHandler>(mut self, app: A) -> Result<(), EventLoopError> {
         self.run_app_on_demand(app)
     }
@@ -180,6 +265,10 @@ impl EventLoop {
                 PumpStatus::Exit(code) => {
                     break Err(EventLoopError::ExitFailure(code));
                 },
+                PumpStatus::ConnectionLost => {
+                    let error = self.connection_error.take().unwrap();
+                    break Err(EventLoopError::ConnectionLost(error));
+                },
                 _ => {
                     continue;
                 },
@@ -213,7 +302,11 @@ impl EventLoop {
             self.poll_events_with_timeout(timeout, &mut app);
         }
 
-        if let Some(code) = self.exit_code() {
+        if self.connection_error.is_some() {
+            self.loop_running = false;
+
+            PumpStatus::ConnectionLost
+        } else if let Some(code) = self.exit_code() {
             self.loop_running = false;
 
             PumpStatus::Exit(code)
@@ -246,7 +339,8 @@ impl EventLoop {
             let start = Instant::now();
 
             timeout = {
-                let control_flow_timeout = match self.control_flow() {
+                let control_flow = self.active_event_loop.timers.control_flow(self.control_flow());
+                let control_flow_timeout = match control_flow {
                     ControlFlow::Wait => None,
                     ControlFlow::Poll => Some(Duration::ZERO),
                     ControlFlow::WaitUntil(wait_deadline) => {
@@ -262,12 +356,24 @@ impl EventLoop {
             //
             // Checking for flush error is essential to perform an exit with error, since
             // once we have a protocol error, we could get stuck retrying...
-            if self.handle.connection.flush().is_err() {
-                self.set_exit_code(1);
+            if let Err(error) = self.handle.connection.flush() {
+                if self.handle.connection.protocol_error().is_none() {
+                    self.connection_lost(app, error);
+                } else {
+                    self.set_exit_code(1);
+                }
                 return;
             }
 
             if let Err(error) = self.loop_dispatch(timeout) {
+                // The compositor went away when the connection broke without a protocol error.
+                if self.handle.connection.flush().is_err()
+                    && self.handle.connection.protocol_error().is_none()
+                {
+                    self.connection_lost(app, error);
+                    return;
+                }
+
                 // NOTE We exit on errors from dispatches, since if we've got protocol error
                 // libwayland-client/wayland-rs will inform us anyway, but crashing downstream is
                 // not really an option. Instead we inform that the event loop got
@@ -333,6 +439,15 @@ impl EventLoop {
             app.proxy_wake_up(&self.active_event_loop);
         }
 
+        // Deliver the ready file descriptors and expired timers.
+        self.active_event_loop.event_sources.dispatch(app, &self.active_event_loop);
+
+        // Fire the timers which are due.
+        self.active_event_loop.timers.dispatch(app, &self.active_event_loop);
+
+        // Poll the futures which were woken up.
+        self.active_event_loop.executor.poll();
+
         // Drain the pending compositor updates.
         self.with_state(|state| compositor_updates.append(&mut state.window_compositor_updates));
 
@@ -518,6 +633,34 @@ impl EventLoop {
         std::mem::swap(&mut self.window_ids, &mut window_ids);
     }
 
+    fn connection_lost<A: ApplicationHandler>(
+        &mut self,
+        app: &mut A,
+        error: impl Into<Box<dyn std::error::Error + Send + Sync>>,
+    ) {
+        let error = os_error!(error);
+        tracing::error!("Lost the connection to the compositor: {error}");
+
+        if let Some(handler) = app.unix_handler() {
+            handler.connection_lost(&self.active_event_loop);
+        }
+
+        if self.reconnect {
+            // Let the application drop everything tied to the old connection.
+            app.destroy_surfaces(&self.active_event_loop);
+
+            match self.reconnect() {
+                Ok(()) => {
+                    app.can_create_surfaces(&self.active_event_loop);
+                    return;
+                },
+                Err(err) => tracing::error!("Failed to reconnect to the compositor: {err}"),
+            }
+        }
+
+        self.connection_error = Some(error);
+    }
+
     #[inline]
     pub fn window_target(&self) -> &dyn RootActiveEventLoop {
         &self.active_event_loop
@@ -579,6 +722,9 @@ pub struct ActiveEventLoop {
     /// Event loop proxy
     event_loop_proxy: CoreEventLoopProxy,
 
+    /// Wakes up the event loop for the futures, without emitting a `proxy_wake_up`.
+    executor_proxy: CoreEventLoopProxy,
+
     /// The event loop wakeup source.
     pub event_loop_awakener: Ping,
 
@@ -600,6 +746,15 @@ pub struct ActiveEventLoop {
 
     /// Handle for the underlying event loop.
     pub handle: Arc<OwnedDisplayHandle>,
+
+    /// File descriptors and timers inserted by the application.
+    event_sources: EventSources<WinitState>,
+
+    /// Timers scheduled by the application.
+    timers: Timers,
+
+    /// Futures spawned by the application.
+    executor: Executor,
 }
 
 impl RootActiveEventLoop for ActiveEventLoop {
@@ -615,6 +770,18 @@ impl RootActiveEventLoop for ActiveEventLoop {
         self.control_flow.get()
     }
 
+    fn schedule_timer(&self, deadline: Instant, timer_id: TimerId) {
+        self.timers.schedule(deadline, timer_id)
+    }
+
+    fn cancel_timer(&self, timer_id: TimerId) {
+        self.timers.cancel(timer_id)
+    }
+
+    fn spawn_local(&self, future: Pin<Box<dyn Future<Output = ()>>>) {
+        self.executor.spawn(future, self.executor_proxy.clone())
+    }
+
     fn exit(&self) {
         self.exit.set(Some(0))
     }
@@ -679,6 +846,60 @@ impl RootActiveEventLoop for ActiveEventLoop {
 }
 
 impl ActiveEventLoop {
+    /// The current state of the keyboard modifiers and lock keys.
+    ///
+    /// The compositor only sends the modifiers once one of the surfaces gains keyboard focus, so
+    /// they are empty before that.
+    pub fn modifiers(&self) -> Modifiers {
+        let state = self.state.borrow();
+        state.seats.values().find_map(|seat| seat.modifiers()).unwrap_or_default()
+    }
+
+    /// Insert a file descriptor into the event loop.
+    pub fn insert_fd<F: AsFd + 'static>(
+        &self,
+        fd: F,
+        interest: FdInterest,
+    ) -> Result<EventSourceId, RequestError> {
+        self.event_sources.insert_fd(fd, interest).map_err(|err| os_error!(err).into())
+    }
+
+    /// Insert a timer into the event loop.
+    pub fn insert_timer(
+        &self,
+        deadline: Instant,
+        interval: Option<Duration>,
+    ) -> Result<EventSourceId, RequestError> {
+        self.event_sources.insert_timer(deadline, interval).map_err(|err| os_error!(err).into())
+    }
+
+    /// Remove a file descriptor or a timer from the event loop.
+    pub fn remove_event_source(&self, id: EventSourceId) {
+        self.event_sources.remove(id)
+    }
+
+    /// Wrap a `wl_surface` created by the host application in a [`Window`][CoreWindow].
+    ///
+    /// The input on the surface is reported for the window, and the surface isn't destroyed when
+    /// the returned window is dropped.
+    ///
+    /// # Safety
+    ///
+    /// The surface must be created on the connection of the event loop, and outlive the window.
+    pub unsafe fn adopt_window(
+        &self,
+        window: rwh_06::RawWindowHandle,
+    ) -> Result<Box<dyn CoreWindow>, RequestError> {
+        let surface = match window {
+            rwh_06::RawWindowHandle::Wayland(handle) => handle.surface,
+            _ => {
+                return Err(NotSupportedError::new("window handle is not a Wayland surface").into())
+            },
+        };
+
+        Ok(Box::new(unsafe { crate::Window::adopt(self, surface)? }))
+    }
+
     fn clear_exit(&self) {
         self.exit.set(None)
     }
@@ -69,6 +69,21 @@ pub trait EventLoopBuilderExtWayland {
     /// By default, the window is only allowed to be created on the main
     /// thread, to make platform compatibility easier.
     fn with_any_thread(&mut self, any_thread: bool) -> &mut Self;
+
+    /// Whether to connect to the compositor again when the connection is lost, for example when
+    /// the compositor restarts.
+    ///
+    /// The windows are lost with the connection, so the application is sent
+    /// [`destroy_surfaces`], and then [`can_create_surfaces`] once connected again, where the
+    /// windows should be re-created.
+    ///
+    /// This is ignored when the event loop uses the display connection of the host application.
+    /// By default, the event loop exits with [`EventLoopError::ConnectionLost`].
+    ///
+    /// [`destroy_surfaces`]: winit_core::application::ApplicationHandler::destroy_surfaces
+    /// [`can_create_surfaces`]: winit_core::application::ApplicationHandler::can_create_surfaces
+    /// [`EventLoopError::ConnectionLost`]: winit_core::error::EventLoopError::ConnectionLost
+    fn with_reconnect(&mut self, reconnect: bool) -> &mut Self;
 }
 
 /// Additional methods on [`Window`] that are specific to Wayland.
@@ -67,7 +67,7 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                     Some(window) => {
                         let mut window = window.lock().unwrap();
                         let was_unfocused = !window.has_focus();
-                        window.add_seat_focus(data.seat.id());
+                        window.add_seat_focus(&data.seat);
                         was_unfocused
                     },
                     None => return,
@@ -89,7 +89,7 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                 // HACK: this is just for GNOME not fixing their ordering issue of modifiers.
                 if std::mem::take(&mut seat_state.modifiers_pending) {
                     state.events_sink.push_window_event(
-                        WindowEvent::ModifiersChanged(seat_state.modifiers.into()),
+                        WindowEvent::ModifiersChanged(seat_state.modifiers),
                         window_id,
                     );
                 }
@@ -106,15 +106,21 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
 
                 // NOTE: The check whether the window exists is essential as we might get a
                 // nil surface, regardless of what protocol says.
-                let focused = match state.windows.get_mut().get(&window_id) {
+                let (focused, deactivated) = match state.windows.get_mut().get(&window_id) {
                     Some(window) => {
                         let mut window = window.lock().unwrap();
-                        window.remove_seat_focus(&data.seat.id());
-                        window.has_focus()
+                        let deactivated = window.remove_seat_focus(&data.seat.id());
+                        (window.has_focus(), deactivated)
                     },
                     None => return,
                 };
 
+                // The compositor doesn't notify about the destroyed inhibitors.
+                if deactivated {
+                    let event = WindowEvent::ShortcutsInhibited(false);
+                    state.events_sink.push_window_event(event, window_id);
+                }
+
                 // We don't need to update it above, because the next `Enter` will overwrite
                 // anyway.
                 *data.window_id.lock().unwrap() = None;
@@ -245,7 +251,7 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                 };
 
                 state.events_sink.push_window_event(
-                    WindowEvent::ModifiersChanged(seat_state.modifiers.into()),
+                    WindowEvent::ModifiersChanged(seat_state.modifiers),
                     window_id,
                 );
             },
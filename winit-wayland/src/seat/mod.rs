@@ -12,8 +12,7 @@ use sctk::reexports::protocols::wp::text_input::zv3::client::zwp_text_input_v3::
 use sctk::seat::pointer::{ThemeSpec, ThemedPointer};
 use sctk::seat::{Capability as SeatCapability, SeatHandler, SeatState};
 use tracing::warn;
-use winit_core::event::WindowEvent;
-use winit_core::keyboard::ModifiersState;
+use winit_core::event::{Modifiers, WindowEvent};
 
 use crate::state::WinitState;
 
@@ -23,6 +22,7 @@ mod text_input;
 mod touch;
 
 use keyboard::{KeyboardData, KeyboardState};
+pub use pointer::high_resolution_scroll::HighResolutionScrollState;
 pub use pointer::relative_pointer::RelativePointerState;
 pub use pointer::{PointerConstraintsState, WinitPointerData, WinitPointerDataExt};
 use text_input::TextInputData;
@@ -53,7 +53,7 @@ pub struct WinitSeatState {
     keyboard_state: Option<KeyboardState>,
 
     /// The current modifiers state on the seat.
-    modifiers: ModifiersState,
+    modifiers: Modifiers,
 
     /// Whether we have pending modifiers.
     modifiers_pending: bool,
@@ -63,6 +63,11 @@ impl WinitSeatState {
     pub fn new() -> Self {
         Default::default()
     }
+
+    /// The current modifiers state on the seat, if it has a keyboard.
+    pub fn modifiers(&self) -> Option<Modifiers> {
+        self.keyboard_state.as_ref().map(|_| self.modifiers)
+    }
 }
 
 impl SeatHandler for WinitState {
@@ -224,7 +229,11 @@ impl WinitState {
         for (window_id, window) in self.windows.get_mut() {
             let mut window = window.lock().unwrap();
             let had_focus = window.has_focus();
-            window.remove_seat_focus(seat);
+            // The compositor doesn't notify about the destroyed inhibitors.
+            if window.remove_seat_focus(seat) {
+                let event = WindowEvent::ShortcutsInhibited(false);
+                self.events_sink.push_window_event(event, *window_id);
+            }
             if had_focus != window.has_focus() {
                 self.events_sink.push_window_event(WindowEvent::Focused(false), *window_id);
             }
@@ -0,0 +1,175 @@
+//! High-resolution scroll.
+//!
+//! The `wl_pointer::axis_value120` event requires a `wl_seat` of version 8, while the seats are
+//! bound by sctk with version 7 at most, which can't be raised. Thus every seat is bound a second
+//! time, and the pointer created on it is marked through its data and used only for the scroll,
+//! while the scroll of the regular pointer is ignored.
+
+use std::sync::Mutex;
+
+use ahash::AHashMap;
+use sctk::reexports::client::globals::GlobalList;
+use sctk::reexports::client::protocol::wl_pointer::{self, WlPointer};
+use sctk::reexports::client::protocol::wl_registry::WlRegistry;
+use sctk::reexports::client::protocol::wl_seat::{self, WlSeat};
+use sctk::reexports::client::{delegate_dispatch, Connection, Dispatch, Proxy, QueueHandle};
+use sctk::registry::RegistryHandler;
+use sctk::seat::SeatState;
+
+use super::WinitPointerData;
+use crate::state::WinitState;
+
+/// The minimal seat version with `wl_pointer::axis_value120`.
+const SEAT_VERSION: u32 = 8;
+
+/// The seats bound for the high-resolution scroll.
+#[derive(Debug, Default)]
+pub struct HighResolutionScrollState {
+    /// The seats by their global name.
+    seats: AHashMap<u32, WlSeat>,
+}
+
+#[derive(Debug, Default)]
+pub struct HighResolutionSeatData {
+    /// The pointer only reporting the scroll.
+    pointer: Mutex<Option<WlPointer>>,
+}
+
+impl HighResolutionScrollState {
+    pub fn new(globals: &GlobalList, queue_handle: &QueueHandle<WinitState>) -> Self {
+        let mut state = Self::default();
+        globals.contents().with_list(|list| {
+            for global in list {
+                state.bind(
+                    globals.registry(),
+                    queue_handle,
+                    global.name,
+                    &global.interface,
+                    global.version,
+                );
+            }
+        });
+
+        state
+    }
+
+    /// Whether the scroll is reported by the high-resolution pointers instead of the regular ones.
+    ///
+    /// The compositors advertise all the seats with the same version, so either all or none of
+    /// them are bound here.
+    pub fn is_active(&self) -> bool {
+        !self.seats.is_empty()
+    }
+
+    fn bind(
+        &mut self,
+        registry: &WlRegistry,
+        queue_handle: &QueueHandle<WinitState>,
+        name: u32,
+        interface: &str,
+        version: u32,
+    ) {
+        if interface != WlSeat::interface().name || version < SEAT_VERSION {
+            return;
+        }
+
+        let seat =
+            registry.bind(name, SEAT_VERSION, queue_handle, HighResolutionSeatData::default());
+        self.seats.insert(name, seat);
+    }
+}
+
+impl RegistryHandler<WinitState> for HighResolutionScrollState {
+    fn new_global(
+        state: &mut WinitState,
+        _: &Connection,
+        queue_handle: &QueueHandle<WinitState>,
+        name: u32,
+        interface: &str,
+        version: u32,
+    ) {
+        let registry = state.registry_state.registry().clone();
+        state.high_resolution_scroll.bind(&registry, queue_handle, name, interface, version);
+    }
+
+    fn remove_global(
+        state: &mut WinitState,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+        name: u32,
+        _: &str,
+    ) {
+        let Some(seat) = state.high_resolution_scroll.seats.remove(&name) else {
+            return;
+        };
+
+        if let Some(data) = seat.data::<HighResolutionSeatData>() {
+            if let Some(pointer) = data.pointer.lock().unwrap().take() {
+                pointer.release();
+            }
+        }
+
+        seat.release();
+    }
+}
+
+impl Dispatch<WlSeat, HighResolutionSeatData, WinitState> for HighResolutionScrollState {
+    fn event(
+        _: &mut WinitState,
+        seat: &WlSeat,
+        event: <WlSeat as Proxy>::Event,
+        data: &HighResolutionSeatData,
+        _: &Connection,
+        queue_handle: &QueueHandle<WinitState>,
+    ) {
+        let wl_seat::Event::Capabilities { capabilities } = event else {
+            return;
+        };
+
+        let capabilities = wl_seat::Capability::from_bits_truncate(capabilities.into());
+        let mut pointer = data.pointer.lock().unwrap();
+        match (capabilities.contains(wl_seat::Capability::Pointer), pointer.take()) {
+            (true, None) => {
+                let data = WinitPointerData::high_resolution_scroll(seat.clone());
+                *pointer = Some(seat.get_pointer(queue_handle, data));
+            },
+            (false, Some(old_pointer)) => old_pointer.release(),
+            (_, old_pointer) => *pointer = old_pointer,
+        }
+    }
+}
+
+impl Dispatch<WlPointer, WinitPointerData, WinitState> for HighResolutionScrollState {
+    fn event(
+        state: &mut WinitState,
+        pointer: &WlPointer,
+        event: <WlPointer as Proxy>::Event,
+        data: &WinitPointerData,
+        connection: &Connection,
+        queue_handle: &QueueHandle<WinitState>,
+    ) {
+        // The high-resolution values are accumulated until the pointer frame, the rest is
+        // handled by sctk, which doesn't know about them.
+        if let wl_pointer::Event::AxisValue120 { axis, value120 } = event {
+            let mut inner = data.inner.lock().unwrap();
+            match axis.into_result() {
+                Ok(wl_pointer::Axis::HorizontalScroll) => inner.value120.0 += value120,
+                Ok(wl_pointer::Axis::VerticalScroll) => inner.value120.1 += value120,
+                _ => (),
+            }
+            return;
+        }
+
+        <SeatState as Dispatch<WlPointer, WinitPointerData, WinitState>>::event(
+            state,
+            pointer,
+            event,
+            data,
+            connection,
+            queue_handle,
+        );
+    }
+}
+
+delegate_dispatch!(WinitState: [WlSeat: HighResolutionSeatData] => HighResolutionScrollState);
+delegate_dispatch!(WinitState: [WlPointer: WinitPointerData] => HighResolutionScrollState);
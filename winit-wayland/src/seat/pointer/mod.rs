@@ -1,5 +1,6 @@
 //! The pointer events.
 
+use std::mem;
 use std::ops::Deref;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
@@ -7,7 +8,7 @@ use std::time::Duration;
 use tracing::warn;
 
 use sctk::reexports::client::delegate_dispatch;
-use sctk::reexports::client::protocol::wl_pointer::WlPointer;
+use sctk::reexports::client::protocol::wl_pointer::{AxisSource, WlPointer};
 use sctk::reexports::client::protocol::wl_seat::WlSeat;
 use sctk::reexports::client::protocol::wl_surface::WlSurface;
 use sctk::reexports::client::{Connection, Proxy, QueueHandle, Dispatch};
@@ -23,19 +24,20 @@ use sctk::reexports::protocols::wp::viewporter::client::wp_viewport::WpViewport;
 use sctk::compositor::SurfaceData;
 use sctk::globals::GlobalData;
 use sctk::seat::pointer::{
-    PointerData, PointerDataExt, PointerEvent, PointerEventKind, PointerHandler,
+    AxisScroll, PointerData, PointerDataExt, PointerEvent, PointerEventKind, PointerHandler,
 };
 use sctk::seat::SeatState;
 
 use dpi::{LogicalPosition, PhysicalPosition};
 use winit_core::event::{
-    ElementState, MouseButton, MouseScrollDelta, PointerKind, PointerSource, TouchPhase,
-    WindowEvent,
+    ElementState, MouseButton, MouseScrollDelta, PointerKind, PointerSource, ScrollSource,
+    TouchPhase, WindowEvent,
 };
 
 use crate::state::WinitState;
 use crate::WindowId;
 
+pub mod high_resolution_scroll;
 pub mod relative_pointer;
 
 impl PointerHandler for WinitState {
@@ -46,6 +48,11 @@ impl PointerHandler for WinitState {
         pointer: &WlPointer,
         events: &[PointerEvent],
     ) {
+        if pointer.winit_data().is_high_resolution_scroll() {
+            self.high_resolution_pointer_frame(pointer, events);
+            return;
+        }
+
         let seat = pointer.winit_data().seat();
         let seat_state = match self.seats.get(&seat.id()) {
             Some(seat_state) => seat_state,
@@ -66,11 +73,12 @@ impl PointerHandler for WinitState {
         for event in events {
             let surface = &event.surface;
 
-            // The parent surface.
-            let parent_surface = match event.surface.data::<SurfaceData>() {
-                Some(data) => data.parent_surface().unwrap_or(surface),
-                None => continue,
-            };
+            // The parent surface, the surfaces adopted from the host have no data.
+            let parent_surface = event
+                .surface
+                .data::<SurfaceData>()
+                .and_then(SurfaceData::parent_surface)
+                .unwrap_or(surface);
 
             let window_id = crate::make_wid(parent_surface);
 
@@ -190,56 +198,114 @@ impl PointerHandler for WinitState {
                         window_id,
                     );
                 },
-                PointerEventKind::Axis { horizontal, vertical, .. } => {
-                    // Get the current phase.
-                    let mut pointer_data = pointer.winit_data().inner.lock().unwrap();
-
-                    let has_discrete_scroll = horizontal.discrete != 0 || vertical.discrete != 0;
-
-                    // Figure out what to do about start/ended phases here.
-                    //
-                    // Figure out how to deal with `Started`. Also the `Ended` is not guaranteed
-                    // to be sent for mouse wheels.
-                    let phase = if horizontal.stop || vertical.stop {
-                        TouchPhase::Ended
-                    } else {
-                        match pointer_data.phase {
-                            // Discrete scroll only results in moved events.
-                            _ if has_discrete_scroll => TouchPhase::Moved,
-                            TouchPhase::Started | TouchPhase::Moved => TouchPhase::Moved,
-                            _ => TouchPhase::Started,
-                        }
-                    };
-
-                    // Update the phase.
-                    pointer_data.phase = phase;
-
-                    // Mice events have both pixel and discrete delta's at the same time. So prefer
-                    // the discrete values if they are present.
-                    let delta = if has_discrete_scroll {
-                        // NOTE: Wayland sign convention is the inverse of winit.
-                        MouseScrollDelta::LineDelta(
-                            (-horizontal.discrete) as f32,
-                            (-vertical.discrete) as f32,
-                        )
-                    } else {
-                        // NOTE: Wayland sign convention is the inverse of winit.
-                        MouseScrollDelta::PixelDelta(
-                            LogicalPosition::new(-horizontal.absolute, -vertical.absolute)
-                                .to_physical(scale_factor),
-                        )
-                    };
-
-                    self.events_sink.push_window_event(
-                        WindowEvent::MouseWheel { device_id: None, delta, phase },
-                        window_id,
-                    )
+                // The high-resolution pointers report the scroll instead.
+                PointerEventKind::Axis { .. } if self.high_resolution_scroll.is_active() => (),
+                PointerEventKind::Axis { horizontal, vertical, source, .. } => {
+                    let event = axis_event(pointer, horizontal, vertical, source, scale_factor);
+                    self.events_sink.push_window_event(event, window_id);
                 },
             }
         }
     }
 }
 
+impl WinitState {
+    /// Handle the frame of a high-resolution pointer, which only reports the scroll.
+    fn high_resolution_pointer_frame(&mut self, pointer: &WlPointer, events: &[PointerEvent]) {
+        for event in events {
+            let PointerEventKind::Axis { horizontal, vertical, source, .. } = event.kind else {
+                continue;
+            };
+
+            let parent_surface = event
+                .surface
+                .data::<SurfaceData>()
+                .and_then(SurfaceData::parent_surface)
+                .unwrap_or(&event.surface);
+
+            let window_id = crate::make_wid(parent_surface);
+            let scale_factor = match self.windows.get_mut().get(&window_id) {
+                Some(window) => window.lock().unwrap().scale_factor(),
+                None => continue,
+            };
+
+            let event = axis_event(pointer, horizontal, vertical, source, scale_factor);
+            self.events_sink.push_window_event(event, window_id);
+        }
+    }
+}
+
+/// Build the mouse wheel event from the axis events of a pointer frame.
+fn axis_event(
+    pointer: &WlPointer,
+    horizontal: AxisScroll,
+    vertical: AxisScroll,
+    source: Option<AxisSource>,
+    scale_factor: f64,
+) -> WindowEvent {
+    // Get the current phase.
+    let mut pointer_data = pointer.winit_data().inner.lock().unwrap();
+
+    // The source is only sent with the first frame of the scroll sequence.
+    if let Some(source) = source {
+        pointer_data.scroll_source = match source {
+            AxisSource::Wheel => ScrollSource::Wheel,
+            AxisSource::Finger => ScrollSource::Finger,
+            AxisSource::Continuous => ScrollSource::Continuous,
+            AxisSource::WheelTilt => ScrollSource::WheelTilt,
+            _ => ScrollSource::Unknown,
+        };
+    }
+    let source = pointer_data.scroll_source;
+
+    // The discrete steps are replaced by the high-resolution values since `wl_pointer` v8.
+    let value120 = if pointer.winit_data().is_high_resolution_scroll() {
+        mem::take(&mut pointer_data.value120)
+    } else {
+        (horizontal.discrete * 120, vertical.discrete * 120)
+    };
+    let has_discrete_scroll = value120 != (0, 0);
+
+    // Figure out what to do about start/ended phases here.
+    //
+    // Figure out how to deal with `Started`. Also the `Ended` is not guaranteed
+    // to be sent for mouse wheels.
+    let phase = if horizontal.stop || vertical.stop {
+        TouchPhase::Ended
+    } else {
+        match pointer_data.phase {
+            // Discrete scroll only results in moved events.
+            _ if has_discrete_scroll => TouchPhase::Moved,
+            TouchPhase::Started | TouchPhase::Moved => TouchPhase::Moved,
+            _ => TouchPhase::Started,
+        }
+    };
+
+    // Update the phase.
+    pointer_data.phase = phase;
+    if phase == TouchPhase::Ended {
+        pointer_data.scroll_source = ScrollSource::Unknown;
+    }
+
+    // Mice events have both pixel and discrete delta's at the same time. So prefer
+    // the discrete values if they are present.
+    let delta = if has_discrete_scroll {
+        // NOTE: Wayland sign convention is the inverse of winit.
+        MouseScrollDelta::LineDelta(-value120.0 as f32 / 120., -value120.1 as f32 / 120.)
+    } else {
+        // NOTE: Wayland sign convention is the inverse of winit.
+        MouseScrollDelta::PixelDelta(
+            LogicalPosition::new(-horizontal.absolute, -vertical.absolute)
+                .to_physical(scale_factor),
+        )
+    };
+
+    // NOTE: Wayland sign convention is the inverse of winit.
+    let value120 = has_discrete_scroll.then_some((-value120.0, -value120.1));
+
+    WindowEvent::MouseWheel { device_id: None, delta, phase, source, value120 }
+}
+
 #[derive(Debug)]
 pub struct WinitPointerData {
     /// The inner winit data associated with the pointer.
@@ -250,6 +316,10 @@ pub struct WinitPointerData {
 
     /// Viewport for fractional cursor.
     viewport: Option<WpViewport>,
+
+    /// Whether the pointer was created on a seat bound for the high-resolution scroll, and only
+    /// reports the scroll.
+    high_resolution_scroll: bool,
 }
 
 impl WinitPointerData {
@@ -258,9 +328,22 @@ impl WinitPointerData {
             inner: Mutex::new(WinitPointerDataInner::default()),
             sctk_data: PointerData::new(seat),
             viewport,
+            high_resolution_scroll: false,
         }
     }
 
+    /// The data of a pointer created on a seat bound for the high-resolution scroll.
+    pub fn high_resolution_scroll(seat: WlSeat) -> Self {
+        let mut data = Self::new(seat, None);
+        data.high_resolution_scroll = true;
+        data
+    }
+
+    #[inline]
+    pub fn is_high_resolution_scroll(&self) -> bool {
+        self.high_resolution_scroll
+    }
+
     pub fn lock_pointer(
         &self,
         pointer_constraints: &PointerConstraintsState,
@@ -374,6 +457,12 @@ pub struct WinitPointerDataInner {
 
     /// Current axis phase.
     phase: TouchPhase,
+
+    /// Source of the current axis sequence.
+    scroll_source: ScrollSource,
+
+    /// The high-resolution scroll of the current frame.
+    value120: (i32, i32),
 }
 
 impl Drop for WinitPointerDataInner {
@@ -396,6 +485,8 @@ impl Default for WinitPointerDataInner {
             confined_pointer: None,
             latest_button_serial: 0,
             phase: TouchPhase::Ended,
+            scroll_source: ScrollSource::Unknown,
+            value120: (0, 0),
         }
     }
 }
@@ -516,7 +607,6 @@ impl Dispatch<WpCursorShapeManagerV1, GlobalData, WinitState> for SeatState {
     }
 }
 
-delegate_dispatch!(WinitState: [ WlPointer: WinitPointerData] => SeatState);
 delegate_dispatch!(WinitState: [ WpCursorShapeManagerV1: GlobalData] => SeatState);
 delegate_dispatch!(WinitState: [ WpCursorShapeDeviceV1: GlobalData] => SeatState);
 delegate_dispatch!(WinitState: [ZwpPointerConstraintsV1: GlobalData] => PointerConstraintsState);
@@ -8,7 +8,7 @@ use sctk::reexports::protocols::wp::text_input::zv3::client::zwp_text_input_mana
 use sctk::reexports::protocols::wp::text_input::zv3::client::zwp_text_input_v3::{
     ContentHint, ContentPurpose, Event as TextInputEvent, ZwpTextInputV3,
 };
-use winit_core::event::{Ime, WindowEvent};
+use winit_core::event::{Ime, PreeditSegment, PreeditStyle, WindowEvent};
 use winit_core::window::ImePurpose;
 
 use crate::state::WinitState;
@@ -70,8 +70,7 @@ impl Dispatch<ZwpTextInputV3, TextInputData, WinitState> for TextInputState {
                 };
 
                 if window.ime_allowed() {
-                    text_input.enable();
-                    text_input.set_content_type_by_purpose(window.ime_purpose());
+                    window.enable_text_input(text_input);
                     text_input.commit();
                     state.events_sink.push_window_event(WindowEvent::Ime(Ime::Enabled), window_id);
                 }
@@ -124,7 +123,15 @@ impl Dispatch<ZwpTextInputV3, TextInputData, WinitState> for TextInputState {
                     || text_input_data.pending_preedit.is_none()
                 {
                     state.events_sink.push_window_event(
-                        WindowEvent::Ime(Ime::Preedit(String::new(), None)),
+                        WindowEvent::Ime(Ime::Preedit(String::new(), None, Vec::new())),
+                        window_id,
+                    );
+                }
+
+                // Send `DeleteSurrounding`.
+                if let Some((before, after)) = text_input_data.pending_delete.take() {
+                    state.events_sink.push_window_event(
+                        WindowEvent::Ime(Ime::DeleteSurrounding { before, after }),
                         window_id,
                     );
                 }
@@ -140,23 +147,50 @@ impl Dispatch<ZwpTextInputV3, TextInputData, WinitState> for TextInputState {
                 if let Some(preedit) = text_input_data.pending_preedit.take() {
                     let cursor_range =
                         preedit.cursor_begin.map(|b| (b, preedit.cursor_end.unwrap_or(b)));
+                    let segments = preedit_segments(preedit.text.len(), cursor_range);
 
                     state.events_sink.push_window_event(
-                        WindowEvent::Ime(Ime::Preedit(preedit.text, cursor_range)),
+                        WindowEvent::Ime(Ime::Preedit(preedit.text, cursor_range, segments)),
                         window_id,
                     );
                 }
             },
-            TextInputEvent::DeleteSurroundingText { .. } => {
-                // Not handled.
+            TextInputEvent::DeleteSurroundingText { before_length, after_length } => {
+                text_input_data.pending_delete =
+                    Some((before_length as usize, after_length as usize));
             },
             _ => {},
         }
     }
 }
 
+/// Split the preedit into underlined segments, marking the non-empty cursor range as selected,
+/// since text-input-v3 uses it to highlight the clause being converted.
+fn preedit_segments(len: usize, cursor_range: Option<(usize, usize)>) -> Vec<PreeditSegment> {
+    let segment = |start, end, style| PreeditSegment { start, end, style };
+    let (start, end) = match cursor_range {
+        Some((begin, end)) if begin != end => (begin.min(end), begin.max(end)),
+        _ if len == 0 => return Vec::new(),
+        _ => return vec![segment(0, len, PreeditStyle::UNDERLINE)],
+    };
+
+    [
+        segment(0, start, PreeditStyle::UNDERLINE),
+        segment(start, end, PreeditStyle::UNDERLINE | PreeditStyle::SELECTED),
+        segment(end, len, PreeditStyle::UNDERLINE),
+    ]
+    .into_iter()
+    .filter(|segment| segment.start < segment.end)
+    .collect()
+}
+
+/// The maximum size of the surrounding text allowed by the protocol.
+const MAX_SURROUNDING_TEXT_BYTES: usize = 4000;
+
 pub trait ZwpTextInputV3Ext {
     fn set_content_type_by_purpose(&self, purpose: ImePurpose);
+
+    fn set_surrounding_text_trimmed(&self, text: &str, cursor: usize, anchor: usize);
 }
 
 impl ZwpTextInputV3Ext for ZwpTextInputV3 {
@@ -168,6 +202,41 @@ impl ZwpTextInputV3Ext for ZwpTextInputV3 {
         };
         self.set_content_type(hint, purpose);
     }
+
+    fn set_surrounding_text_trimmed(&self, text: &str, cursor: usize, anchor: usize) {
+        if !text.is_char_boundary(cursor) || !text.is_char_boundary(anchor) {
+            tracing::warn!("IME surrounding text cursor or anchor is not on a char boundary");
+            return;
+        }
+
+        if text.len() <= MAX_SURROUNDING_TEXT_BYTES {
+            self.set_surrounding_text(text.to_owned(), cursor as i32, anchor as i32);
+            return;
+        }
+
+        // Collapse the selection when it can't fit.
+        let anchor =
+            if cursor.abs_diff(anchor) > MAX_SURROUNDING_TEXT_BYTES { cursor } else { anchor };
+        let (low, high) = (cursor.min(anchor), cursor.max(anchor));
+
+        // Center the selection in the sent text, snapping to char boundaries.
+        let budget = MAX_SURROUNDING_TEXT_BYTES - (high - low);
+        let before = low.min(budget / 2);
+        let mut end = (high + budget - before).min(text.len());
+        let mut start = end.saturating_sub(MAX_SURROUNDING_TEXT_BYTES);
+        while !text.is_char_boundary(start) {
+            start += 1;
+        }
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+
+        self.set_surrounding_text(
+            text[start..end].to_owned(),
+            (cursor - start) as i32,
+            (anchor - start) as i32,
+        );
+    }
 }
 
 /// The Data associated with the text input.
@@ -186,6 +255,9 @@ pub struct TextInputDataInner {
 
     /// The preedit to submit on `done`.
     pending_preedit: Option<Preedit>,
+
+    /// The surrounding text deletion to submit on `done`.
+    pending_delete: Option<(usize, usize)>,
 }
 
 /// The state of the preedit.
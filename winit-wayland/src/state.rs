@@ -25,11 +25,15 @@ use winit_core::error::OsError;
 use crate::event_loop::sink::EventSink;
 use crate::output::MonitorHandle;
 use crate::seat::{
-    PointerConstraintsState, RelativePointerState, TextInputState, WinitPointerData,
-    WinitPointerDataExt, WinitSeatState,
+    HighResolutionScrollState, PointerConstraintsState, RelativePointerState, TextInputState,
+    WinitPointerData, WinitPointerDataExt, WinitSeatState,
 };
 use crate::types::kwin_blur::KWinBlurManager;
+use crate::types::wp_content_type::ContentTypeManager;
 use crate::types::wp_fractional_scaling::FractionalScalingManager;
+use crate::types::wp_keyboard_shortcuts_inhibit::KeyboardShortcutsInhibitManager;
+use crate::types::wp_presentation::PresentationManager;
+use crate::types::wp_tearing_control::TearingControlManager;
 use crate::types::wp_viewporter::ViewporterState;
 use crate::types::xdg_activation::XdgActivationState;
 use crate::window::{WindowRequests, WindowState};
@@ -96,6 +100,9 @@ pub struct WinitState {
     /// Relative pointer.
     pub relative_pointer: Option<RelativePointerState>,
 
+    /// The seats bound for the high-resolution scroll.
+    pub high_resolution_scroll: HighResolutionScrollState,
+
     /// Pointer constraints to handle pointer locking and confining.
     pub pointer_constraints: Option<Arc<PointerConstraintsState>>,
 
@@ -108,6 +115,18 @@ pub struct WinitState {
     /// KWin blur manager.
     pub kwin_blur_manager: Option<KWinBlurManager>,
 
+    /// Keyboard shortcuts inhibit manager.
+    pub keyboard_shortcuts_inhibit_manager: Option<KeyboardShortcutsInhibitManager>,
+
+    /// Presentation time manager.
+    pub presentation_manager: Option<PresentationManager>,
+
+    /// Tearing control manager.
+    pub tearing_control_manager: Option<TearingControlManager>,
+
+    /// Content type manager.
+    pub content_type_manager: Option<ContentTypeManager>,
+
     /// Loop handle to re-register event sources, such as keyboard repeat.
     pub loop_handle: LoopHandle<'static, Self>,
 
@@ -179,11 +198,20 @@ impl WinitState {
             viewporter_state,
             fractional_scaling_manager,
             kwin_blur_manager: KWinBlurManager::new(globals, queue_handle).ok(),
+            keyboard_shortcuts_inhibit_manager: KeyboardShortcutsInhibitManager::new(
+                globals,
+                queue_handle,
+            )
+            .ok(),
+            presentation_manager: PresentationManager::new(globals, queue_handle).ok(),
+            tearing_control_manager: TearingControlManager::new(globals, queue_handle).ok(),
+            content_type_manager: ContentTypeManager::new(globals, queue_handle).ok(),
 
             seats,
             text_input_state: TextInputState::new(globals, queue_handle).ok(),
 
             relative_pointer: RelativePointerState::new(globals, queue_handle).ok(),
+            high_resolution_scroll: HighResolutionScrollState::new(globals, queue_handle),
             pointer_constraints: PointerConstraintsState::new(globals, queue_handle)
                 .map(Arc::new)
                 .ok(),
@@ -400,7 +428,7 @@ impl CompositorHandler for WinitState {
 }
 
 impl ProvidesRegistryState for WinitState {
-    sctk::registry_handlers![OutputState, SeatState];
+    sctk::registry_handlers![OutputState, SeatState, HighResolutionScrollState];
 
     fn registry(&mut self) -> &mut RegistryState {
         &mut self.registry_state
@@ -2,6 +2,10 @@
 
 pub mod cursor;
 pub mod kwin_blur;
+pub mod wp_content_type;
 pub mod wp_fractional_scaling;
+pub mod wp_keyboard_shortcuts_inhibit;
+pub mod wp_presentation;
+pub mod wp_tearing_control;
 pub mod wp_viewporter;
 pub mod xdg_activation;
@@ -0,0 +1,63 @@
+//! Handling of the wp-content-type.
+
+use sctk::globals::GlobalData;
+use sctk::reexports::client::globals::{BindError, GlobalList};
+use sctk::reexports::client::protocol::wl_surface::WlSurface;
+use sctk::reexports::client::{delegate_dispatch, Connection, Dispatch, Proxy, QueueHandle};
+use sctk::reexports::protocols::wp::content_type::v1::client::wp_content_type_manager_v1::WpContentTypeManagerV1;
+use sctk::reexports::protocols::wp::content_type::v1::client::wp_content_type_v1::WpContentTypeV1;
+
+use crate::state::WinitState;
+
+/// Content type manager.
+#[derive(Debug, Clone)]
+pub struct ContentTypeManager {
+    manager: WpContentTypeManagerV1,
+}
+
+impl ContentTypeManager {
+    pub fn new(
+        globals: &GlobalList,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Result<Self, BindError> {
+        let manager = globals.bind(queue_handle, 1..=1, GlobalData)?;
+        Ok(Self { manager })
+    }
+
+    pub fn content_type(
+        &self,
+        surface: &WlSurface,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> WpContentTypeV1 {
+        self.manager.get_surface_content_type(surface, queue_handle, GlobalData)
+    }
+}
+
+impl Dispatch<WpContentTypeManagerV1, GlobalData, WinitState> for ContentTypeManager {
+    fn event(
+        _: &mut WinitState,
+        _: &WpContentTypeManagerV1,
+        _: <WpContentTypeManagerV1 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        // No events.
+    }
+}
+
+impl Dispatch<WpContentTypeV1, GlobalData, WinitState> for ContentTypeManager {
+    fn event(
+        _: &mut WinitState,
+        _: &WpContentTypeV1,
+        _: <WpContentTypeV1 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        // No events.
+    }
+}
+
+delegate_dispatch!(WinitState: [WpContentTypeManagerV1: GlobalData] => ContentTypeManager);
+delegate_dispatch!(WinitState: [WpContentTypeV1: GlobalData] => ContentTypeManager);
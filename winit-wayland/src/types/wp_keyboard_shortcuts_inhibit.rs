@@ -0,0 +1,102 @@
+//! Handling of the keyboard shortcuts inhibition.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use sctk::globals::GlobalData;
+use sctk::reexports::client::globals::{BindError, GlobalList};
+use sctk::reexports::client::protocol::wl_seat::WlSeat;
+use sctk::reexports::client::protocol::wl_surface::WlSurface;
+use sctk::reexports::client::{delegate_dispatch, Connection, Dispatch, Proxy, QueueHandle};
+use sctk::reexports::protocols::wp::keyboard_shortcuts_inhibit::zv1::client::zwp_keyboard_shortcuts_inhibit_manager_v1::ZwpKeyboardShortcutsInhibitManagerV1;
+use sctk::reexports::protocols::wp::keyboard_shortcuts_inhibit::zv1::client::zwp_keyboard_shortcuts_inhibitor_v1::{
+    Event as KeyboardShortcutsInhibitorEvent, ZwpKeyboardShortcutsInhibitorV1,
+};
+use winit_core::event::WindowEvent;
+
+use crate::state::WinitState;
+
+/// Keyboard shortcuts inhibit manager.
+#[derive(Debug, Clone)]
+pub struct KeyboardShortcutsInhibitManager {
+    manager: ZwpKeyboardShortcutsInhibitManagerV1,
+}
+
+pub struct KeyboardShortcutsInhibitor {
+    /// The surface the shortcuts are inhibited for.
+    surface: WlSurface,
+
+    /// Whether the compositor reported the inhibitor as active.
+    active: AtomicBool,
+}
+
+impl KeyboardShortcutsInhibitor {
+    /// Whether the inhibitor is active, the compositor doesn't notify when it's destroyed.
+    pub fn is_active(&self) -> bool {
+        self.active.load(Ordering::Relaxed)
+    }
+}
+
+impl KeyboardShortcutsInhibitManager {
+    pub fn new(
+        globals: &GlobalList,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Result<Self, BindError> {
+        let manager = globals.bind(queue_handle, 1..=1, GlobalData)?;
+        Ok(Self { manager })
+    }
+
+    pub fn inhibit_shortcuts(
+        &self,
+        surface: &WlSurface,
+        seat: &WlSeat,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> ZwpKeyboardShortcutsInhibitorV1 {
+        let data = KeyboardShortcutsInhibitor { surface: surface.clone(), active: false.into() };
+        self.manager.inhibit_shortcuts(surface, seat, queue_handle, data)
+    }
+}
+
+impl Dispatch<ZwpKeyboardShortcutsInhibitManagerV1, GlobalData, WinitState>
+    for KeyboardShortcutsInhibitManager
+{
+    fn event(
+        _: &mut WinitState,
+        _: &ZwpKeyboardShortcutsInhibitManagerV1,
+        _: <ZwpKeyboardShortcutsInhibitManagerV1 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        // No events.
+    }
+}
+
+impl Dispatch<ZwpKeyboardShortcutsInhibitorV1, KeyboardShortcutsInhibitor, WinitState>
+    for KeyboardShortcutsInhibitManager
+{
+    fn event(
+        state: &mut WinitState,
+        _: &ZwpKeyboardShortcutsInhibitorV1,
+        event: <ZwpKeyboardShortcutsInhibitorV1 as Proxy>::Event,
+        data: &KeyboardShortcutsInhibitor,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        let active = match event {
+            KeyboardShortcutsInhibitorEvent::Active => true,
+            KeyboardShortcutsInhibitorEvent::Inactive => false,
+            _ => return,
+        };
+
+        // Only report the changes, the inhibitors start inactive.
+        if data.active.swap(active, Ordering::Relaxed) == active {
+            return;
+        }
+
+        let window_id = crate::make_wid(&data.surface);
+        state.events_sink.push_window_event(WindowEvent::ShortcutsInhibited(active), window_id);
+    }
+}
+
+delegate_dispatch!(WinitState: [ZwpKeyboardShortcutsInhibitManagerV1: GlobalData] => KeyboardShortcutsInhibitManager);
+delegate_dispatch!(WinitState: [ZwpKeyboardShortcutsInhibitorV1: KeyboardShortcutsInhibitor] => KeyboardShortcutsInhibitManager);
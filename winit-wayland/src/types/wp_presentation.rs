@@ -0,0 +1,130 @@
+//! Handling of the wp-presentation.
+
+use std::time::Duration;
+
+use sctk::globals::GlobalData;
+use sctk::reexports::client::globals::{BindError, GlobalList};
+use sctk::reexports::client::protocol::wl_surface::WlSurface;
+use sctk::reexports::client::{delegate_dispatch, Connection, Dispatch, Proxy, QueueHandle, WEnum};
+use sctk::reexports::protocols::wp::presentation_time::client::wp_presentation::{
+    Event as PresentationEvent, WpPresentation,
+};
+use sctk::reexports::protocols::wp::presentation_time::client::wp_presentation_feedback::{
+    Event as PresentationFeedbackEvent, Kind, WpPresentationFeedback,
+};
+use winit_core::event::{PresentationFlags, WindowEvent};
+
+use crate::state::WinitState;
+
+/// Presentation time manager.
+#[derive(Debug, Clone)]
+pub struct PresentationManager {
+    presentation: WpPresentation,
+
+    /// The clock the compositor reports the timestamps on.
+    clock_id: u32,
+}
+
+pub struct PresentationFeedback {
+    /// The surface the feedback was requested for.
+    surface: WlSurface,
+}
+
+impl PresentationManager {
+    pub fn new(
+        globals: &GlobalList,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Result<Self, BindError> {
+        let presentation = globals.bind(queue_handle, 1..=1, GlobalData)?;
+        Ok(Self { presentation, clock_id: libc::CLOCK_MONOTONIC as u32 })
+    }
+
+    /// Request the presentation feedback for the next commit of the surface.
+    pub fn feedback(&self, surface: &WlSurface, queue_handle: &QueueHandle<WinitState>) {
+        let data = PresentationFeedback { surface: surface.clone() };
+        self.presentation.feedback(surface, queue_handle, data);
+    }
+}
+
+impl Dispatch<WpPresentation, GlobalData, WinitState> for PresentationManager {
+    fn event(
+        state: &mut WinitState,
+        _: &WpPresentation,
+        event: <WpPresentation as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        if let PresentationEvent::ClockId { clk_id } = event {
+            if let Some(manager) = state.presentation_manager.as_mut() {
+                manager.clock_id = clk_id;
+            }
+        }
+    }
+}
+
+impl Dispatch<WpPresentationFeedback, PresentationFeedback, WinitState> for PresentationManager {
+    fn event(
+        state: &mut WinitState,
+        _: &WpPresentationFeedback,
+        event: <WpPresentationFeedback as Proxy>::Event,
+        data: &PresentationFeedback,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        // NOTE: discarded frames never reached the screen, so there's nothing to report.
+        let PresentationFeedbackEvent::Presented {
+            tv_sec_hi,
+            tv_sec_lo,
+            tv_nsec,
+            refresh,
+            flags,
+            ..
+        } = event
+        else {
+            return;
+        };
+
+        let clock_id = match state.presentation_manager.as_ref() {
+            Some(manager) => manager.clock_id,
+            None => return,
+        };
+
+        let seconds = ((tv_sec_hi as u64) << 32) | tv_sec_lo as u64;
+        let presented_at = to_monotonic(clock_id, Duration::new(seconds, tv_nsec));
+        let refresh_interval = (refresh != 0).then(|| Duration::from_nanos(refresh as u64));
+
+        let kind = match flags {
+            WEnum::Value(kind) => kind,
+            WEnum::Unknown(kind) => Kind::from_bits_truncate(kind),
+        };
+        let mut flags = PresentationFlags::empty();
+        flags.set(PresentationFlags::VSYNC, kind.contains(Kind::Vsync));
+        flags.set(PresentationFlags::HW_CLOCK, kind.contains(Kind::HwClock));
+        flags.set(PresentationFlags::HW_COMPLETION, kind.contains(Kind::HwCompletion));
+        flags.set(PresentationFlags::ZERO_COPY, kind.contains(Kind::ZeroCopy));
+
+        let window_id = crate::make_wid(&data.surface);
+        let event = WindowEvent::FramePresented { presented_at, refresh_interval, flags };
+        state.events_sink.push_window_event(event, window_id);
+    }
+}
+
+/// Convert a timestamp on the given clock to `CLOCK_MONOTONIC`.
+fn to_monotonic(clock_id: u32, time: Duration) -> Duration {
+    if clock_id == libc::CLOCK_MONOTONIC as u32 {
+        return time;
+    }
+
+    let now = |clock_id| {
+        let mut now = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+        unsafe { libc::clock_gettime(clock_id, &mut now) };
+        Duration::new(now.tv_sec as u64, now.tv_nsec as u32)
+    };
+
+    let elapsed = now(clock_id as libc::clockid_t).saturating_sub(time);
+    now(libc::CLOCK_MONOTONIC).saturating_sub(elapsed)
+}
+
+delegate_dispatch!(WinitState: [WpPresentation: GlobalData] => PresentationManager);
+delegate_dispatch!(WinitState: [WpPresentationFeedback: PresentationFeedback] => PresentationManager);
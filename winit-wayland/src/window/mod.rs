@@ -6,7 +6,8 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 
 use dpi::{LogicalSize, PhysicalInsets, PhysicalPosition, PhysicalSize, Position, Size};
-use sctk::compositor::{CompositorState, Region, SurfaceData};
+use sctk::compositor::SurfaceData;
+use sctk::reexports::client::backend::ObjectId;
 use sctk::reexports::client::protocol::wl_display::WlDisplay;
 use sctk::reexports::client::protocol::wl_surface::WlSurface;
 use sctk::reexports::client::{Proxy, QueueHandle};
@@ -20,8 +21,8 @@ use winit_core::event::{Ime, WindowEvent};
 use winit_core::event_loop::AsyncRequestSerial;
 use winit_core::monitor::{Fullscreen, MonitorHandle as CoreMonitorHandle};
 use winit_core::window::{
-    CursorGrabMode, ImePurpose, ResizeDirection, Theme, UserAttentionType, Window as CoreWindow,
-    WindowAttributes, WindowButtons, WindowId, WindowLevel,
+    CursorGrabMode, ImePurpose, PresentationHint, ResizeDirection, Theme, UserAttentionType,
+    Window as CoreWindow, WindowAttributes, WindowButtons, WindowId, WindowLevel,
 };
 
 use super::event_loop::sink::EventSink;
@@ -38,8 +39,11 @@ pub use state::WindowState;
 /// The Wayland window.
 #[derive(Debug)]
 pub struct Window {
-    /// Reference to the underlying SCTK window.
-    window: SctkWindow,
+    /// Reference to the underlying SCTK window, `None` for the surfaces adopted from the host.
+    window: Option<SctkWindow>,
+
+    /// The surface of the window.
+    surface: WlSurface,
 
     /// Window id.
     window_id: WindowId,
@@ -47,9 +51,6 @@ pub struct Window {
     /// The state of the window.
     window_state: Arc<Mutex<WindowState>>,
 
-    /// Compositor to handle WlRegion stuff.
-    compositor: Arc<CompositorState>,
-
     /// The wayland display used solely for raw window handle.
     #[allow(dead_code)]
     display: WlDisplay,
@@ -87,7 +88,6 @@ impl Window {
         let monitors = state.monitors.clone();
 
         let surface = state.compositor_state.create_surface(&queue_handle);
-        let compositor = state.compositor_state.clone();
         let xdg_activation =
             state.xdg_activation.as_ref().map(|activation_state| activation_state.global().clone());
         let display = event_loop_window_target.handle.connection.display();
@@ -110,7 +110,8 @@ impl Window {
             &event_loop_window_target.queue_handle,
             &state,
             size,
-            window.clone(),
+            surface.clone(),
+            Some(window.clone()),
             attributes.preferred_theme,
         );
 
@@ -146,6 +147,7 @@ impl Window {
 
         // Non-resizable implies that the min and max sizes are set to the same value.
         window_state.set_resizable(attributes.resizable);
+        window_state.set_enabled_buttons(attributes.enabled_buttons);
 
         // Set startup mode.
         match attributes.fullscreen {
@@ -207,11 +209,11 @@ impl Window {
         event_loop_awakener.ping();
 
         Ok(Self {
-            window,
+            window: Some(window),
+            surface,
             display,
             monitors,
             window_id,
-            compositor,
             window_state,
             queue_handle,
             xdg_activation,
@@ -222,8 +224,65 @@ impl Window {
         })
     }
 
+    /// Wrap the `wl_surface` of the host, created on the connection of the event loop.
+    ///
+    /// # Safety
+    ///
+    /// `surface` must point to a valid `wl_surface`, which outlives the window.
+    pub(crate) unsafe fn adopt(
+        event_loop_window_target: &ActiveEventLoop,
+        surface: NonNull<c_void>,
+    ) -> Result<Self, RequestError> {
+        let queue_handle = event_loop_window_target.queue_handle.clone();
+        let connection = &event_loop_window_target.handle.connection;
+        let mut state = event_loop_window_target.state.borrow_mut();
+
+        let id = unsafe { ObjectId::from_ptr(WlSurface::interface(), surface.as_ptr().cast()) }
+            .map_err(|_| NotSupportedError::new("window handle is not a wl_surface"))?;
+        let surface = WlSurface::from_id(connection, id).map_err(|err| os_error!(err))?;
+
+        // The size of the surface is only known to the host.
+        let window_state = WindowState::new(
+            event_loop_window_target.handle.clone(),
+            &queue_handle,
+            &state,
+            PhysicalSize::new(0, 0).into(),
+            surface.clone(),
+            None,
+            None,
+        );
+
+        let window_state = Arc::new(Mutex::new(window_state));
+        let window_id = super::make_wid(&surface);
+        state.windows.get_mut().insert(window_id, window_state.clone());
+
+        // The host draws the surface on its own.
+        let window_requests = WindowRequests {
+            redraw_requested: AtomicBool::new(false),
+            closed: AtomicBool::new(false),
+        };
+        let window_requests = Arc::new(window_requests);
+        state.window_requests.get_mut().insert(window_id, window_requests.clone());
+
+        Ok(Self {
+            window: None,
+            surface,
+            display: connection.display(),
+            monitors: state.monitors.clone(),
+            window_id,
+            window_state,
+            queue_handle,
+            xdg_activation: state.xdg_activation.as_ref().map(|state| state.global().clone()),
+            attention_requested: Arc::new(AtomicBool::new(false)),
+            event_loop_awakener: event_loop_window_target.event_loop_awakener.clone(),
+            window_requests,
+            window_events_sink: state.window_events_sink.clone(),
+        })
+    }
+
     pub(crate) fn xdg_toplevel(&self) -> Option<NonNull<c_void>> {
-        NonNull::new(self.window.xdg_toplevel().id().as_ptr().cast())
+        let window = self.window.as_ref()?;
+        NonNull::new(window.xdg_toplevel().id().as_ptr().cast())
     }
 }
 
@@ -246,7 +305,7 @@ impl Window {
 
     #[inline]
     pub fn surface(&self) -> &WlSurface {
-        self.window.wl_surface()
+        &self.surface
     }
 }
 
@@ -260,7 +319,7 @@ impl Drop for Window {
 impl rwh_06::HasWindowHandle for Window {
     fn window_handle(&self) -> Result<rwh_06::WindowHandle<'_>, rwh_06::HandleError> {
         let raw = rwh_06::WaylandWindowHandle::new({
-            let ptr = self.window.wl_surface().id().as_ptr();
+            let ptr = self.surface.id().as_ptr();
             std::ptr::NonNull::new(ptr as *mut _).expect("wl_surface will never be null")
         });
 
@@ -305,7 +364,17 @@ impl CoreWindow for Window {
     }
 
     fn pre_present_notify(&self) {
-        self.window_state.lock().unwrap().request_frame_callback();
+        let mut window_state = self.window_state.lock().unwrap();
+        window_state.request_frame_callback();
+        window_state.request_presentation_feedback();
+    }
+
+    fn set_frame_presented_events(&self, enabled: bool) -> Result<(), RequestError> {
+        self.window_state.lock().unwrap().set_frame_presented_events(enabled)
+    }
+
+    fn set_presentation_hint(&self, hint: PresentationHint) -> Result<(), RequestError> {
+        self.window_state.lock().unwrap().set_presentation_hint(hint)
     }
 
     fn reset_dead_keys(&self) {
@@ -403,13 +472,15 @@ impl CoreWindow for Window {
         self.window_state.lock().unwrap().resizable()
     }
 
-    fn set_enabled_buttons(&self, _buttons: WindowButtons) {
-        // TODO(kchibisov) v5 of the xdg_shell allows that.
+    fn set_enabled_buttons(&self, buttons: WindowButtons) {
+        if self.window_state.lock().unwrap().set_enabled_buttons(buttons) {
+            // NOTE: Requires commit to be applied.
+            self.request_redraw();
+        }
     }
 
     fn enabled_buttons(&self) -> WindowButtons {
-        // TODO(kchibisov) v5 of the xdg_shell allows that.
-        WindowButtons::all()
+        self.window_state.lock().unwrap().enabled_buttons()
     }
 
     fn set_minimized(&self, minimized: bool) {
@@ -419,7 +490,9 @@ impl CoreWindow for Window {
             return;
         }
 
-        self.window.set_minimized();
+        if let Some(window) = self.window.as_ref() {
+            window.set_minimized();
+        }
     }
 
     fn is_minimized(&self) -> Option<bool> {
@@ -428,10 +501,10 @@ impl CoreWindow for Window {
     }
 
     fn set_maximized(&self, maximized: bool) {
-        if maximized {
-            self.window.set_maximized()
-        } else {
-            self.window.unset_maximized()
+        match self.window.as_ref() {
+            Some(window) if maximized => window.set_maximized(),
+            Some(window) => window.unset_maximized(),
+            None => (),
         }
     }
 
@@ -446,6 +519,10 @@ impl CoreWindow for Window {
     }
 
     fn set_fullscreen(&self, fullscreen: Option<Fullscreen>) {
+        let Some(window) = self.window.as_ref() else {
+            return;
+        };
+
         match fullscreen {
             Some(Fullscreen::Exclusive(..)) => {
                 warn!("`Fullscreen::Exclusive` is ignored on Wayland");
@@ -455,9 +532,9 @@ impl CoreWindow for Window {
                     monitor.cast_ref::<output::MonitorHandle>().map(|handle| &handle.proxy)
                 });
 
-                self.window.set_fullscreen(output)
+                window.set_fullscreen(output)
             },
-            None => self.window.unset_fullscreen(),
+            None => window.unset_fullscreen(),
         }
     }
 
@@ -501,6 +578,18 @@ impl CoreWindow for Window {
 
     fn set_window_level(&self, _level: WindowLevel) {}
 
+    fn set_workspace(&self, _workspace: u32) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_workspace is not supported").into())
+    }
+
+    fn workspace(&self) -> Option<u32> {
+        None
+    }
+
+    fn set_sticky(&self, _sticky: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_sticky is not supported").into())
+    }
+
     fn set_window_icon(&self, _window_icon: Option<winit_core::icon::Icon>) {}
 
     #[inline]
@@ -530,6 +619,14 @@ impl CoreWindow for Window {
         self.window_state.lock().unwrap().set_ime_purpose(purpose);
     }
 
+    #[inline]
+    fn set_ime_surrounding_text(&self, text: &str, cursor: usize, anchor: usize) {
+        let mut window_state = self.window_state.lock().unwrap();
+        if window_state.ime_allowed() {
+            window_state.set_ime_surrounding_text(text, cursor, anchor);
+        }
+    }
+
     fn focus_window(&self) {}
 
     fn has_focus(&self) -> bool {
@@ -570,7 +667,16 @@ impl CoreWindow for Window {
         self.window_state.lock().unwrap().theme()
     }
 
-    fn set_content_protected(&self, _protected: bool) {}
+    fn set_content_protected(&self, protected: bool) {
+        // NOTE: there's no protocol to exclude a surface from screen capture.
+        if protected {
+            warn!("content protection is not supported on Wayland");
+        }
+    }
+
+    fn is_content_protected(&self) -> bool {
+        false
+    }
 
     fn set_cursor(&self, cursor: Cursor) {
         let window_state = &mut self.window_state.lock().unwrap();
@@ -608,28 +714,40 @@ impl CoreWindow for Window {
         self.window_state.lock().unwrap().drag_resize_window(direction)
     }
 
-    fn show_window_menu(&self, position: Position) {
+    fn show_window_menu(&self, position: Position) -> Result<(), RequestError> {
         let scale_factor = self.scale_factor();
         let position = position.to_logical(scale_factor);
-        self.window_state.lock().unwrap().show_window_menu(position);
+        self.window_state.lock().unwrap().show_window_menu(position)
     }
 
     fn set_cursor_hittest(&self, hittest: bool) -> Result<(), RequestError> {
-        let surface = self.window.wl_surface();
+        self.window_state.lock().unwrap().set_cursor_hittest(hittest)
+    }
 
-        if hittest {
-            surface.set_input_region(None);
-            Ok(())
-        } else {
-            let region = Region::new(&*self.compositor).map_err(|err| os_error!(err))?;
-            region.add(0, 0, 0, 0);
-            surface.set_input_region(Some(region.wl_region()));
-            Ok(())
+    fn set_input_region(&self, region: Option<&[(Position, Size)]>) -> Result<(), RequestError> {
+        self.window_state.lock().unwrap().set_input_region(region)
+    }
+
+    fn set_opaque_region(&self, region: Option<&[(Position, Size)]>) -> Result<(), RequestError> {
+        self.window_state.lock().unwrap().set_opaque_region(region);
+        Ok(())
+    }
+
+    fn set_shortcuts_inhibited(&self, inhibited: bool) -> Result<(), RequestError> {
+        let deactivated = self.window_state.lock().unwrap().set_shortcuts_inhibited(inhibited)?;
+
+        // The compositor doesn't notify about the destroyed inhibitors.
+        if deactivated {
+            let event = WindowEvent::ShortcutsInhibited(false);
+            self.window_events_sink.lock().unwrap().push_window_event(event, self.window_id);
+            self.event_loop_awakener.ping();
         }
+
+        Ok(())
     }
 
     fn current_monitor(&self) -> Option<CoreMonitorHandle> {
-        let data = self.window.wl_surface().data::<SurfaceData>()?;
+        let data = self.surface.data::<SurfaceData>()?;
         data.outputs()
             .next()
             .map(MonitorHandle::new)
@@ -4,8 +4,8 @@ use std::num::NonZeroU32;
 use std::sync::{Arc, Mutex, Weak};
 use std::time::Duration;
 
-use ahash::HashSet;
-use dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize, Size};
+use ahash::HashMap;
+use dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize, Position, Size};
 use sctk::compositor::{CompositorState, Region, SurfaceData, SurfaceDataExt};
 use sctk::reexports::client::backend::ObjectId;
 use sctk::reexports::client::protocol::wl_seat::WlSeat;
@@ -13,9 +13,17 @@ use sctk::reexports::client::protocol::wl_shm::WlShm;
 use sctk::reexports::client::protocol::wl_surface::WlSurface;
 use sctk::reexports::client::{Proxy, QueueHandle};
 use sctk::reexports::csd_frame::{
-    DecorationsFrame, FrameAction, FrameClick, ResizeEdge, WindowState as XdgWindowState,
+    DecorationsFrame, FrameAction, FrameClick, ResizeEdge, WindowManagerCapabilities,
+    WindowState as XdgWindowState,
+};
+use sctk::reexports::protocols::wp::content_type::v1::client::wp_content_type_v1::{
+    Type as WpContentType, WpContentTypeV1,
 };
 use sctk::reexports::protocols::wp::fractional_scale::v1::client::wp_fractional_scale_v1::WpFractionalScaleV1;
+use sctk::reexports::protocols::wp::keyboard_shortcuts_inhibit::zv1::client::zwp_keyboard_shortcuts_inhibitor_v1::ZwpKeyboardShortcutsInhibitorV1;
+use sctk::reexports::protocols::wp::tearing_control::v1::client::wp_tearing_control_v1::{
+    PresentationHint as TearingPresentationHint, WpTearingControlV1,
+};
 use sctk::reexports::protocols::wp::text_input::zv3::client::zwp_text_input_v3::ZwpTextInputV3;
 use sctk::reexports::protocols::wp::viewporter::client::wp_viewport::WpViewport;
 use sctk::reexports::protocols::xdg::shell::client::xdg_toplevel::ResizeEdge as XdgResizeEdge;
@@ -30,7 +38,10 @@ use tracing::{info, warn};
 use wayland_protocols_plasma::blur::client::org_kde_kwin_blur::OrgKdeKwinBlur;
 use winit_core::cursor::{CursorIcon, CustomCursor as CoreCustomCursor};
 use winit_core::error::{NotSupportedError, RequestError};
-use winit_core::window::{CursorGrabMode, ImePurpose, ResizeDirection, Theme, WindowId};
+use winit_core::window::{
+    ContentType, CursorGrabMode, ImePurpose, PresentationHint, ResizeDirection, Theme,
+    WindowButtons, WindowId,
+};
 
 use crate::event_loop::OwnedDisplayHandle;
 use crate::logical_to_physical_rounded;
@@ -40,6 +51,12 @@ use crate::seat::{
 use crate::state::{WindowCompositorUpdate, WinitState};
 use crate::types::cursor::{CustomCursor, SelectedCursor, WaylandCustomCursor};
 use crate::types::kwin_blur::KWinBlurManager;
+use crate::types::wp_content_type::ContentTypeManager;
+use crate::types::wp_keyboard_shortcuts_inhibit::{
+    KeyboardShortcutsInhibitManager, KeyboardShortcutsInhibitor,
+};
+use crate::types::wp_presentation::PresentationManager;
+use crate::types::wp_tearing_control::TearingControlManager;
 
 #[cfg(feature = "sctk-adwaita")]
 pub type WinitFrame = sctk_adwaita::AdwaitaFrame<WinitState>;
@@ -87,10 +104,13 @@ pub struct WindowState {
     /// Whether the frame is resizable.
     resizable: bool,
 
+    /// The buttons enabled by the user.
+    enabled_buttons: WindowButtons,
+
     // NOTE: we can't use simple counter, since it's racy when seat getting destroyed and new
     // is created, since add/removed stuff could be delivered a bit out of order.
     /// Seats that has keyboard focus on that window.
-    seat_focus: HashSet<ObjectId>,
+    seat_focus: HashMap<ObjectId, WlSeat>,
 
     /// The scale factor of the window.
     scale_factor: f64,
@@ -98,6 +118,15 @@ pub struct WindowState {
     /// Whether the window is transparent.
     transparent: bool,
 
+    /// The rectangles hinted as opaque, `None` to derive them from the transparency.
+    opaque_region: Option<Vec<(Position, Size)>>,
+
+    /// Whether the window receives the pointer and touch input.
+    cursor_hittest: bool,
+
+    /// The rectangles receiving the input, `None` for the whole surface.
+    input_region: Option<Vec<(Position, Size)>>,
+
     /// The state of the compositor to create WlRegions.
     compositor: Arc<CompositorState>,
 
@@ -110,6 +139,9 @@ pub struct WindowState {
     /// The current IME purpose.
     ime_purpose: ImePurpose,
 
+    /// The text surrounding the IME cursor, with the cursor and anchor, while the IME is allowed.
+    ime_surrounding_text: Option<(String, usize, usize)>,
+
     /// The text inputs observed on the window.
     text_inputs: Vec<ZwpTextInputV3>,
 
@@ -138,18 +170,35 @@ pub struct WindowState {
     /// The state of the frame callback.
     frame_callback_state: FrameCallbackState,
 
+    /// Whether the presentation feedback is requested for the presented frames.
+    frame_presented_events: bool,
+    presentation_manager: Option<PresentationManager>,
+
+    tearing_control: Option<WpTearingControlV1>,
+    tearing_control_manager: Option<TearingControlManager>,
+    content_type: Option<WpContentTypeV1>,
+    content_type_manager: Option<ContentTypeManager>,
+
     viewport: Option<WpViewport>,
     fractional_scale: Option<WpFractionalScaleV1>,
     blur: Option<OrgKdeKwinBlur>,
     blur_manager: Option<KWinBlurManager>,
 
+    /// Whether the keyboard shortcuts inhibition was requested.
+    shortcuts_inhibited: bool,
+    shortcuts_inhibitors: HashMap<ObjectId, ZwpKeyboardShortcutsInhibitorV1>,
+    shortcuts_inhibit_manager: Option<KeyboardShortcutsInhibitManager>,
+
     /// Whether the client side decorations have pending move operations.
     ///
     /// The value is the serial of the event triggered moved.
     has_pending_move: Option<u32>,
 
-    /// The underlying SCTK window.
-    pub window: Window,
+    /// The surface of the window.
+    surface: WlSurface,
+
+    /// The underlying SCTK window, `None` for the surfaces adopted from the host.
+    pub window: Option<Window>,
 
     // NOTE: The spec says that destroying parent(`window` in our case), will unmap the
     // subsurfaces. Thus to achieve atomic unmap of the client, drop the decorations
@@ -166,23 +215,30 @@ impl WindowState {
         queue_handle: &QueueHandle<WinitState>,
         winit_state: &WinitState,
         initial_size: Size,
-        window: Window,
+        surface: WlSurface,
+        window: Option<Window>,
         theme: Option<Theme>,
     ) -> Self {
         let compositor = winit_state.compositor_state.clone();
         let pointer_constraints = winit_state.pointer_constraints.clone();
+        // The host could already have these for the adopted surfaces, which is a protocol error.
         let viewport = winit_state
             .viewporter_state
             .as_ref()
-            .map(|state| state.get_viewport(window.wl_surface(), queue_handle));
+            .filter(|_| window.is_some())
+            .map(|state| state.get_viewport(&surface, queue_handle));
         let fractional_scale = winit_state
             .fractional_scaling_manager
             .as_ref()
-            .map(|fsm| fsm.fractional_scaling(window.wl_surface(), queue_handle));
+            .filter(|_| window.is_some())
+            .map(|fsm| fsm.fractional_scaling(&surface, queue_handle));
 
         Self {
             blur: None,
             blur_manager: winit_state.kwin_blur_manager.clone(),
+            shortcuts_inhibited: false,
+            shortcuts_inhibitors: Default::default(),
+            shortcuts_inhibit_manager: winit_state.keyboard_shortcuts_inhibit_manager.clone(),
             compositor,
             handle,
             csd_fails: false,
@@ -190,13 +246,21 @@ impl WindowState {
             selected_cursor: Default::default(),
             cursor_visible: true,
             decorate: true,
+            enabled_buttons: WindowButtons::all(),
             fractional_scale,
             frame: None,
             frame_callback_state: FrameCallbackState::None,
+            frame_presented_events: false,
+            presentation_manager: winit_state.presentation_manager.clone(),
+            tearing_control: None,
+            tearing_control_manager: winit_state.tearing_control_manager.clone(),
+            content_type: None,
+            content_type_manager: winit_state.content_type_manager.clone(),
             seat_focus: Default::default(),
             has_pending_move: None,
             ime_allowed: false,
             ime_purpose: ImePurpose::Normal,
+            ime_surrounding_text: None,
             last_configure: None,
             max_surface_size: None,
             min_surface_size: MIN_WINDOW_SIZE,
@@ -214,7 +278,11 @@ impl WindowState {
             theme,
             title: String::default(),
             transparent: false,
+            opaque_region: None,
+            cursor_hittest: true,
+            input_region: None,
             viewport,
+            surface,
             window,
         }
     }
@@ -247,7 +315,7 @@ impl WindowState {
 
     /// Request a frame callback if we don't have one for this window in flight.
     pub fn request_frame_callback(&mut self) {
-        let surface = self.window.wl_surface();
+        let surface = &self.surface;
         match self.frame_callback_state {
             FrameCallbackState::None | FrameCallbackState::Received => {
                 self.frame_callback_state = FrameCallbackState::Requested;
@@ -257,6 +325,65 @@ impl WindowState {
         }
     }
 
+    /// Enable the presentation feedback for the presented frames.
+    pub fn set_frame_presented_events(&mut self, enabled: bool) -> Result<(), RequestError> {
+        if self.presentation_manager.is_none() {
+            return Err(NotSupportedError::new("wp_presentation is not available").into());
+        }
+
+        self.frame_presented_events = enabled;
+        Ok(())
+    }
+
+    /// Request the presentation feedback for the next commit, if enabled.
+    pub fn request_presentation_feedback(&self) {
+        if let Some(manager) =
+            self.presentation_manager.as_ref().filter(|_| self.frame_presented_events)
+        {
+            manager.feedback(&self.surface, &self.queue_handle);
+        }
+    }
+
+    /// Hint the compositor about the presentation of the next frames.
+    pub fn set_presentation_hint(&mut self, hint: PresentationHint) -> Result<(), RequestError> {
+        // The host may already own the objects of adopted surfaces, which can't be created twice.
+        if self.window.is_none() {
+            return Err(NotSupportedError::new("the host manages the presentation").into());
+        }
+        if hint.allow_tearing && self.tearing_control_manager.is_none() {
+            return Err(NotSupportedError::new("wp_tearing_control_v1 is not available").into());
+        }
+        if hint.content_type != ContentType::None && self.content_type_manager.is_none() {
+            return Err(NotSupportedError::new("wp_content_type_v1 is not available").into());
+        }
+
+        let surface = &self.surface;
+        if let Some(manager) = self.tearing_control_manager.as_ref() {
+            let tearing_control = self
+                .tearing_control
+                .get_or_insert_with(|| manager.tearing_control(surface, &self.queue_handle));
+            tearing_control.set_presentation_hint(if hint.allow_tearing {
+                TearingPresentationHint::Async
+            } else {
+                TearingPresentationHint::Vsync
+            });
+        }
+
+        if let Some(manager) = self.content_type_manager.as_ref() {
+            let content_type = self
+                .content_type
+                .get_or_insert_with(|| manager.content_type(surface, &self.queue_handle));
+            content_type.set_content_type(match hint.content_type {
+                ContentType::None => WpContentType::None,
+                ContentType::Photo => WpContentType::Photo,
+                ContentType::Video => WpContentType::Video,
+                ContentType::Game => WpContentType::Game,
+            });
+        }
+
+        Ok(())
+    }
+
     pub fn configure(
         &mut self,
         configure: WindowConfigure,
@@ -271,13 +398,14 @@ impl WindowState {
             self.stateless_size = self.size;
         }
 
-        if let Some(subcompositor) = subcompositor.as_ref().filter(|_| {
+        let subcompositor = subcompositor.as_ref().filter(|_| {
             configure.decoration_mode == DecorationMode::Client
                 && self.frame.is_none()
                 && !self.csd_fails
-        }) {
+        });
+        if let (Some(subcompositor), Some(window)) = (subcompositor, self.window.as_ref()) {
             match WinitFrame::new(
-                &self.window,
+                window,
                 shm,
                 #[cfg(feature = "sctk-adwaita")]
                 self.compositor.clone(),
@@ -305,9 +433,11 @@ impl WindowState {
 
         let stateless = Self::is_stateless(&configure);
 
+        let frame_capabilities = self.frame_capabilities(configure.capabilities);
         let (mut new_size, constrain) = if let Some(frame) = self.frame.as_mut() {
             // Configure the window states.
             frame.update_state(configure.state);
+            frame.update_wm_capabilities(frame_capabilities);
 
             match configure.new_size {
                 (Some(width), Some(height)) => {
@@ -390,7 +520,7 @@ impl WindowState {
 
     /// Start interacting drag resize.
     pub fn drag_resize_window(&self, direction: ResizeDirection) -> Result<(), RequestError> {
-        let xdg_toplevel = self.window.xdg_toplevel();
+        let xdg_toplevel = self.toplevel()?.xdg_toplevel();
 
         // TODO(kchibisov) handle touch serials.
         self.apply_on_pointer(|_, data| {
@@ -404,7 +534,7 @@ impl WindowState {
 
     /// Start the window drag.
     pub fn drag_window(&self) -> Result<(), RequestError> {
-        let xdg_toplevel = self.window.xdg_toplevel();
+        let xdg_toplevel = self.toplevel()?.xdg_toplevel();
         // TODO(kchibisov) handle touch serials.
         self.apply_on_pointer(|_, data| {
             let serial = data.latest_button_serial();
@@ -415,6 +545,14 @@ impl WindowState {
         Ok(())
     }
 
+    /// The SCTK window, which the adopted surfaces don't have.
+    fn toplevel(&self) -> Result<&Window, RequestError> {
+        match self.window.as_ref() {
+            Some(window) => Ok(window),
+            None => Err(NotSupportedError::new("the host manages the adopted surfaces").into()),
+        }
+    }
+
     /// Tells whether the window should be closed.
     #[allow(clippy::too_many_arguments)]
     pub fn frame_click(
@@ -427,11 +565,21 @@ impl WindowState {
         window_id: WindowId,
         updates: &mut Vec<WindowCompositorUpdate>,
     ) -> Option<bool> {
+        let enabled_buttons = self.enabled_buttons;
+        let window = self.window.as_ref()?;
         match self.frame.as_mut()?.on_click(timestamp, click, pressed)? {
-            FrameAction::Minimize => self.window.set_minimized(),
-            FrameAction::Maximize => self.window.set_maximized(),
-            FrameAction::UnMaximize => self.window.unset_maximized(),
-            FrameAction::Close => WinitState::queue_close(updates, window_id),
+            FrameAction::Minimize if enabled_buttons.contains(WindowButtons::MINIMIZE) => {
+                window.set_minimized()
+            },
+            FrameAction::Maximize if enabled_buttons.contains(WindowButtons::MAXIMIZE) => {
+                window.set_maximized()
+            },
+            FrameAction::UnMaximize if enabled_buttons.contains(WindowButtons::MAXIMIZE) => {
+                window.unset_maximized()
+            },
+            FrameAction::Close if enabled_buttons.contains(WindowButtons::CLOSE) => {
+                WinitState::queue_close(updates, window_id)
+            },
             FrameAction::Move => self.has_pending_move = Some(serial),
             FrameAction::Resize(edge) => {
                 let edge = match edge {
@@ -446,9 +594,9 @@ impl WindowState {
                     ResizeEdge::BottomRight => XdgResizeEdge::BottomRight,
                     _ => return None,
                 };
-                self.window.resize(seat, serial, edge);
+                window.resize(seat, serial, edge);
             },
-            FrameAction::ShowMenu(x, y) => self.window.show_window_menu(seat, serial, (x, y)),
+            FrameAction::ShowMenu(x, y) => window.show_window_menu(seat, serial, (x, y)),
             _ => (),
         };
 
@@ -473,12 +621,12 @@ impl WindowState {
         // Take the serial if we had any, so it doesn't stick around.
         let serial = self.has_pending_move.take();
 
-        if let Some(frame) = self.frame.as_mut() {
+        if let (Some(frame), Some(window)) = (self.frame.as_mut(), self.window.as_ref()) {
             let cursor = frame.click_point_moved(timestamp, &surface.id(), x, y);
             // If we have a cursor change, that means that cursor is over the decorations,
             // so try to apply move.
             if let Some(serial) = cursor.is_some().then_some(serial).flatten() {
-                self.window.move_(seat, serial);
+                window.move_(seat, serial);
                 None
             } else {
                 cursor
@@ -494,6 +642,64 @@ impl WindowState {
         self.resizable
     }
 
+    /// Get the enabled buttons, without the ones the compositor doesn't support.
+    pub fn enabled_buttons(&self) -> WindowButtons {
+        let capabilities = self
+            .last_configure
+            .as_ref()
+            .map_or(WindowManagerCapabilities::all(), |configure| configure.capabilities);
+
+        let mut buttons = self.enabled_buttons;
+        if !capabilities.contains(WindowManagerCapabilities::MINIMIZE) {
+            buttons.remove(WindowButtons::MINIMIZE);
+        }
+        if !capabilities.contains(WindowManagerCapabilities::MAXIMIZE) {
+            buttons.remove(WindowButtons::MAXIMIZE);
+        }
+
+        buttons
+    }
+
+    /// Set the enabled buttons on the window.
+    ///
+    /// Returns `true` when the frame must be redrawn.
+    pub fn set_enabled_buttons(&mut self, buttons: WindowButtons) -> bool {
+        if self.enabled_buttons == buttons {
+            return false;
+        }
+
+        self.enabled_buttons = buttons;
+        let capabilities = match self.last_configure.as_ref() {
+            Some(configure) => self.frame_capabilities(configure.capabilities),
+            None => return false,
+        };
+
+        match self.frame.as_mut() {
+            Some(frame) => {
+                frame.update_wm_capabilities(capabilities);
+                frame.is_dirty()
+            },
+            None => false,
+        }
+    }
+
+    /// The capabilities to draw the frame with, based on the ones of the compositor.
+    ///
+    /// The close button is always drawn, since the frames don't allow hiding it.
+    fn frame_capabilities(
+        &self,
+        mut capabilities: WindowManagerCapabilities,
+    ) -> WindowManagerCapabilities {
+        if !self.enabled_buttons.contains(WindowButtons::MINIMIZE) {
+            capabilities.remove(WindowManagerCapabilities::MINIMIZE);
+        }
+        if !self.enabled_buttons.contains(WindowButtons::MAXIMIZE) {
+            capabilities.remove(WindowManagerCapabilities::MAXIMIZE);
+        }
+
+        capabilities
+    }
+
     /// Set the resizable state on the window.
     ///
     /// Returns `true` when the state was applied.
@@ -616,9 +822,14 @@ impl WindowState {
 
     /// Reissue the transparency hint to the compositor.
     pub fn reload_transparency_hint(&self) {
-        let surface = self.window.wl_surface();
+        let surface = &self.surface;
 
-        if self.transparent {
+        if let Some(opaque_region) = self.opaque_region.as_ref() {
+            match self.create_region(opaque_region) {
+                Ok(region) => surface.set_opaque_region(Some(region.wl_region())),
+                Err(err) => warn!("Failed to set the opaque region: {err}"),
+            }
+        } else if self.transparent {
             surface.set_opaque_region(None);
         } else if let Ok(region) = Region::new(&*self.compositor) {
             region.add(0, 0, i32::MAX, i32::MAX);
@@ -628,6 +839,55 @@ impl WindowState {
         }
     }
 
+    /// Set the opaque region hint, `None` to derive it from the transparency.
+    pub fn set_opaque_region(&mut self, region: Option<&[(Position, Size)]>) {
+        self.opaque_region = region.map(<[_]>::to_vec);
+        self.reload_transparency_hint();
+    }
+
+    /// Set whether the window receives the pointer and touch input.
+    pub fn set_cursor_hittest(&mut self, hittest: bool) -> Result<(), RequestError> {
+        self.cursor_hittest = hittest;
+        self.reload_input_region()
+    }
+
+    /// Set the rectangles receiving the input, `None` for the whole surface.
+    pub fn set_input_region(
+        &mut self,
+        region: Option<&[(Position, Size)]>,
+    ) -> Result<(), RequestError> {
+        self.input_region = region.map(<[_]>::to_vec);
+        self.reload_input_region()
+    }
+
+    /// Reissue the input region to the compositor.
+    fn reload_input_region(&self) -> Result<(), RequestError> {
+        let surface = &self.surface;
+        let region = match self.input_region.as_deref() {
+            _ if !self.cursor_hittest => self.create_region(&[])?,
+            Some(input_region) => self.create_region(input_region)?,
+            None => {
+                surface.set_input_region(None);
+                return Ok(());
+            },
+        };
+
+        surface.set_input_region(Some(region.wl_region()));
+        Ok(())
+    }
+
+    /// Create a region in surface coordinates from the rectangles.
+    fn create_region(&self, rectangles: &[(Position, Size)]) -> Result<Region, RequestError> {
+        let region = Region::new(&*self.compositor).map_err(|err| os_error!(err))?;
+        for (position, size) in rectangles {
+            let position = position.to_logical::<i32>(self.scale_factor());
+            let size = size.to_logical::<i32>(self.scale_factor());
+            region.add(position.x, position.y, size.width, size.height);
+        }
+
+        Ok(region)
+    }
+
     /// Try to resize the window when the user can do so.
     pub fn request_surface_size(&mut self, surface_size: Size) -> PhysicalSize<u32> {
         if self.last_configure.as_ref().map(Self::is_stateless).unwrap_or(true) {
@@ -661,11 +921,16 @@ impl WindowState {
             ((0, 0), self.size)
         };
 
+        // The host manages the state of the adopted surfaces.
+        let Some(window) = self.window.as_ref() else {
+            return;
+        };
+
         // Reload the hint.
         self.reload_transparency_hint();
 
         // Set the window geometry.
-        self.window.xdg_surface().set_window_geometry(
+        window.xdg_surface().set_window_geometry(
             x,
             y,
             outer_size.width as i32,
@@ -773,7 +1038,9 @@ impl WindowState {
             .unwrap_or(size);
 
         self.min_surface_size = size;
-        self.window.set_min_size(Some(size.into()));
+        if let Some(window) = self.window.as_ref() {
+            window.set_min_size(Some(size.into()));
+        }
     }
 
     /// Set maximum inner window size.
@@ -786,7 +1053,9 @@ impl WindowState {
         });
 
         self.max_surface_size = size;
-        self.window.set_max_size(size.map(Into::into));
+        if let Some(window) = self.window.as_ref() {
+            window.set_max_size(size.map(Into::into));
+        }
     }
 
     /// Set the CSD theme.
@@ -856,7 +1125,7 @@ impl WindowState {
         }
 
         let mut set_mode = false;
-        let surface = self.window.wl_surface();
+        let surface = &self.surface;
         match mode {
             CursorGrabMode::Locked => self.apply_on_pointer(|pointer, data| {
                 let pointer = pointer.pointer();
@@ -882,13 +1151,28 @@ impl WindowState {
         Ok(())
     }
 
-    pub fn show_window_menu(&self, position: LogicalPosition<u32>) {
+    pub fn show_window_menu(&self, position: LogicalPosition<u32>) -> Result<(), RequestError> {
+        let capabilities = self.last_configure.as_ref().map(|configure| configure.capabilities);
+        if capabilities.is_some_and(|caps| !caps.contains(WindowManagerCapabilities::WINDOW_MENU)) {
+            return Err(NotSupportedError::new("the compositor has no window menu").into());
+        }
+
+        let window = self.toplevel()?;
+
         // TODO(kchibisov) handle touch serials.
+        let mut shown = false;
         self.apply_on_pointer(|_, data| {
             let serial = data.latest_button_serial();
             let seat = data.seat();
-            self.window.show_window_menu(seat, serial, position.into());
+            window.show_window_menu(seat, serial, position.into());
+            shown = true;
         });
+
+        if shown {
+            Ok(())
+        } else {
+            Err(RequestError::Ignored)
+        }
     }
 
     /// Set the position of the cursor.
@@ -939,12 +1223,16 @@ impl WindowState {
 
         self.decorate = decorate;
 
-        match self.last_configure.as_ref().map(|configure| configure.decoration_mode) {
-            Some(DecorationMode::Server) if !self.decorate => {
+        let decoration_mode =
+            self.last_configure.as_ref().map(|configure| configure.decoration_mode);
+        match (decoration_mode, self.window.as_ref()) {
+            (Some(DecorationMode::Server), Some(window)) if !self.decorate => {
                 // To disable decorations we should request client and hide the frame.
-                self.window.request_decoration_mode(Some(DecorationMode::Client))
+                window.request_decoration_mode(Some(DecorationMode::Client))
+            },
+            (_, Some(window)) if self.decorate => {
+                window.request_decoration_mode(Some(DecorationMode::Server))
             },
-            _ if self.decorate => self.window.request_decoration_mode(Some(DecorationMode::Server)),
             _ => (),
         }
 
@@ -957,26 +1245,73 @@ impl WindowState {
 
     /// Add seat focus for the window.
     #[inline]
-    pub fn add_seat_focus(&mut self, seat: ObjectId) {
-        self.seat_focus.insert(seat);
+    pub fn add_seat_focus(&mut self, seat: &WlSeat) {
+        self.seat_focus.insert(seat.id(), seat.clone());
+        if self.shortcuts_inhibited {
+            self.inhibit_shortcuts();
+        }
     }
 
     /// Remove seat focus from the window.
+    ///
+    /// Returns `true` when an active keyboard shortcuts inhibitor was destroyed.
     #[inline]
-    pub fn remove_seat_focus(&mut self, seat: &ObjectId) {
+    pub fn remove_seat_focus(&mut self, seat: &ObjectId) -> bool {
         self.seat_focus.remove(seat);
+        self.shortcuts_inhibitors.remove(seat).is_some_and(destroy_shortcuts_inhibitor)
+    }
+
+    /// Request the keyboard shortcuts inhibition for the focused seats.
+    ///
+    /// Returns `true` when active inhibitors were destroyed by disabling the inhibition.
+    pub fn set_shortcuts_inhibited(&mut self, inhibited: bool) -> Result<bool, RequestError> {
+        if self.shortcuts_inhibit_manager.is_none() {
+            return Err(NotSupportedError::new(
+                "zwp_keyboard_shortcuts_inhibit_manager_v1 is not available",
+            )
+            .into());
+        }
+
+        self.shortcuts_inhibited = inhibited;
+        if inhibited {
+            self.inhibit_shortcuts();
+            Ok(false)
+        } else {
+            let mut deactivated = false;
+            for (_, inhibitor) in self.shortcuts_inhibitors.drain() {
+                deactivated |= destroy_shortcuts_inhibitor(inhibitor);
+            }
+            Ok(deactivated)
+        }
+    }
+
+    /// Create the keyboard shortcuts inhibitors for the seats which don't have them yet.
+    fn inhibit_shortcuts(&mut self) {
+        let manager = match self.shortcuts_inhibit_manager.as_ref() {
+            Some(manager) => manager,
+            None => return,
+        };
+
+        let surface = &self.surface;
+        for (seat_id, seat) in self.seat_focus.iter() {
+            self.shortcuts_inhibitors
+                .entry(seat_id.clone())
+                .or_insert_with(|| manager.inhibit_shortcuts(surface, seat, &self.queue_handle));
+        }
     }
 
     /// Returns `true` if the requested state was applied.
     pub fn set_ime_allowed(&mut self, allowed: bool) -> bool {
         self.ime_allowed = allowed;
+        if !allowed {
+            self.ime_surrounding_text = None;
+        }
 
         let mut applied = false;
         for text_input in &self.text_inputs {
             applied = true;
             if allowed {
-                text_input.enable();
-                text_input.set_content_type_by_purpose(self.ime_purpose);
+                self.enable_text_input(text_input);
             } else {
                 text_input.disable();
             }
@@ -999,6 +1334,25 @@ impl WindowState {
         }
     }
 
+    /// Set the text surrounding the IME cursor.
+    pub fn set_ime_surrounding_text(&mut self, text: &str, cursor: usize, anchor: usize) {
+        self.ime_surrounding_text = Some((text.to_owned(), cursor, anchor));
+
+        for text_input in &self.text_inputs {
+            text_input.set_surrounding_text_trimmed(text, cursor, anchor);
+            text_input.commit();
+        }
+    }
+
+    /// Enable the text input, sending the IME state of the window again since enabling resets it.
+    pub fn enable_text_input(&self, text_input: &ZwpTextInputV3) {
+        text_input.enable();
+        text_input.set_content_type_by_purpose(self.ime_purpose);
+        if let Some((text, cursor, anchor)) = &self.ime_surrounding_text {
+            text_input.set_surrounding_text_trimmed(text, *cursor, *anchor);
+        }
+    }
+
     /// Set the IME purpose.
     pub fn set_ime_purpose(&mut self, purpose: ImePurpose) {
         self.ime_purpose = purpose;
@@ -1020,13 +1374,20 @@ impl WindowState {
         self.scale_factor = scale_factor;
 
         // NOTE: When fractional scaling is not used update the buffer scale.
-        if self.fractional_scale.is_none() {
-            let _ = self.window.set_buffer_scale(self.scale_factor as _);
+        if let Some(window) = self.window.as_ref().filter(|_| self.fractional_scale.is_none()) {
+            let _ = window.set_buffer_scale(self.scale_factor as _);
         }
 
         if let Some(frame) = self.frame.as_mut() {
             frame.set_scaling_factor(scale_factor);
         }
+
+        // The regions could be in physical coordinates.
+        if self.input_region.is_some() {
+            if let Err(err) = self.reload_input_region() {
+                warn!("Failed to set the input region: {err}");
+            }
+        }
     }
 
     /// Make window background blurred
@@ -1034,14 +1395,14 @@ impl WindowState {
     pub fn set_blur(&mut self, blurred: bool) {
         if blurred && self.blur.is_none() {
             if let Some(blur_manager) = self.blur_manager.as_ref() {
-                let blur = blur_manager.blur(self.window.wl_surface(), &self.queue_handle);
+                let blur = blur_manager.blur(&self.surface, &self.queue_handle);
                 blur.commit();
                 self.blur = Some(blur);
             } else {
                 info!("Blur manager unavailable, unable to change blur")
             }
         } else if !blurred && self.blur.is_some() {
-            self.blur_manager.as_ref().unwrap().unset(self.window.wl_surface());
+            self.blur_manager.as_ref().unwrap().unset(&self.surface);
             self.blur.take().unwrap().release();
         }
     }
@@ -1065,7 +1426,9 @@ impl WindowState {
             frame.set_title(&title);
         }
 
-        self.window.set_title(&title);
+        if let Some(window) = self.window.as_ref() {
+            window.set_title(&title);
+        }
         self.title = title;
     }
 
@@ -1113,8 +1476,20 @@ impl Drop for WindowState {
             viewport.destroy();
         }
 
+        if let Some(tearing_control) = self.tearing_control.take() {
+            tearing_control.destroy();
+        }
+
+        if let Some(content_type) = self.content_type.take() {
+            content_type.destroy();
+        }
+
+        for (_, inhibitor) in self.shortcuts_inhibitors.drain() {
+            inhibitor.destroy();
+        }
+
         // NOTE: the wl_surface used by the window is being cleaned up when
-        // dropping SCTK `Window`.
+        // dropping SCTK `Window`, the adopted ones are left to the host.
     }
 }
 
@@ -1159,6 +1534,14 @@ fn resize_direction_to_xdg(direction: ResizeDirection) -> XdgResizeEdge {
     }
 }
 
+/// Destroy the keyboard shortcuts inhibitor, returning whether it was active.
+fn destroy_shortcuts_inhibitor(inhibitor: ZwpKeyboardShortcutsInhibitorV1) -> bool {
+    let active =
+        inhibitor.data::<KeyboardShortcutsInhibitor>().is_some_and(|data| data.is_active());
+    inhibitor.destroy();
+    active
+}
+
 // NOTE: Rust doesn't allow `From<Option<Theme>>`.
 #[cfg(feature = "sctk-adwaita")]
 fn into_sctk_adwaita_config(theme: Option<Theme>) -> sctk_adwaita::FrameConfig {
@@ -4,7 +4,9 @@ mod runner;
 
 use std::cell::Cell;
 use std::ffi::c_void;
+use std::future::Future;
 use std::os::windows::io::{AsRawHandle as _, FromRawHandle as _, OwnedHandle, RawHandle};
+use std::pin::Pin;
 use std::rc::Rc;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::{Arc, LazyLock, Mutex, MutexGuard};
@@ -63,14 +65,14 @@ use winit_core::application::ApplicationHandler;
 use winit_core::cursor::{CustomCursor, CustomCursorSource};
 use winit_core::error::{EventLoopError, NotSupportedError, RequestError};
 use winit_core::event::{
-    DeviceEvent, DeviceId, FingerId, Force, Ime, RawKeyEvent, SurfaceSizeWriter, TouchPhase,
-    WindowEvent,
+    DeviceEvent, DeviceId, FingerId, Force, Ime, RawKeyEvent, ScrollSource, SurfaceSizeWriter,
+    TouchPhase, WindowEvent,
 };
 use winit_core::event_loop::pump_events::PumpStatus;
 use winit_core::event_loop::{
     ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEvents,
     EventLoopProxy as RootEventLoopProxy, EventLoopProxyProvider,
-    OwnedDisplayHandle as CoreOwnedDisplayHandle,
+    OwnedDisplayHandle as CoreOwnedDisplayHandle, TimerId,
 };
 use winit_core::keyboard::ModifiersState;
 use winit_core::monitor::{Fullscreen, MonitorHandle as CoreMonitorHandle};
@@ -320,7 +322,7 @@ impl EventLoop {
         self.runner.prepare_wait();
         wait_for_messages_impl(
             &mut self.high_resolution_timer,
-            self.runner.control_flow(),
+            self.runner.timers.control_flow(self.runner.control_flow()),
             timeout,
         );
         // Before we potentially exit, make sure to consistently emit an event for the wake up
@@ -459,6 +461,19 @@ impl RootActiveEventLoop for ActiveEventLoop {
         self.0.control_flow()
     }
 
+    fn schedule_timer(&self, deadline: Instant, timer_id: TimerId) {
+        self.0.timers.schedule(deadline, timer_id)
+    }
+
+    fn cancel_timer(&self, timer_id: TimerId) {
+        self.0.timers.cancel(timer_id)
+    }
+
+    fn spawn_local(&self, future: Pin<Box<dyn Future<Output = ()>>>) {
+        let executor_waker = ExecutorWaker { target_window: self.0.thread_msg_target };
+        self.0.executor.spawn(future, RootEventLoopProxy::new(Arc::new(executor_waker)))
+    }
+
     fn exit(&self) {
         self.0.set_exit_code(0)
     }
@@ -764,6 +779,21 @@ impl EventLoopProxyProvider for EventLoopProxy {
     }
 }
 
+/// Wakes up the event loop for the futures, without emitting [`ApplicationHandler::proxy_wake_up`].
+#[derive(Debug)]
+struct ExecutorWaker {
+    target_window: HWND,
+}
+
+unsafe impl Send for ExecutorWaker {}
+unsafe impl Sync for ExecutorWaker {}
+
+impl EventLoopProxyProvider for ExecutorWaker {
+    fn wake_up(&self) {
+        unsafe { PostMessageW(self.target_window, EXECUTOR_WAKE_UP_MSG_ID.get(), 0, 0) };
+    }
+}
+
 /// A lazily-initialized window message ID.
 pub struct LazyMessageId {
     /// The ID.
@@ -817,6 +847,9 @@ impl LazyMessageId {
 // Message sent by the `EventLoopProxy` when we want to wake up the thread.
 // WPARAM and LPARAM are unused.
 static USER_EVENT_MSG_ID: LazyMessageId = LazyMessageId::new("Winit::WakeupMsg\0");
+// Message sent when a future spawned on the thread is woken up, the futures are polled once the
+// thread is woken up. WPARAM and LPARAM are unused.
+static EXECUTOR_WAKE_UP_MSG_ID: LazyMessageId = LazyMessageId::new("Winit::ExecutorWakeupMsg\0");
 // Message sent when we want to execute a closure in the thread.
 // WPARAM contains a Box<Box<dyn FnMut()>> that must be retrieved with `Box::from_raw`,
 // and LPARAM is unused.
@@ -1433,7 +1466,7 @@ unsafe fn public_window_callback_inner(
                 if lparam == 0 {
                     userdata.send_window_event(
                         window,
-                        WindowEvent::Ime(Ime::Preedit(String::new(), None)),
+                        WindowEvent::Ime(Ime::Preedit(String::new(), None, Vec::new())),
                     );
                 }
 
@@ -1445,7 +1478,7 @@ unsafe fn public_window_callback_inner(
 
                         userdata.send_window_event(
                             window,
-                            WindowEvent::Ime(Ime::Preedit(String::new(), None)),
+                            WindowEvent::Ime(Ime::Preedit(String::new(), None, Vec::new())),
                         );
                         userdata.send_window_event(window, WindowEvent::Ime(Ime::Commit(text)));
                     }
@@ -1461,7 +1494,7 @@ unsafe fn public_window_callback_inner(
 
                         userdata.send_window_event(
                             window,
-                            WindowEvent::Ime(Ime::Preedit(text, cursor_range)),
+                            WindowEvent::Ime(Ime::Preedit(text, cursor_range, Vec::new())),
                         );
                     }
                 }
@@ -1484,7 +1517,7 @@ unsafe fn public_window_callback_inner(
                     if let Some(text) = unsafe { ime_context.get_composed_text() } {
                         userdata.send_window_event(
                             window,
-                            WindowEvent::Ime(Ime::Preedit(String::new(), None)),
+                            WindowEvent::Ime(Ime::Preedit(String::new(), None, Vec::new())),
                         );
                         userdata.send_window_event(window, WindowEvent::Ime(Ime::Commit(text)));
                     }
@@ -1625,8 +1658,8 @@ unsafe fn public_window_callback_inner(
         WM_MOUSEWHEEL => {
             use winit_core::event::MouseScrollDelta::LineDelta;
 
-            let value = (wparam >> 16) as i16;
-            let value = value as f32 / WHEEL_DELTA as f32;
+            let value120 = (wparam >> 16) as i16;
+            let value = value120 as f32 / WHEEL_DELTA as f32;
 
             update_modifiers(window, userdata);
 
@@ -1634,6 +1667,8 @@ unsafe fn public_window_callback_inner(
                 device_id: None,
                 delta: LineDelta(0.0, value),
                 phase: TouchPhase::Moved,
+                source: ScrollSource::Unknown,
+                value120: Some((0, value120 as i32)),
             });
 
             result = ProcResult::Value(0);
@@ -1642,8 +1677,8 @@ unsafe fn public_window_callback_inner(
         WM_MOUSEHWHEEL => {
             use winit_core::event::MouseScrollDelta::LineDelta;
 
-            let value = (wparam >> 16) as i16;
-            let value = -value as f32 / WHEEL_DELTA as f32; // NOTE: inverted! See https://github.com/rust-windowing/winit/pull/2105/
+            let value120 = -((wparam >> 16) as i16 as i32); // NOTE: inverted! See https://github.com/rust-windowing/winit/pull/2105/
+            let value = value120 as f32 / WHEEL_DELTA as f32;
 
             update_modifiers(window, userdata);
 
@@ -1651,6 +1686,8 @@ unsafe fn public_window_callback_inner(
                 device_id: None,
                 delta: LineDelta(value, 0.0),
                 phase: TouchPhase::Moved,
+                source: ScrollSource::Unknown,
+                value120: Some((value120, 0)),
             });
 
             result = ProcResult::Value(0);
@@ -2518,6 +2555,7 @@ unsafe extern "system" fn thread_event_target_callback(
             userdata.send_wakeup();
             0
         },
+        _ if msg == EXECUTOR_WAKE_UP_MSG_ID.get() => 0,
         _ if msg == EXEC_MSG_ID.get() => {
             let mut function: ThreadExecFn = unsafe { Box::from_raw(wparam as *mut _) };
             function();
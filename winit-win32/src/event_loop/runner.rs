@@ -8,6 +8,8 @@ use std::{fmt, mem, panic};
 
 use dpi::PhysicalSize;
 use windows_sys::Win32::Foundation::HWND;
+use winit_common::executor::Executor;
+use winit_common::timers::Timers;
 use winit_core::application::ApplicationHandler;
 use winit_core::event::{DeviceEvent, DeviceId, StartCause, SurfaceSizeWriter, WindowEvent};
 use winit_core::event_loop::ActiveEventLoop as RootActiveEventLoop;
@@ -36,6 +38,8 @@ pub(crate) struct EventLoopRunner {
     last_events_cleared: Cell<Instant>,
     event_handler: Rc<EventHandler>,
     event_buffer: RefCell<VecDeque<Event>>,
+    pub(super) timers: Timers,
+    pub(super) executor: Executor,
 
     panic_error: Cell<Option<PanicError>>,
 }
@@ -87,6 +91,8 @@ impl EventLoopRunner {
             last_events_cleared: Cell::new(Instant::now()),
             event_handler: Rc::new(Cell::new(None)),
             event_buffer: RefCell::new(VecDeque::new()),
+            timers: Timers::default(),
+            executor: Executor::default(),
         }
     }
 
@@ -138,6 +144,8 @@ impl EventLoopRunner {
             last_events_cleared: _,
             event_handler,
             event_buffer: _,
+            timers: _,
+            executor: _,
         } = self;
         interrupt_msg_dispatch.set(false);
         runner_state.set(RunnerState::Uninitialized);
@@ -326,6 +334,7 @@ impl EventLoopRunner {
             },
             (Uninitialized, Idle) => {
                 self.call_new_events(true);
+                self.dispatch_timers();
                 self.call_event_handler(|app, event_loop| app.about_to_wait(event_loop));
                 self.last_events_cleared.set(Instant::now());
             },
@@ -343,6 +352,7 @@ impl EventLoopRunner {
             (Idle, Destroyed) => {},
 
             (HandlingMainEvents, Idle) => {
+                self.dispatch_timers();
                 // This is always the last event we dispatch before waiting for new events
                 self.call_event_handler(|app, event_loop| app.about_to_wait(event_loop));
                 self.last_events_cleared.set(Instant::now());
@@ -356,6 +366,12 @@ impl EventLoopRunner {
         }
     }
 
+    fn dispatch_timers(self: &Rc<Self>) {
+        self.call_event_handler(|app, event_loop| self.timers.dispatch(app, event_loop));
+        // Poll the futures which were woken up.
+        self.executor.poll();
+    }
+
     fn call_new_events(self: &Rc<Self>, init: bool) {
         let start_cause = match (init, self.control_flow(), self.exit.get()) {
             (true, ..) => StartCause::Init,
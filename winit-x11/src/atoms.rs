@@ -43,6 +43,9 @@ atom_manager! {
     XIM_SERVERS,
 
     // Assorted ICCCM Atoms
+    _NET_WM_BYPASS_COMPOSITOR,
+    _NET_WM_DESKTOP,
+    _NET_WM_FRAME_DRAWN,
     _NET_WM_ICON,
     _NET_WM_MOVERESIZE,
     _NET_WM_NAME,
@@ -57,7 +60,19 @@ atom_manager! {
     _NET_WM_STATE_HIDDEN,
     _NET_WM_STATE_MAXIMIZED_HORZ,
     _NET_WM_STATE_MAXIMIZED_VERT,
+    _NET_WM_STATE_SKIP_PAGER,
+    _NET_WM_STATE_SKIP_TASKBAR,
+    _NET_WM_STATE_STICKY,
     _NET_WM_WINDOW_TYPE,
+    _NET_WM_OPAQUE_REGION,
+    _NET_WM_WINDOW_OPACITY,
+
+    // WM allowed actions.
+    _NET_WM_ALLOWED_ACTIONS,
+    _NET_WM_ACTION_CLOSE,
+    _NET_WM_ACTION_MAXIMIZE_HORZ,
+    _NET_WM_ACTION_MAXIMIZE_VERT,
+    _NET_WM_ACTION_MINIMIZE,
 
     // Activation atoms.
     _NET_STARTUP_INFO_BEGIN,
@@ -95,7 +110,9 @@ atom_manager! {
     None: b"None",
 
     // Miscellaneous Atoms
+    _GTK_SHOW_WINDOW_MENU,
     _GTK_THEME_VARIANT,
+    _KDE_NET_WM_BLUR_BEHIND_REGION,
     _MOTIF_WM_HINTS,
     _NET_ACTIVE_WINDOW,
     _NET_CLIENT_LIST,
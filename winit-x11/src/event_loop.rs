@@ -1,10 +1,14 @@
 use std::cell::{Cell, RefCell};
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::ffi::CStr;
+use std::future::Future;
 use std::mem::MaybeUninit;
 use std::ops::Deref;
 use std::os::raw::*;
 use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, RawFd};
+use std::pin::Pin;
+use std::ptr::NonNull;
+use std::sync::atomic::{self, AtomicPtr};
 use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
 use std::sync::{Arc, LazyLock, Mutex, Weak};
 use std::time::{Duration, Instant};
@@ -15,16 +19,20 @@ use calloop::ping::Ping;
 use calloop::{EventLoop as Loop, Readiness};
 use libc::{setlocale, LC_CTYPE};
 use tracing::warn;
+use winit_common::event_sources::EventSources;
+use winit_common::executor::Executor;
+use winit_common::timers::Timers;
 use winit_common::xkb::Context;
+use winit_core::application::unix::{EventSourceId, FdInterest};
 use winit_core::application::ApplicationHandler;
 use winit_core::cursor::{CustomCursor as CoreCustomCursor, CustomCursorSource};
-use winit_core::error::{EventLoopError, RequestError};
-use winit_core::event::{DeviceId, StartCause, WindowEvent};
+use winit_core::error::{EventLoopError, NotSupportedError, RequestError};
+use winit_core::event::{DeviceId, Modifiers, StartCause, WindowEvent};
 use winit_core::event_loop::pump_events::PumpStatus;
 use winit_core::event_loop::{
     ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEvents,
     EventLoopProxy as CoreEventLoopProxy, EventLoopProxyProvider,
-    OwnedDisplayHandle as CoreOwnedDisplayHandle,
+    OwnedDisplayHandle as CoreOwnedDisplayHandle, TimerId,
 };
 use winit_core::monitor::MonitorHandle as CoreMonitorHandle;
 use winit_core::window::{Theme, Window as CoreWindow, WindowAttributes, WindowId};
@@ -55,7 +63,20 @@ type X11rbConnection = x11rb::xcb_ffi::XCBConnection;
 type X11Source = Generic<BorrowedFd<'static>>;
 
 pub(crate) static X11_BACKEND: LazyLock<Mutex<Result<Arc<XConnection>, XNotSupported>>> =
-    LazyLock::new(|| Mutex::new(XConnection::new(Some(x_error_callback)).map(Arc::new)));
+    LazyLock::new(|| {
+        let foreign_display = FOREIGN_DISPLAY.load(atomic::Ordering::Relaxed);
+        BACKEND_FOREIGN_DISPLAY.store(foreign_display, atomic::Ordering::Relaxed);
+        let foreign_display = NonNull::new(foreign_display);
+        // SAFETY: The foreign display was checked by `EventLoop::from_display`'s caller.
+        let xconn = unsafe { XConnection::new(Some(x_error_callback), foreign_display) };
+        Mutex::new(xconn.map(Arc::new))
+    });
+
+/// The `Display` of the host application passed to `EventLoop::from_display`.
+static FOREIGN_DISPLAY: AtomicPtr<ffi::Display> = AtomicPtr::new(ptr::null_mut());
+
+/// The foreign `Display` used by [`X11_BACKEND`], null when it opened its own connection.
+static BACKEND_FOREIGN_DISPLAY: AtomicPtr<ffi::Display> = AtomicPtr::new(ptr::null_mut());
 
 /// Hooks for X11 errors.
 pub(crate) static XLIB_ERROR_HOOKS: Mutex<Vec<XlibErrorHook>> = Mutex::new(Vec::new());
@@ -178,8 +199,16 @@ pub struct ActiveEventLoop {
     pub(crate) windows: RefCell<HashMap<WindowId, Weak<UnownedWindow>>>,
     pub(crate) redraw_sender: WakeSender<WindowId>,
     pub(crate) activation_sender: WakeSender<ActivationItem>,
+    pub(crate) shortcuts_inhibit_sender: WakeSender<WindowId>,
     event_loop_proxy: CoreEventLoopProxy,
+    /// Wakes up the event loop for the futures, without emitting a `proxy_wake_up`.
+    executor_proxy: CoreEventLoopProxy,
     device_events: Cell<DeviceEvents>,
+    /// The keyboard modifiers as last reported by the server, regardless of focus.
+    pub(crate) keyboard_modifiers: Cell<Modifiers>,
+    event_sources: EventSources<EventLoopState>,
+    timers: Timers,
+    executor: Executor,
 }
 
 #[derive(Debug)]
@@ -189,6 +218,10 @@ pub struct EventLoop {
     event_processor: EventProcessor,
     redraw_receiver: PeekableReceiver<WindowId>,
     activation_receiver: PeekableReceiver<ActivationItem>,
+    shortcuts_inhibit_receiver: PeekableReceiver<WindowId>,
+
+    /// The reason the connection to the X server was lost.
+    connection_error: Option<ConnectionError>,
 
     /// The current state of the event loop.
     state: EventLoopState,
@@ -206,6 +239,34 @@ struct EventLoopState {
 }
 
 impl EventLoop {
+    /// Create the event loop on the Xlib `Display` of the host application.
+    ///
+    /// The event loop only reads the events of its windows, of the root window, and those which
+    /// can't be attributed to a window before reading them, like the XInput 2 and XKB events. The
+    /// other events stay in the Xlib queue for the host application, which must pass them to
+    /// `XFilterEvent` for the input method of winit. Since the event loop reads the socket, the
+    /// host must process the events reported by `XPending` after each dispatch. Winit installs its
+    /// own Xlib error handler.
+    ///
+    /// This fails when the X11 connection of winit was already opened on another `Display`.
+    ///
+    /// # Safety
+    ///
+    /// `display` must point to a valid `Display`, opened after calling `XInitThreads`, which stays
+    /// valid for the rest of the process, since the connection is kept until the process exits.
+    pub unsafe fn from_display(display: NonNull<c_void>) -> Result<EventLoop, EventLoopError> {
+        let display = display.as_ptr().cast();
+        FOREIGN_DISPLAY.store(display, atomic::Ordering::Relaxed);
+
+        // The connection is only initialized once, an earlier one can't be replaced.
+        LazyLock::force(&X11_BACKEND);
+        if BACKEND_FOREIGN_DISPLAY.load(atomic::Ordering::Relaxed) != display {
+            return Err(NotSupportedError::new("the X11 connection was already opened").into());
+        }
+
+        Self::new()
+    }
+
     pub fn new() -> Result<EventLoop, EventLoopError> {
         let xconn = match X11_BACKEND.lock().unwrap_or_else(|e| e.into_inner()).as_ref() {
             Ok(xconn) => xconn.clone(),
@@ -315,6 +376,9 @@ impl EventLoop {
         // Create a channel for sending activation tokens.
         let (activation_token_sender, activation_token_channel) = mpsc::channel();
 
+        // Create a channel for updating the keyboard shortcuts inhibition.
+        let (shortcuts_inhibit_sender, shortcuts_inhibit_channel) = mpsc::channel();
+
         // Create a channel for sending user events.
         let (user_waker, user_waker_source) =
             calloop::ping::make_ping().expect("Failed to create user event loop waker.");
@@ -327,8 +391,10 @@ impl EventLoop {
             .expect("Failed to register the event loop waker source");
         let event_loop_proxy = EventLoopProxy::new(user_waker);
 
-        let xkb_context =
+        let mut xkb_context =
             Context::from_x11_xkb(xconn.xcb_connection().get_raw_xcb_connection()).unwrap();
+        let keyboard_modifiers =
+            xkb_context.state_mut().map(|state| state.modifiers().into()).unwrap_or_default();
 
         let mut xmodmap = util::ModifierKeymap::new();
         xmodmap.reload_from_x_connection(&xconn);
@@ -352,8 +418,18 @@ impl EventLoop {
                 sender: activation_token_sender, // not used again so no clone
                 waker: waker.clone(),
             },
+            shortcuts_inhibit_sender: WakeSender {
+                sender: shortcuts_inhibit_sender, // not used again so no clone
+                waker: waker.clone(),
+            },
             event_loop_proxy: event_loop_proxy.into(),
+            executor_proxy: EventLoopProxy::new(waker.clone()).into(),
             device_events: Default::default(),
+            keyboard_modifiers: Cell::new(keyboard_modifiers),
+            // The queued events are picked up by `has_pending`.
+            event_sources: EventSources::new(event_loop.handle(), |_| ()),
+            timers: Timers::default(),
+            executor: Executor::default(),
         };
 
         // Set initial device event filter.
@@ -410,6 +486,8 @@ impl EventLoop {
             event_processor,
             redraw_receiver: PeekableReceiver::from_recv(redraw_channel),
             activation_receiver: PeekableReceiver::from_recv(activation_token_channel),
+            shortcuts_inhibit_receiver: PeekableReceiver::from_recv(shortcuts_inhibit_channel),
+            connection_error: None,
             state: EventLoopState { x11_readiness: Readiness::EMPTY, proxy_wake_up: false },
         };
 
@@ -437,6 +515,14 @@ impl EventLoop {
                 PumpStatus::Exit(code) => {
                     break Err(EventLoopError::ExitFailure(code));
                 },
+                PumpStatus::ConnectionLost => {
+                    // There's nothing left to send to the X Server.
+                    let error =
+                        self.connection_error.take().unwrap_or(ConnectionError::UnknownError);
+                    return Err(EventLoopError::ConnectionLost(os_error!(X11Error::Connection(
+                        error
+                    ))));
+                },
                 _ => {
                     continue;
                 },
@@ -461,6 +547,12 @@ impl EventLoop {
         timeout: Option<Duration>,
         mut app: A,
     ) -> PumpStatus {
+        // Nothing can be read from a lost connection.
+        if self.event_processor.target.xconn.is_disconnected() {
+            self.loop_running = false;
+            return PumpStatus::ConnectionLost;
+        }
+
         if !self.loop_running {
             self.loop_running = true;
 
@@ -473,7 +565,11 @@ impl EventLoop {
         if !self.exiting() {
             self.poll_events_with_timeout(timeout, &mut app);
         }
-        if let Some(code) = self.exit_code() {
+        if self.event_processor.target.xconn.is_disconnected() {
+            self.loop_running = false;
+
+            PumpStatus::ConnectionLost
+        } else if let Some(code) = self.exit_code() {
             self.loop_running = false;
 
             PumpStatus::Exit(code)
@@ -486,6 +582,17 @@ impl EventLoop {
         self.event_processor.poll()
             || self.state.proxy_wake_up
             || self.redraw_receiver.has_incoming()
+            || self.shortcuts_inhibit_receiver.has_incoming()
+            || self.event_processor.target.event_sources.has_pending()
+            || self.event_processor.target.timers.has_expired()
+            || self.event_processor.target.executor.is_ready()
+            || self.deferred_redraw_deadline().is_some_and(|deadline| deadline <= Instant::now())
+    }
+
+    /// The earliest deadline of the redraws waiting for the compositor.
+    fn deferred_redraw_deadline(&self) -> Option<Instant> {
+        let windows = self.event_processor.target.windows.borrow();
+        windows.values().filter_map(|window| window.upgrade()?.deferred_redraw_deadline()).min()
     }
 
     fn poll_events_with_timeout<A: ApplicationHandler>(
@@ -495,21 +602,31 @@ impl EventLoop {
     ) {
         let start = Instant::now();
 
+        // Xlib terminates the process when reading from a lost connection.
+        if let Err(error) = self.event_processor.target.xconn.check_connection() {
+            self.connection_lost(app, error);
+            return;
+        }
+
         let has_pending = self.has_pending();
 
         timeout = if has_pending {
             // If we already have work to do then we don't want to block on the next poll.
             Some(Duration::ZERO)
         } else {
-            let control_flow_timeout = match self.control_flow() {
+            let control_flow = self.event_processor.target.timers.control_flow(self.control_flow());
+            let control_flow_timeout = match control_flow {
                 ControlFlow::Wait => None,
                 ControlFlow::Poll => Some(Duration::ZERO),
                 ControlFlow::WaitUntil(wait_deadline) => {
                     Some(wait_deadline.saturating_duration_since(start))
                 },
             };
+            let redraw_timeout = self
+                .deferred_redraw_deadline()
+                .map(|deadline| deadline.saturating_duration_since(start));
 
-            min_timeout(control_flow_timeout, timeout)
+            min_timeout(min_timeout(control_flow_timeout, redraw_timeout), timeout)
         };
 
         self.state.x11_readiness = Readiness::EMPTY;
@@ -522,6 +639,11 @@ impl EventLoop {
             return;
         }
 
+        if let Err(error) = self.event_processor.target.xconn.check_connection() {
+            self.connection_lost(app, error);
+            return;
+        }
+
         // NB: `StartCause::Init` is handled as a special case and doesn't need
         // to be considered here
         let cause = match self.control_flow() {
@@ -589,11 +711,35 @@ impl EventLoop {
             }
         }
 
+        // Apply the keyboard shortcuts inhibition requests.
+        while let Ok(window_id) = self.shortcuts_inhibit_receiver.try_recv() {
+            let window = window_id.into_raw() as xproto::Window;
+            self.event_processor.update_shortcuts_inhibited(window, app);
+        }
+
         // Empty the user event buffer
         if mem::take(&mut self.state.proxy_wake_up) {
             app.proxy_wake_up(&self.event_processor.target);
         }
 
+        // Deliver the ready file descriptors and expired timers.
+        let target = &self.event_processor.target;
+        target.event_sources.dispatch(app, target);
+
+        // Fire the timers which are due.
+        target.timers.dispatch(app, target);
+
+        // Poll the futures which were woken up.
+        target.executor.poll();
+
+        // Stop waiting for the compositor after the timeout.
+        let now = Instant::now();
+        for window in self.event_processor.target.windows.borrow().values() {
+            if let Some(window) = window.upgrade() {
+                window.frame_drawn_timeout(now);
+            }
+        }
+
         // Empty the redraw requests
         {
             let mut windows = HashSet::new();
@@ -603,6 +749,16 @@ impl EventLoop {
             }
 
             for window_id in windows {
+                // Wait until the compositor showed the last frame.
+                let deferred = self
+                    .event_processor
+                    .with_window(window_id.into_raw() as xproto::Window, |window| {
+                        window.defer_redraw()
+                    });
+                if deferred == Some(true) {
+                    continue;
+                }
+
                 app.window_event(
                     &self.event_processor.target,
                     window_id,
@@ -611,10 +767,35 @@ impl EventLoop {
             }
         }
 
+        // Complete the frames drawn during this iteration.
+        let mut frames_ended = false;
+        for window in self.event_processor.target.windows.borrow().values() {
+            if let Some(window) = window.upgrade() {
+                frames_ended |= window.end_frame();
+            }
+        }
+        if frames_ended {
+            self.event_processor
+                .target
+                .xconn
+                .flush_requests()
+                .expect("Failed to set the sync counters");
+        }
+
         // This is always the last event we dispatch before poll again
         app.about_to_wait(&self.event_processor.target);
     }
 
+    fn connection_lost<A: ApplicationHandler>(&mut self, app: &mut A, error: ConnectionError) {
+        tracing::error!("Lost the connection to the X server: {error}");
+
+        if let Some(handler) = app.unix_handler() {
+            handler.connection_lost(&self.event_processor.target);
+        }
+
+        self.connection_error = Some(error);
+    }
+
     fn drain_events<A: ApplicationHandler>(&mut self, app: &mut A) {
         let mut xev = MaybeUninit::uninit();
 
@@ -679,6 +860,54 @@ impl ActiveEventLoop {
             .expect_then_ignore_error("Failed to update device event filter");
     }
 
+    /// The current state of the keyboard modifiers and lock keys.
+    ///
+    /// Unlike [`WindowEvent::ModifiersChanged`], this is tracked regardless of whether any of the
+    /// windows has focus, so it can be used to query e.g. Caps Lock before any key was pressed.
+    pub fn modifiers(&self) -> Modifiers {
+        self.keyboard_modifiers.get()
+    }
+
+    /// Insert a file descriptor into the event loop.
+    pub fn insert_fd<F: AsFd + 'static>(
+        &self,
+        fd: F,
+        interest: FdInterest,
+    ) -> Result<EventSourceId, RequestError> {
+        self.event_sources.insert_fd(fd, interest).map_err(|err| os_error!(err).into())
+    }
+
+    /// Insert a timer into the event loop.
+    pub fn insert_timer(
+        &self,
+        deadline: Instant,
+        interval: Option<Duration>,
+    ) -> Result<EventSourceId, RequestError> {
+        self.event_sources.insert_timer(deadline, interval).map_err(|err| os_error!(err).into())
+    }
+
+    /// Remove a file descriptor or a timer from the event loop.
+    pub fn remove_event_source(&self, id: EventSourceId) {
+        self.event_sources.remove(id)
+    }
+
+    /// Wrap a window created by the host application in a [`Window`][CoreWindow].
+    ///
+    /// The input of the window is selected, and it's not destroyed when the returned window is
+    /// dropped.
+    pub fn adopt_window(
+        &self,
+        window: rwh_06::RawWindowHandle,
+    ) -> Result<Box<dyn CoreWindow>, RequestError> {
+        let xwindow = match window {
+            rwh_06::RawWindowHandle::Xlib(handle) => handle.window as xproto::Window,
+            rwh_06::RawWindowHandle::Xcb(handle) => handle.window.get(),
+            _ => return Err(NotSupportedError::new("window handle is not an X11 window").into()),
+        };
+
+        Ok(Box::new(Window::adopt(self, xwindow)?))
+    }
+
     pub(crate) fn clear_exit(&self) {
         self.exit.set(None)
     }
@@ -741,6 +970,18 @@ impl RootActiveEventLoop for ActiveEventLoop {
         self.control_flow.get()
     }
 
+    fn schedule_timer(&self, deadline: Instant, timer_id: TimerId) {
+        self.timers.schedule(deadline, timer_id)
+    }
+
+    fn cancel_timer(&self, timer_id: TimerId) {
+        self.timers.cancel(timer_id)
+    }
+
+    fn spawn_local(&self, future: Pin<Box<dyn Future<Output = ()>>>) {
+        self.executor.spawn(future, self.executor_proxy.clone())
+    }
+
     fn exit(&self) {
         self.exit.set(Some(0))
     }
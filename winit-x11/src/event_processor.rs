@@ -1,5 +1,6 @@
 use std::cell::{Cell, RefCell};
 use std::collections::{HashMap, VecDeque};
+use std::mem::MaybeUninit;
 use std::os::raw::{c_char, c_int, c_long, c_ulong};
 use std::slice;
 use std::sync::{Arc, Mutex};
@@ -8,11 +9,10 @@ use dpi::{PhysicalPosition, PhysicalSize};
 use winit_common::xkb::{self, Context, XkbState};
 use winit_core::application::ApplicationHandler;
 use winit_core::event::{
-    ButtonSource, DeviceEvent, DeviceId, ElementState, FingerId, Ime, MouseButton,
-    MouseScrollDelta, PointerKind, PointerSource, RawKeyEvent, SurfaceSizeWriter, TouchPhase,
-    WindowEvent,
+    ButtonSource, DeviceEvent, DeviceId, ElementState, FingerId, Ime, Modifiers, MouseButton,
+    MouseScrollDelta, PointerKind, PointerSource, RawKeyEvent, ScrollSource, SurfaceSizeWriter,
+    TouchPhase, WindowEvent,
 };
-use winit_core::keyboard::ModifiersState;
 use winit_core::window::WindowId;
 use x11_dl::xinput2::{
     self, XIDeviceEvent, XIEnterEvent, XIFocusInEvent, XIFocusOutEvent, XIHierarchyEvent,
@@ -21,13 +21,12 @@ use x11_dl::xinput2::{
 use x11_dl::xlib::{
     self, Display as XDisplay, Window as XWindow, XAnyEvent, XClientMessageEvent, XConfigureEvent,
     XDestroyWindowEvent, XEvent, XExposeEvent, XKeyEvent, XMapEvent, XPropertyEvent,
-    XReparentEvent, XSelectionEvent, XVisibilityEvent, XkbAnyEvent, XkbStateRec,
+    XReparentEvent, XSelectionEvent, XUnmapEvent, XVisibilityEvent, XkbAnyEvent, XkbStateRec,
 };
-use x11rb::protocol::sync::{ConnectionExt, Int64};
-use x11rb::protocol::xinput;
 use x11rb::protocol::xkb::ID as XkbId;
 use x11rb::protocol::xproto::{self, ConnectionExt as _, ModMask};
-use x11rb::x11_utils::{ExtensionInformation, Serialize};
+use x11rb::protocol::{present, xinput};
+use x11rb::x11_utils::{ExtensionInformation, Serialize, TryParse};
 use xkbcommon_dl::xkb_mod_mask_t;
 
 use crate::atoms::*;
@@ -69,7 +68,7 @@ pub struct EventProcessor {
     // Currently focused window belonging to this process
     pub active_window: Option<xproto::Window>,
     /// Latest modifiers we've sent for the user to trigger change in event.
-    pub modifiers: Cell<ModifiersState>,
+    pub modifiers: Cell<Modifiers>,
     // Track modifiers based on keycodes. NOTE: that serials generally don't work for tracking
     // since they are not unique and could be duplicated in case of sequence of key events is
     // delivered at near the same time.
@@ -97,6 +96,12 @@ impl EventProcessor {
                 ImeRequest::Allow(window_id, allowed) => {
                     ime.set_ime_allowed(window_id, allowed);
                 },
+                ImeRequest::SurroundingText(window_id, text, cursor) => {
+                    ime.set_surrounding_text(window_id, &text, cursor);
+                },
+                ImeRequest::Remove(window_id) => {
+                    ime.remove_context(window_id).expect("Failed to destroy input context");
+                },
             }
         }
 
@@ -107,15 +112,18 @@ impl EventProcessor {
                 ImeEvent::Enabled => WindowEvent::Ime(Ime::Enabled),
                 ImeEvent::Start => {
                     self.is_composing = true;
-                    WindowEvent::Ime(Ime::Preedit("".to_owned(), None))
+                    WindowEvent::Ime(Ime::Preedit("".to_owned(), None, Vec::new()))
+                },
+                ImeEvent::Update(text, position, segments) if self.is_composing => {
+                    WindowEvent::Ime(Ime::Preedit(text, Some((position, position)), segments))
                 },
-                ImeEvent::Update(text, position) if self.is_composing => {
-                    WindowEvent::Ime(Ime::Preedit(text, Some((position, position))))
+                ImeEvent::DeleteSurrounding(before, after) => {
+                    WindowEvent::Ime(Ime::DeleteSurrounding { before, after })
                 },
                 ImeEvent::End => {
                     self.is_composing = false;
                     // Issue empty preedit on `Done`.
-                    WindowEvent::Ime(Ime::Preedit(String::new(), None))
+                    WindowEvent::Ime(Ime::Preedit(String::new(), None, Vec::new()))
                 },
                 ImeEvent::Disabled => {
                     self.is_composing = false;
@@ -185,6 +193,7 @@ impl EventProcessor {
             xlib::ConfigureNotify => self.configure_notify(xev.as_ref(), app),
             xlib::ReparentNotify => self.reparent_notify(xev.as_ref()),
             xlib::MapNotify => self.map_notify(xev.as_ref(), app),
+            xlib::UnmapNotify => self.unmap_notify(xev.as_ref()),
             xlib::DestroyNotify => self.destroy_notify(xev.as_ref(), app),
             xlib::PropertyNotify => self.property_notify(xev.as_ref(), app),
             xlib::VisibilityNotify => self.visibility_notify(xev.as_ref(), app),
@@ -203,6 +212,11 @@ impl EventProcessor {
                 let xev: GenericEventCookie =
                     match GenericEventCookie::from_event(self.target.xconn.clone(), *xev) {
                         Some(xev) if xev.extension() == self.xi2ext.major_opcode => xev,
+                        Some(xev)
+                            if Some(xev.extension()) == self.target.xconn.present_opcode() =>
+                        {
+                            return self.present_event(&xev, app);
+                        },
                         _ => return,
                     };
 
@@ -291,7 +305,35 @@ impl EventProcessor {
     }
 
     pub fn poll(&self) -> bool {
-        unsafe { (self.target.xconn.xlib.XPending)(self.target.xconn.display) != 0 }
+        if self.target.xconn.owns_display() {
+            return unsafe { (self.target.xconn.xlib.XPending)(self.target.xconn.display) != 0 };
+        }
+
+        // Look for an event of winit without removing it, the events of the host application
+        // stay in the queue.
+        unsafe extern "C" fn predicate(
+            _display: *mut XDisplay,
+            event: *mut XEvent,
+            arg: *mut c_char,
+        ) -> c_int {
+            let (processor, found) = unsafe { &*(arg as *const (&EventProcessor, Cell<bool>)) };
+            if processor.is_own_event(unsafe { &*event }) {
+                found.set(true);
+            }
+            0
+        }
+
+        let arg = (self, Cell::new(false));
+        let mut xev = MaybeUninit::uninit();
+        unsafe {
+            (self.target.xconn.xlib.XCheckIfEvent)(
+                self.target.xconn.display,
+                xev.as_mut_ptr(),
+                Some(predicate),
+                &arg as *const _ as *mut c_char,
+            )
+        };
+        arg.1.get()
     }
 
     pub unsafe fn poll_one_event(&mut self, event_ptr: *mut XEvent) -> bool {
@@ -303,11 +345,11 @@ impl EventProcessor {
         // XPeekEvent does not remove events from the queue.
         unsafe extern "C" fn predicate(
             _display: *mut XDisplay,
-            _event: *mut XEvent,
-            _arg: *mut c_char,
+            event: *mut XEvent,
+            arg: *mut c_char,
         ) -> c_int {
-            // This predicate always returns "true" (1) to accept all events
-            1
+            let processor = unsafe { &*(arg as *const EventProcessor) };
+            processor.is_own_event(unsafe { &*event }) as c_int
         }
 
         unsafe {
@@ -315,11 +357,45 @@ impl EventProcessor {
                 self.target.xconn.display,
                 event_ptr,
                 Some(predicate),
-                std::ptr::null_mut(),
+                self as *const Self as *mut c_char,
             ) != 0
         }
     }
 
+    /// Whether the event is read by winit, rather than left to the host application which passed
+    /// its `Display`.
+    ///
+    /// The events naming a window are only read for the windows of winit and the root window, the
+    /// others, like the extension events, are always read.
+    fn is_own_event(&self, xev: &XEvent) -> bool {
+        if self.target.xconn.owns_display() {
+            return true;
+        }
+
+        let event_type = xev.get_type();
+        match event_type {
+            xlib::GenericEvent | xlib::KeymapNotify | xlib::MappingNotify => true,
+            _ if event_type >= xlib::LASTEvent => true,
+            xlib::PropertyNotify => {
+                let xev: &XPropertyEvent = xev.as_ref();
+                let atom = xev.atom as xproto::Atom;
+                atom == xproto::Atom::from(xproto::AtomEnum::RESOURCE_MANAGER)
+                    || atom == self.target.xconn.atoms()[_XSETTINGS_SETTINGS]
+                    || self.is_own_window(xev.window as xproto::Window)
+            },
+            _ => {
+                let xev: &XAnyEvent = xev.as_ref();
+                self.is_own_window(xev.window as xproto::Window)
+            },
+        }
+    }
+
+    fn is_own_window(&self, window: xproto::Window) -> bool {
+        window == self.target.root
+            || self.target.windows.borrow().contains_key(&mkwid(window))
+            || util::is_compositing_manager(window)
+    }
+
     pub fn init_device(&self, device: xinput::DeviceId) {
         let mut devices = self.devices.borrow_mut();
         if let Some(info) = DeviceInfo::get(&self.target.xconn, device as _) {
@@ -394,13 +470,6 @@ impl EventProcessor {
         }
 
         if xev.data.get_long(0) as xproto::Atom == self.target.net_wm_sync_request {
-            let sync_counter_id = match self
-                .with_window(xev.window as xproto::Window, |window| window.sync_counter_id())
-            {
-                Some(Some(sync_counter_id)) => sync_counter_id.get(),
-                _ => return,
-            };
-
             #[cfg(target_pointer_width = "32")]
             let (lo, hi) =
                 (bytemuck::cast::<c_long, u32>(xev.data.get_long(2)), xev.data.get_long(3));
@@ -411,15 +480,21 @@ impl EventProcessor {
                 bytemuck::cast::<u32, i32>((xev.data.get_long(3) & 0xffffffff) as u32),
             );
 
-            self.target
-                .xconn
-                .xcb_connection()
-                .sync_set_counter(sync_counter_id, Int64 { lo, hi })
-                .expect_then_ignore_error("Failed to set XSync counter.");
+            // The counter is set once the frame for the new size is drawn, the window manager
+            // sets the fifth field for the extended counter.
+            let value = (i64::from(hi) << 32) | i64::from(lo);
+            let extended = xev.data.get_long(4) != 0;
+            self.with_window(window, |window| window.sync_request(value, extended));
 
             return;
         }
 
+        // `_NET_WM_FRAME_TIMINGS` only carries statistics, thus it's ignored.
+        if xev.message_type == atoms[_NET_WM_FRAME_DRAWN] as c_ulong {
+            self.with_window(window, |window| window.frame_drawn());
+            return;
+        }
+
         if xev.message_type == atoms[XdndEnter] as c_ulong {
             let source_window = xev.data.get_long(0) as xproto::Window;
             let flags = xev.data.get_long(1);
@@ -717,9 +792,10 @@ impl EventProcessor {
 
         // NOTE: Ensure that the lock is dropped before handling the resized and
         // sending the event back to user.
-        let hittest = {
+        let reload_input_shape = {
             let mut shared_state_lock = window.shared_state_lock();
-            let hittest = shared_state_lock.cursor_hittest;
+            let reload_input_shape = shared_state_lock.cursor_hittest.is_some()
+                || shared_state_lock.input_region.is_some();
 
             // This is a hack to ensure that the DPI adjusted resize is actually
             // applied on all WMs. KWin doesn't need this, but Xfwm does. The hack
@@ -738,15 +814,18 @@ impl EventProcessor {
                 }
             }
 
-            hittest
+            reload_input_shape
         };
 
-        // Reload hittest.
-        if hittest.unwrap_or(false) {
-            let _ = window.set_cursor_hittest(true);
+        // Reload the input shape, since it's in physical coordinates.
+        if reload_input_shape {
+            let _ = window.update_input_shape();
         }
 
         if resized {
+            // The opaque region covers the whole surface.
+            window.update_opaque_region();
+
             let event = WindowEvent::SurfaceResized(new_surface_size.into());
             app.window_event(&self.target, window_id, event);
         }
@@ -778,10 +857,22 @@ impl EventProcessor {
         app.window_event(&self.target, window_id, WindowEvent::Focused(focus));
     }
 
+    fn unmap_notify(&self, xev: &XUnmapEvent) {
+        // The compositor doesn't draw the frames of unmapped windows, which includes the windows
+        // iconified by the window manager.
+        self.with_window(xev.window as xproto::Window, |window| window.frame_drawn());
+    }
+
     fn destroy_notify(&self, xev: &XDestroyWindowEvent, app: &mut dyn ApplicationHandler) {
         let window = xev.window as xproto::Window;
         let window_id = mkwid(window);
 
+        // The compositing manager released its selection, look for its replacement.
+        if util::is_compositing_manager(window) {
+            self.target.xconn.update_cached_compositing_manager();
+            return;
+        }
+
         // In the event that the window's been destroyed without being dropped first, we
         // cleanup again here.
         self.target.windows.borrow_mut().remove(&WindowId::from_raw(window as _));
@@ -805,6 +896,10 @@ impl EventProcessor {
             || atom == atoms[_XSETTINGS_SETTINGS]
         {
             self.process_dpi_change(app);
+        } else if atom == atoms[_NET_WM_ALLOWED_ACTIONS] {
+            self.with_window(xev.window as xproto::Window, |window| {
+                window.update_allowed_buttons();
+            });
         }
     }
 
@@ -924,7 +1019,7 @@ impl EventProcessor {
         {
             let written = self.target.xconn.lookup_utf8(ic, xev);
             if !written.is_empty() {
-                let event = WindowEvent::Ime(Ime::Preedit(String::new(), None));
+                let event = WindowEvent::Ime(Ime::Preedit(String::new(), None, Vec::new()));
                 app.window_event(&self.target, window_id, event);
 
                 let event = WindowEvent::Ime(Ime::Commit(written));
@@ -956,9 +1051,9 @@ impl EventProcessor {
 
         let mask = self.xkb_mod_mask_from_core(state);
         xkb_state.update_modifiers(mask, 0, 0, 0, 0, Self::core_keyboard_group(state));
-        let mods: ModifiersState = xkb_state.modifiers().into();
+        let mods = xkb_state.modifiers().into();
 
-        let event = WindowEvent::ModifiersChanged(mods.into());
+        let event = WindowEvent::ModifiersChanged(mods);
         app.window_event(&self.target, window_id, event);
     }
 
@@ -1009,16 +1104,21 @@ impl EventProcessor {
             // those. In practice, even clicky scroll wheels appear to be reported by
             // evdev (and XInput2 in turn) as axis motion, so we don't otherwise
             // special-case these button presses.
-            4..=7 => WindowEvent::MouseWheel {
-                device_id,
-                delta: match event.detail {
-                    4 => MouseScrollDelta::LineDelta(0.0, 1.0),
-                    5 => MouseScrollDelta::LineDelta(0.0, -1.0),
-                    6 => MouseScrollDelta::LineDelta(1.0, 0.0),
-                    7 => MouseScrollDelta::LineDelta(-1.0, 0.0),
+            4..=7 => {
+                let (x, y) = match event.detail {
+                    4 => (0, 1),
+                    5 => (0, -1),
+                    6 => (1, 0),
+                    7 => (-1, 0),
                     _ => unreachable!(),
-                },
-                phase: TouchPhase::Moved,
+                };
+                WindowEvent::MouseWheel {
+                    device_id,
+                    delta: MouseScrollDelta::LineDelta(x as f32, y as f32),
+                    phase: TouchPhase::Moved,
+                    source: if x == 0 { ScrollSource::Wheel } else { ScrollSource::WheelTilt },
+                    value120: Some((x * 120, y * 120)),
+                }
             },
             8 => WindowEvent::PointerButton {
                 device_id,
@@ -1057,6 +1157,11 @@ impl EventProcessor {
         let new_cursor_pos = (event.event_x, event.event_y);
 
         let cursor_moved = self.with_window(window, |window| {
+            // The locked cursor doesn't move, its motion is only reported by the raw events.
+            if window.recenter_locked_cursor(new_cursor_pos) {
+                return false;
+            }
+
             let mut shared_state_lock = window.shared_state_lock();
             util::maybe_change(&mut shared_state_lock.cursor_pos, new_cursor_pos)
         });
@@ -1100,14 +1205,23 @@ impl EventProcessor {
                 let delta = (x - info.position) / info.increment;
                 info.position = x;
                 // X11 vertical scroll coordinates are opposite to winit's
-                let delta = match info.orientation {
+                let value120 = (-delta * 120.0).round() as i32;
+                let (delta, value120) = match info.orientation {
                     ScrollOrientation::Horizontal => {
-                        MouseScrollDelta::LineDelta(-delta as f32, 0.0)
+                        (MouseScrollDelta::LineDelta(-delta as f32, 0.0), (value120, 0))
+                    },
+                    ScrollOrientation::Vertical => {
+                        (MouseScrollDelta::LineDelta(0.0, -delta as f32), (0, value120))
                     },
-                    ScrollOrientation::Vertical => MouseScrollDelta::LineDelta(0.0, -delta as f32),
                 };
 
-                let event = WindowEvent::MouseWheel { device_id, delta, phase: TouchPhase::Moved };
+                let event = WindowEvent::MouseWheel {
+                    device_id,
+                    delta,
+                    phase: TouchPhase::Moved,
+                    source: ScrollSource::Unknown,
+                    value120: Some(value120),
+                };
                 events.push(event);
             }
 
@@ -1205,6 +1319,8 @@ impl EventProcessor {
 
         app.window_event(&self.target, window_id, WindowEvent::Focused(true));
 
+        self.update_shortcuts_inhibited(window, app);
+
         // Issue key press events for all pressed keys
         Self::handle_pressed_keys(
             &self.target,
@@ -1276,6 +1392,8 @@ impl EventProcessor {
                 window.shared_state_lock().has_focus = false;
             }
 
+            self.update_shortcuts_inhibited(window, app);
+
             app.window_event(&self.target, window_id, WindowEvent::Focused(false));
         }
     }
@@ -1447,6 +1565,23 @@ impl EventProcessor {
         }
     }
 
+    fn present_event(&self, xev: &GenericEventCookie, app: &mut dyn ApplicationHandler) {
+        if xev.evtype() != present::COMPLETE_NOTIFY_EVENT as c_int {
+            return;
+        }
+
+        // SAFETY: The Present events are stored with `wire_to_raw_cookie`.
+        let xev = match present::CompleteNotifyEvent::try_parse(unsafe { xev.as_wire() }) {
+            Ok((xev, _)) => xev,
+            Err(_) => return,
+        };
+
+        let event = self.with_window(xev.window, |window| window.frame_presented(&xev)).flatten();
+        if let Some(event) = event {
+            app.window_event(&self.target, mkwid(xev.window), event);
+        }
+    }
+
     fn xkb_event(&mut self, xev: &XkbAnyEvent, app: &mut dyn ApplicationHandler) {
         match xev.xkb_type {
             xlib::XkbNewKeyboardNotify => {
@@ -1467,6 +1602,7 @@ impl EventProcessor {
                     let xcb = self.target.xconn.xcb_connection().get_raw_xcb_connection();
                     self.xkb_context.set_keymap_from_x11(xcb);
                     self.xmodmap.reload_from_x_connection(&self.target.xconn);
+                    self.reload_keyboard_modifiers();
 
                     let window_id = match self.active_window.map(mkwid) {
                         Some(window_id) => window_id,
@@ -1483,6 +1619,7 @@ impl EventProcessor {
                 let xcb = self.target.xconn.xcb_connection().get_raw_xcb_connection();
                 self.xkb_context.set_keymap_from_x11(xcb);
                 self.xmodmap.reload_from_x_connection(&self.target.xconn);
+                self.reload_keyboard_modifiers();
                 let window_id = match self.active_window.map(mkwid) {
                     Some(window_id) => window_id,
                     None => return,
@@ -1508,6 +1645,7 @@ impl EventProcessor {
                         xev.latched_group as u32,
                         xev.locked_group as u32,
                     );
+                    self.target.keyboard_modifiers.set(state.modifiers().into());
 
                     let window_id = match self.active_window.map(mkwid) {
                         Some(window_id) => window_id,
@@ -1657,6 +1795,26 @@ impl EventProcessor {
         depressed
     }
 
+    /// Update the keyboard grab of the window to match its shortcuts inhibition request.
+    pub(crate) fn update_shortcuts_inhibited(
+        &self,
+        window: xproto::Window,
+        app: &mut dyn ApplicationHandler,
+    ) {
+        let inhibited = self.with_window(window, |window| window.update_keyboard_grab());
+        if let Some(Some(inhibited)) = inhibited {
+            let event = WindowEvent::ShortcutsInhibited(inhibited);
+            app.window_event(&self.target, mkwid(window), event);
+        }
+    }
+
+    /// Refresh the modifiers tracked by the event loop from the current xkb state.
+    fn reload_keyboard_modifiers(&mut self) {
+        if let Some(state) = self.xkb_context.state_mut() {
+            self.target.keyboard_modifiers.set(state.modifiers().into());
+        }
+    }
+
     /// Send modifiers for the active window.
     ///
     /// The event won't be sent when the `modifiers` match the previously `sent` modifiers value,
@@ -1664,14 +1822,14 @@ impl EventProcessor {
     fn send_modifiers(
         &self,
         window_id: winit_core::window::WindowId,
-        modifiers: ModifiersState,
+        modifiers: Modifiers,
         force: bool,
         app: &mut dyn ApplicationHandler,
     ) {
         // NOTE: Always update the modifiers to account for case when they've changed
         // and forced was `true`.
         if self.modifiers.replace(modifiers) != modifiers || force {
-            let event = WindowEvent::ModifiersChanged(self.modifiers.get().into());
+            let event = WindowEvent::ModifiersChanged(self.modifiers.get());
             app.window_event(&self.target, window_id, event);
         }
     }
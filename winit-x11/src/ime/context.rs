@@ -1,8 +1,10 @@
 use std::error::Error;
 use std::ffi::CStr;
+use std::os::raw::{c_char, c_ulong, c_ushort};
 use std::sync::Arc;
 use std::{fmt, mem, ptr};
 
+use winit_core::event::{PreeditSegment, PreeditStyle};
 use x11_dl::xlib::{XIMCallback, XIMPreeditCaretCallbackStruct, XIMPreeditDrawCallbackStruct};
 
 use super::input_method::{InputMethod, Style, XIMStyle};
@@ -50,6 +52,7 @@ extern "C" fn preedit_start_callback(
     let client_data = unsafe { &mut *(client_data as *mut ImeContextClientData) };
 
     client_data.text.clear();
+    client_data.feedback.clear();
     client_data.cursor_pos = 0;
     client_data
         .event_sender
@@ -68,6 +71,7 @@ extern "C" fn preedit_done_callback(
 
     // Drop text buffer and reset cursor position on done.
     client_data.text = Vec::new();
+    client_data.feedback = Vec::new();
     client_data.cursor_pos = 0;
 
     client_data
@@ -80,6 +84,38 @@ fn calc_byte_position(text: &[char], pos: usize) -> usize {
     text.iter().take(pos).fold(0, |byte_pos, text| byte_pos + text.len_utf8())
 }
 
+const XIM_REVERSE: ffi::XIMFeedback = 1;
+const XIM_UNDERLINE: ffi::XIMFeedback = 1 << 1;
+const XIM_HIGHLIGHT: ffi::XIMFeedback = 1 << 2;
+
+fn feedback_to_style(feedback: ffi::XIMFeedback) -> PreeditStyle {
+    let mut style = PreeditStyle::empty();
+    style.set(PreeditStyle::SELECTED, feedback & XIM_REVERSE != 0);
+    style.set(PreeditStyle::UNDERLINE, feedback & XIM_UNDERLINE != 0);
+    style.set(PreeditStyle::HIGHLIGHTED, feedback & XIM_HIGHLIGHT != 0);
+    style
+}
+
+/// Merge the per-character styles into byte-indexed segments.
+fn preedit_segments(text: &[char], styles: &[PreeditStyle]) -> Vec<PreeditSegment> {
+    let mut segments: Vec<PreeditSegment> = Vec::new();
+    let mut byte_pos = 0;
+    for (ch, &style) in text.iter().zip(styles) {
+        let start = byte_pos;
+        byte_pos += ch.len_utf8();
+        if style.is_empty() {
+            continue;
+        }
+
+        match segments.last_mut() {
+            Some(last) if last.end == start && last.style == style => last.end = byte_pos,
+            _ => segments.push(PreeditSegment { start, end: byte_pos, style }),
+        }
+    }
+
+    segments
+}
+
 /// Preedit text information to be drawn inline by the client.
 extern "C" fn preedit_draw_callback(
     _xim: ffi::XIM,
@@ -103,6 +139,7 @@ extern "C" fn preedit_draw_callback(
     }
 
     // NULL indicate text deletion
+    let mut new_styles = Vec::new();
     let mut new_chars = if call_data.text.is_null() {
         Vec::new()
     } else {
@@ -113,25 +150,60 @@ extern "C" fn preedit_draw_callback(
 
         let new_text = unsafe { xim_text.string.multi_byte };
 
+        // NULL text with feedback only restyles the changed range, e.g. the converted clause.
         if new_text.is_null() {
+            if xim_text.feedback.is_null() {
+                return;
+            }
+
+            let feedback =
+                unsafe { std::slice::from_raw_parts(xim_text.feedback, xim_text.length as usize) };
+            client_data.feedback.resize(client_data.text.len(), PreeditStyle::empty());
+            for (style, &feedback) in client_data.feedback[chg_range].iter_mut().zip(feedback) {
+                *style = feedback_to_style(feedback);
+            }
+
+            send_preedit_update(client_data);
             return;
         }
 
         let new_text = unsafe { CStr::from_ptr(new_text) };
 
+        if !xim_text.feedback.is_null() {
+            let feedback =
+                unsafe { std::slice::from_raw_parts(xim_text.feedback, xim_text.length as usize) };
+            new_styles = feedback.iter().map(|&feedback| feedback_to_style(feedback)).collect();
+        }
+
         String::from(new_text.to_str().expect("Invalid UTF-8 String from IME")).chars().collect()
     };
+    new_styles.resize(new_chars.len(), PreeditStyle::empty());
+    client_data.feedback.resize(client_data.text.len(), PreeditStyle::empty());
     let mut old_text_tail = client_data.text.split_off(chg_range.end);
     client_data.text.truncate(chg_range.start);
     client_data.text.append(&mut new_chars);
     client_data.text.append(&mut old_text_tail);
+    let mut old_styles_tail = client_data.feedback.split_off(chg_range.end);
+    client_data.feedback.truncate(chg_range.start);
+    client_data.feedback.append(&mut new_styles);
+    client_data.feedback.append(&mut old_styles_tail);
+
+    send_preedit_update(client_data);
+}
+
+/// Send the current preedit text, cursor and styles.
+fn send_preedit_update(client_data: &ImeContextClientData) {
     let cursor_byte_pos = calc_byte_position(&client_data.text, client_data.cursor_pos);
 
     client_data
         .event_sender
         .send((
             client_data.window,
-            ImeEvent::Update(client_data.text.iter().collect(), cursor_byte_pos),
+            ImeEvent::Update(
+                client_data.text.iter().collect(),
+                cursor_byte_pos,
+                preedit_segments(&client_data.text, &client_data.feedback),
+            ),
         ))
         .expect("failed to send preedit update event");
 }
@@ -147,24 +219,102 @@ extern "C" fn preedit_caret_callback(
 
     if call_data.direction == ffi::XIMCaretDirection::XIMAbsolutePosition {
         client_data.cursor_pos = call_data.position as usize;
-        let cursor_byte_pos = calc_byte_position(&client_data.text, client_data.cursor_pos);
-
-        client_data
-            .event_sender
-            .send((
-                client_data.window,
-                ImeEvent::Update(client_data.text.iter().collect(), cursor_byte_pos),
-            ))
-            .expect("failed to send preedit update event");
+        send_preedit_update(client_data);
     }
 }
 
+/// `XIMStringConversionText` from `Xlib.h`, which is missing from `x11-dl`.
+#[repr(C)]
+struct XIMStringConversionText {
+    length: c_ushort,
+    feedback: *mut c_ulong,
+    encoding_is_wchar: ffi::Bool,
+    string: *mut c_char,
+}
+
+/// `XIMStringConversionCallbackStruct` from `Xlib.h`, which is missing from `x11-dl`.
+#[repr(C)]
+struct XIMStringConversionCallbackStruct {
+    position: c_ushort,
+    direction: ffi::XIMCaretDirection,
+    operation: c_ushort,
+    factor: c_ushort,
+    text: *mut XIMStringConversionText,
+}
+
+const XIM_STRING_CONVERSION_SUBSTITUTION: c_ushort = 0x0001;
+
+/// The server requested a part of the surrounding text, and possibly its removal.
+extern "C" fn string_conversion_callback(
+    _xim: ffi::XIM,
+    client_data: ffi::XPointer,
+    call_data: ffi::XPointer,
+) {
+    let client_data = unsafe { &mut *(client_data as *mut ImeContextClientData) };
+    let call_data = unsafe { &mut *(call_data as *mut XIMStringConversionCallbackStruct) };
+
+    let len = client_data.surrounding_text.len();
+    let cursor = client_data.surrounding_cursor.min(len);
+    let offset = call_data.position as usize;
+    let factor = call_data.factor as usize;
+    let range = match call_data.direction {
+        ffi::XIMCaretDirection::XIMForwardChar => {
+            let start = cursor.saturating_add(offset).min(len);
+            start..start.saturating_add(factor).min(len)
+        },
+        ffi::XIMCaretDirection::XIMBackwardChar => {
+            let end = cursor.saturating_sub(offset);
+            end.saturating_sub(factor)..end
+        },
+        _ => cursor..cursor,
+    };
+
+    // Xlib takes ownership of the returned text and frees it with `XFree`.
+    let text: String = client_data.surrounding_text[range.clone()].iter().collect();
+    unsafe {
+        let string = libc::malloc(text.len() + 1) as *mut c_char;
+        let xim_text =
+            libc::malloc(mem::size_of::<XIMStringConversionText>()) as *mut XIMStringConversionText;
+        if string.is_null() || xim_text.is_null() {
+            libc::free(string as *mut _);
+            libc::free(xim_text as *mut _);
+            return;
+        }
+
+        ptr::copy_nonoverlapping(text.as_ptr(), string as *mut u8, text.len());
+        *string.add(text.len()) = 0;
+        xim_text.write(XIMStringConversionText {
+            length: range.len() as c_ushort,
+            feedback: ptr::null_mut(),
+            encoding_is_wchar: ffi::False,
+            string,
+        });
+        call_data.text = xim_text;
+    }
+
+    if call_data.operation != XIM_STRING_CONVERSION_SUBSTITUTION || range.is_empty() {
+        return;
+    }
+
+    let (start, end) = (range.start.min(cursor), range.end.max(cursor));
+    let before = calc_byte_position(&client_data.surrounding_text[start..], cursor - start);
+    let after = calc_byte_position(&client_data.surrounding_text[cursor..], end - cursor);
+    client_data.surrounding_text.drain(range);
+    client_data.surrounding_cursor = start;
+
+    client_data
+        .event_sender
+        .send((client_data.window, ImeEvent::DeleteSurrounding(before, after)))
+        .expect("failed to send delete surrounding event");
+}
+
 /// Struct to simplify callback creation and latter passing into Xlib XIM.
 struct PreeditCallbacks {
     start_callback: ffi::XIMCallback,
     done_callback: ffi::XIMCallback,
     draw_callback: ffi::XIMCallback,
     caret_callback: ffi::XIMCallback,
+    string_conversion_callback: ffi::XIMCallback,
 }
 
 impl PreeditCallbacks {
@@ -177,8 +327,16 @@ impl PreeditCallbacks {
         let done_callback = create_xim_callback(client_data, preedit_done_callback);
         let caret_callback = create_xim_callback(client_data, preedit_caret_callback);
         let draw_callback = create_xim_callback(client_data, preedit_draw_callback);
-
-        PreeditCallbacks { start_callback, done_callback, caret_callback, draw_callback }
+        let string_conversion_callback =
+            create_xim_callback(client_data, string_conversion_callback);
+
+        PreeditCallbacks {
+            start_callback,
+            done_callback,
+            caret_callback,
+            draw_callback,
+            string_conversion_callback,
+        }
     }
 }
 
@@ -186,7 +344,10 @@ struct ImeContextClientData {
     window: ffi::Window,
     event_sender: ImeEventSender,
     text: Vec<char>,
+    feedback: Vec<PreeditStyle>,
     cursor_pos: usize,
+    surrounding_text: Vec<char>,
+    surrounding_cursor: usize,
 }
 
 // XXX: this struct doesn't destroy its XIC resource when dropped.
@@ -215,7 +376,10 @@ impl ImeContext {
             window,
             event_sender,
             text: Vec::new(),
+            feedback: Vec::new(),
             cursor_pos: 0,
+            surrounding_text: Vec::new(),
+            surrounding_cursor: 0,
         }));
 
         let style = if allowed { im.preedit_style } else { im.none_style };
@@ -309,10 +473,31 @@ impl ImeContext {
                 window,
                 ffi::XNPreeditAttributes_0.as_ptr() as *const _,
                 preedit_attr.ptr,
+                ffi::XNStringConversionCallback_0.as_ptr() as *const _,
+                &(preedit_callbacks.string_conversion_callback) as *const _,
                 ptr::null_mut::<()>(),
             )
         };
 
+        // The servers not listing the string conversion attribute reject the whole context, so
+        // retry without it, giving up on the surrounding text.
+        let ic = if ic.is_null() {
+            unsafe {
+                (xconn.xlib.XCreateIC)(
+                    im,
+                    ffi::XNInputStyle_0.as_ptr() as *const _,
+                    style,
+                    ffi::XNClientWindow_0.as_ptr() as *const _,
+                    window,
+                    ffi::XNPreeditAttributes_0.as_ptr() as *const _,
+                    preedit_attr.ptr,
+                    ptr::null_mut::<()>(),
+                )
+            }
+        } else {
+            ic
+        };
+
         (!ic.is_null()).then_some(ic)
     }
 
@@ -354,6 +539,12 @@ impl ImeContext {
         self.allowed
     }
 
+    /// Set the text around the cursor, served to the input method on string conversion requests.
+    pub(crate) fn set_surrounding_text(&mut self, text: &str, cursor: usize) {
+        self._client_data.surrounding_cursor = text[..cursor].chars().count();
+        self._client_data.surrounding_text = text.chars().collect();
+    }
+
     /// Set the spot and area for preedit text.
     ///
     /// This functionality depends on the libx11 version.
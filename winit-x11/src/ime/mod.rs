@@ -11,6 +11,7 @@ use std::sync::Arc;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use winit_core::event::PreeditSegment;
 
 use self::callbacks::*;
 use self::context::ImeContext;
@@ -25,7 +26,8 @@ use crate::{ffi, util};
 pub enum ImeEvent {
     Enabled,
     Start,
-    Update(String, usize),
+    Update(String, usize, Vec<PreeditSegment>),
+    DeleteSurrounding(usize, usize),
     End,
     Disabled,
 }
@@ -42,6 +44,12 @@ pub enum ImeRequest {
 
     /// Allow IME input for the given `window_id`.
     Allow(ffi::Window, bool),
+
+    /// Set the text surrounding the cursor for the given `window_id`.
+    SurroundingText(ffi::Window, String, usize),
+
+    /// Destroy the input context of the given `window_id`, which winit stopped using.
+    Remove(ffi::Window),
 }
 
 #[derive(Debug)]
@@ -220,6 +228,15 @@ impl Ime {
         let _ = self.create_context(window, allowed);
     }
 
+    pub fn set_surrounding_text(&mut self, window: ffi::Window, text: &str, cursor: usize) {
+        if self.is_destroyed() {
+            return;
+        }
+        if let Some(&mut Some(ref mut context)) = self.inner.contexts.get_mut(&window) {
+            context.set_surrounding_text(text, cursor);
+        }
+    }
+
     pub fn is_ime_allowed(&self, window: ffi::Window) -> bool {
         if self.is_destroyed() {
             false
@@ -233,6 +250,11 @@ impl Ime {
 
 impl Drop for Ime {
     fn drop(&mut self) {
+        // The requests would make Xlib terminate the process.
+        if self.inner.xconn.is_disconnected() {
+            return;
+        }
+
         unsafe {
             let _ = self.inner.destroy_all_contexts_if_necessary();
             let _ = self.inner.close_im_if_necessary();
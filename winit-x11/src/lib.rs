@@ -3,6 +3,7 @@
 use dpi::Size;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use winit_core::error::{NotSupportedError, RequestError};
 use winit_core::event_loop::ActiveEventLoop as CoreActiveEventLoop;
 use winit_core::window::{ActivationToken, PlatformWindowAttributes, Window as CoreWindow};
 
@@ -140,9 +141,45 @@ pub trait EventLoopBuilderExtX11 {
 /// Additional methods on [`Window`] that are specific to X11.
 ///
 /// [`Window`]: crate::window::Window
-pub trait WindowExtX11 {}
+pub trait WindowExtX11 {
+    /// Set the opacity of the whole window, including its decorations, from `0.0` for an
+    /// invisible window to `1.0` for an opaque window.
+    ///
+    /// This sets `_NET_WM_WINDOW_OPACITY`, which is applied by the compositing manager. A value
+    /// which isn't finite is treated as `1.0`.
+    fn set_opacity(&self, opacity: f32);
+
+    /// Hide the window from the taskbar with `_NET_WM_STATE_SKIP_TASKBAR`.
+    fn set_skip_taskbar(&self, skip: bool) -> Result<(), RequestError>;
+
+    /// Hide the window from the pager, showing the workspaces, with `_NET_WM_STATE_SKIP_PAGER`.
+    fn set_skip_pager(&self, skip: bool) -> Result<(), RequestError>;
+}
+
+impl WindowExtX11 for dyn CoreWindow + '_ {
+    #[inline]
+    fn set_opacity(&self, opacity: f32) {
+        if let Some(window) = self.cast_ref::<Window>() {
+            window.set_opacity(opacity);
+        }
+    }
 
-impl WindowExtX11 for dyn CoreWindow {}
+    #[inline]
+    fn set_skip_taskbar(&self, skip: bool) -> Result<(), RequestError> {
+        match self.cast_ref::<Window>() {
+            Some(window) => window.set_skip_taskbar(skip),
+            None => Err(NotSupportedError::new("not an X11 window").into()),
+        }
+    }
+
+    #[inline]
+    fn set_skip_pager(&self, skip: bool) -> Result<(), RequestError> {
+        match self.cast_ref::<Window>() {
+            Some(window) => window.set_skip_pager(skip),
+            None => Err(NotSupportedError::new("not an X11 window").into()),
+        }
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct ApplicationName {
@@ -1,7 +1,8 @@
 use std::ffi::c_int;
 use std::sync::Arc;
+use std::{ptr, slice};
 
-use x11_dl::xlib::{self, XEvent, XGenericEventCookie};
+use x11_dl::xlib::{self, xEvent, Display, XEvent, XGenericEventCookie};
 
 use crate::xdisplay::XConnection;
 
@@ -44,6 +45,50 @@ impl GenericEventCookie {
     pub unsafe fn as_event<T>(&self) -> &T {
         unsafe { &*(self.cookie.data as *const _) }
     }
+
+    /// Borrow the wire event stored by [`wire_to_raw_cookie`].
+    ///
+    /// ## SAFETY
+    ///
+    /// The caller must ensure that the extension of the event uses [`wire_to_raw_cookie`].
+    pub unsafe fn as_wire(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self.cookie.data as *const u8, wire_len(self.cookie.data)) }
+    }
+}
+
+/// The length of a generic wire event, including the data following the first 32 bytes.
+unsafe fn wire_len(wire: *const impl Sized) -> usize {
+    let length = unsafe { ptr::read_unaligned((wire as *const u8).add(4) as *const u32) };
+    32 + 4 * length as usize
+}
+
+/// Keep the raw wire event as the cookie data of the generic events of an extension that Xlib
+/// doesn't know about, which would be dropped otherwise.
+///
+/// Register with `XESetWireToEventCookie`, the data is released by `XFreeEventData`.
+pub unsafe extern "C" fn wire_to_raw_cookie(
+    display: *mut Display,
+    cookie: *mut XGenericEventCookie,
+    wire: *mut xEvent,
+) -> c_int {
+    unsafe {
+        let len = wire_len(wire);
+        let data = libc::malloc(len);
+        if data.is_null() {
+            return xlib::False;
+        }
+        ptr::copy_nonoverlapping(wire as *const u8, data as *mut u8, len);
+
+        let wire = slice::from_raw_parts(wire as *const u8, 10);
+        let cookie = &mut *cookie;
+        cookie.type_ = xlib::GenericEvent;
+        cookie.send_event = (wire[0] & 0x80 != 0) as _;
+        cookie.display = display;
+        cookie.extension = wire[1] as c_int;
+        cookie.evtype = u16::from_ne_bytes([wire[8], wire[9]]) as c_int;
+        cookie.data = data;
+        xlib::True
+    }
 }
 
 impl Drop for GenericEventCookie {
@@ -27,6 +27,14 @@ impl XConnection {
             .map_err(Into::into)
     }
 
+    /// The XInput events selected by this client on the `window`.
+    pub fn selected_xinput_events(
+        &self,
+        window: xproto::Window,
+    ) -> Result<Vec<xinput::EventMask>, X11Error> {
+        Ok(self.xcb_connection().xinput_xi_get_selected_events(window)?.reply()?.masks)
+    }
+
     pub fn select_xkb_events(
         &self,
         device_id: xkb::DeviceSpec,
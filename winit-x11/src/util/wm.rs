@@ -1,3 +1,4 @@
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Mutex;
 
 use super::*;
@@ -16,11 +17,23 @@ pub const MOVERESIZE_MOVE: isize = 8;
 // This info is global to the window manager.
 static SUPPORTED_HINTS: Mutex<Vec<xproto::Atom>> = Mutex::new(Vec::new());
 static WM_NAME: Mutex<Option<String>> = Mutex::new(None);
+// The owner of the `_NET_WM_CM_S<n>` selection, `x11rb::NONE` without a compositing manager.
+static COMPOSITING_MANAGER: AtomicU32 = AtomicU32::new(x11rb::NONE);
 
 pub fn hint_is_supported(hint: xproto::Atom) -> bool {
     (*SUPPORTED_HINTS.lock().unwrap()).contains(&hint)
 }
 
+/// Whether a compositing manager owned the `_NET_WM_CM_S<n>` selection when last checked.
+pub fn is_composited() -> bool {
+    COMPOSITING_MANAGER.load(Ordering::Relaxed) != x11rb::NONE
+}
+
+/// Whether the window is the owner of the `_NET_WM_CM_S<n>` selection.
+pub fn is_compositing_manager(window: xproto::Window) -> bool {
+    window != x11rb::NONE && COMPOSITING_MANAGER.load(Ordering::Relaxed) == window
+}
+
 pub fn wm_name_is_one_of(names: &[&str]) -> bool {
     if let Some(ref name) = *WM_NAME.lock().unwrap() {
         names.contains(&name.as_str())
@@ -33,6 +46,35 @@ impl XConnection {
     pub fn update_cached_wm_info(&self, root: xproto::Window) {
         *SUPPORTED_HINTS.lock().unwrap() = self.get_supported_hints(root);
         *WM_NAME.lock().unwrap() = self.get_wm_name(root);
+        self.update_cached_compositing_manager();
+    }
+
+    /// The selection is released when its owner window is destroyed, so we select the
+    /// `DestroyNotify` of the owner to refresh it. A compositing manager starting later is only
+    /// noticed with the rest of the WM info.
+    pub fn update_cached_compositing_manager(&self) {
+        let owner = self.compositing_manager_owner();
+        if owner != x11rb::NONE {
+            let aux = xproto::ChangeWindowAttributesAux::new()
+                .event_mask(xproto::EventMask::STRUCTURE_NOTIFY);
+            if let Ok(cookie) = self.xcb_connection().change_window_attributes(owner, &aux) {
+                cookie.ignore_error();
+            }
+        }
+        COMPOSITING_MANAGER.store(owner, Ordering::Relaxed);
+    }
+
+    fn compositing_manager_owner(&self) -> xproto::Window {
+        let name = format!("_NET_WM_CM_S{}", self.default_screen_index());
+        let owner = self
+            .xcb_connection()
+            .intern_atom(true, name.as_bytes())
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+            .filter(|reply| reply.atom != x11rb::NONE)
+            .and_then(|reply| self.xcb_connection().get_selection_owner(reply.atom).ok())
+            .and_then(|cookie| cookie.reply().ok());
+        owner.map_or(x11rb::NONE, |reply| reply.owner)
     }
 
     fn get_supported_hints(&self, root: xproto::Window) -> Vec<xproto::Atom> {
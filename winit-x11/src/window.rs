@@ -6,30 +6,34 @@ use std::ops::Deref;
 use std::os::raw::*;
 use std::path::Path;
 use std::sync::{Arc, Mutex, MutexGuard};
-use std::{cmp, env};
+use std::time::{Duration, Instant};
+use std::{cmp, env, mem};
 
 use dpi::{PhysicalInsets, PhysicalPosition, PhysicalSize, Position, Size};
 use tracing::{debug, info, warn};
 use winit_core::application::ApplicationHandler;
 use winit_core::cursor::Cursor;
 use winit_core::error::{NotSupportedError, RequestError};
-use winit_core::event::{SurfaceSizeWriter, WindowEvent};
+use winit_core::event::{PresentationFlags, SurfaceSizeWriter, WindowEvent};
 use winit_core::event_loop::AsyncRequestSerial;
 use winit_core::icon::RgbaIcon;
 use winit_core::monitor::{
     Fullscreen, MonitorHandle as CoreMonitorHandle, MonitorHandleProvider, VideoMode,
 };
 use winit_core::window::{
-    CursorGrabMode, ImePurpose, ResizeDirection, Theme, UserAttentionType, Window as CoreWindow,
-    WindowAttributes, WindowButtons, WindowId, WindowLevel,
+    ContentType, CursorGrabMode, ImePurpose, PresentationHint, ResizeDirection, Theme,
+    UserAttentionType, Window as CoreWindow, WindowAttributes, WindowButtons, WindowId,
+    WindowLevel,
 };
 use x11rb::connection::{Connection, RequestConnection};
 use x11rb::properties::{WmHints, WmSizeHints, WmSizeHintsSpecification};
+use x11rb::protocol::present::{self, ConnectionExt as _};
+use x11rb::protocol::randr;
 use x11rb::protocol::shape::SK;
 use x11rb::protocol::sync::{ConnectionExt as _, Int64};
 use x11rb::protocol::xfixes::{ConnectionExt, RegionWrapper};
+use x11rb::protocol::xinput::{self, ConnectionExt as _};
 use x11rb::protocol::xproto::{self, ConnectionExt as _, Rectangle};
-use x11rb::protocol::{randr, xinput};
 
 use crate::atoms::*;
 use crate::event_loop::{
@@ -63,6 +67,15 @@ impl Window {
         event_loop.windows.borrow_mut().insert(window.id(), Arc::downgrade(&window));
         Ok(Window(window))
     }
+
+    pub(crate) fn adopt(
+        event_loop: &ActiveEventLoop,
+        xwindow: xproto::Window,
+    ) -> Result<Self, RequestError> {
+        let window = Arc::new(UnownedWindow::adopt(event_loop, xwindow)?);
+        event_loop.windows.borrow_mut().insert(window.id(), Arc::downgrade(&window));
+        Ok(Window(window))
+    }
 }
 
 impl CoreWindow for Window {
@@ -82,6 +95,14 @@ impl CoreWindow for Window {
         self.0.pre_present_notify()
     }
 
+    fn set_frame_presented_events(&self, enabled: bool) -> Result<(), RequestError> {
+        self.0.set_frame_presented_events(enabled)
+    }
+
+    fn set_presentation_hint(&self, hint: PresentationHint) -> Result<(), RequestError> {
+        self.0.set_presentation_hint(hint)
+    }
+
     fn reset_dead_keys(&self) {
         winit_common::xkb::reset_dead_keys();
     }
@@ -202,6 +223,18 @@ impl CoreWindow for Window {
         self.0.set_window_level(level);
     }
 
+    fn set_workspace(&self, workspace: u32) -> Result<(), RequestError> {
+        self.0.set_workspace(workspace)
+    }
+
+    fn workspace(&self) -> Option<u32> {
+        self.0.workspace()
+    }
+
+    fn set_sticky(&self, sticky: bool) -> Result<(), RequestError> {
+        self.0.set_sticky(sticky)
+    }
+
     fn set_window_icon(&self, window_icon: Option<winit_core::icon::Icon>) {
         let icon = match window_icon.as_ref() {
             Some(icon) => icon.cast_ref::<RgbaIcon>(),
@@ -222,6 +255,10 @@ impl CoreWindow for Window {
         self.0.set_ime_purpose(purpose);
     }
 
+    fn set_ime_surrounding_text(&self, text: &str, cursor: usize, anchor: usize) {
+        self.0.set_ime_surrounding_text(text, cursor, anchor);
+    }
+
     fn focus_window(&self) {
         self.0.focus_window();
     }
@@ -246,6 +283,10 @@ impl CoreWindow for Window {
         self.0.set_content_protected(protected);
     }
 
+    fn is_content_protected(&self) -> bool {
+        false
+    }
+
     fn title(&self) -> String {
         self.0.title()
     }
@@ -274,14 +315,26 @@ impl CoreWindow for Window {
         self.0.drag_resize_window(direction)
     }
 
-    fn show_window_menu(&self, position: Position) {
-        self.0.show_window_menu(position);
+    fn show_window_menu(&self, position: Position) -> Result<(), RequestError> {
+        self.0.show_window_menu(position)
     }
 
     fn set_cursor_hittest(&self, hittest: bool) -> Result<(), RequestError> {
         self.0.set_cursor_hittest(hittest)
     }
 
+    fn set_input_region(&self, region: Option<&[(Position, Size)]>) -> Result<(), RequestError> {
+        self.0.set_input_region(region)
+    }
+
+    fn set_opaque_region(&self, region: Option<&[(Position, Size)]>) -> Result<(), RequestError> {
+        self.0.set_opaque_region(region)
+    }
+
+    fn set_shortcuts_inhibited(&self, inhibited: bool) -> Result<(), RequestError> {
+        self.0.set_shortcuts_inhibited(inhibited)
+    }
+
     fn current_monitor(&self) -> Option<CoreMonitorHandle> {
         self.0.current_monitor().map(|monitor| CoreMonitorHandle(Arc::new(monitor)))
     }
@@ -332,14 +385,82 @@ impl Drop for Window {
             window.set_fullscreen(None);
         }
 
-        if let Ok(c) =
+        if let Some(event_masks) = &window.adopted_event_masks {
+            // Give the window back as it was adopted, the connection could be shared with the
+            // host.
+            let aux = xproto::ChangeWindowAttributesAux::new().event_mask(event_masks.core);
+            if let Ok(c) = xconn.xcb_connection().change_window_attributes(window.xwindow, &aux) {
+                c.ignore_error();
+            }
+            let mask = xinput::XIEventMask::from(0u32);
+            if let Ok(c) = xconn.select_xinput_events(window.xwindow, ALL_MASTER_DEVICES, mask) {
+                c.ignore_error();
+            }
+            if !event_masks.xinput.is_empty() {
+                if let Ok(c) = xconn
+                    .xcb_connection()
+                    .xinput_xi_select_events(window.xwindow, &event_masks.xinput)
+                {
+                    c.ignore_error();
+                }
+            }
+
+            // The window may not be destroyed, nor its `DestroyNotify` selected anymore.
+            let _ = window
+                .ime_sender
+                .lock()
+                .unwrap()
+                .send(ImeRequest::Remove(window.xwindow as ffi::Window));
+        } else if let Ok(c) =
             xconn.xcb_connection().destroy_window(window.id().into_raw() as xproto::Window)
         {
             c.ignore_error();
         }
+
+        for counter in
+            [window.sync_counter_id, window.extended_sync_counter_id].into_iter().flatten()
+        {
+            if let Ok(c) = xconn.xcb_connection().sync_destroy_counter(counter.get()) {
+                c.ignore_error();
+            }
+        }
     }
 }
 
+/// How long a redraw waits for the compositor to report the last frame as drawn, so a compositor
+/// which never does doesn't stop the redraws, as GTK does.
+const FRAME_DRAWN_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// The state of the frame synchronization with the window manager through
+/// `_NET_WM_SYNC_REQUEST`.
+///
+/// The basic counter is set to the value requested by the window manager once the frame for the
+/// new size is drawn. The extended counter is odd while a frame is drawn, so the compositor
+/// doesn't show a partially drawn frame, and the compositor reports the frame it showed with
+/// `_NET_WM_FRAME_DRAWN`.
+#[derive(Debug, Default)]
+struct FrameSync {
+    /// The value of the basic counter requested by the window manager.
+    basic_request: Option<i64>,
+    /// The value of the extended counter requested by the window manager.
+    extended_request: Option<i64>,
+    /// The current value of the extended counter.
+    extended_value: i64,
+    /// When the compositor is no longer awaited to report the last frame as drawn.
+    awaiting_frame_drawn: Option<Instant>,
+    /// Whether a redraw was deferred until the last frame is drawn.
+    redraw_deferred: bool,
+}
+
+/// The selection of the Present extension events reporting the presented frames.
+#[derive(Debug, Default)]
+struct FramePresentation {
+    /// The event context selecting the `PresentCompleteNotify` events.
+    event_id: Option<u32>,
+    /// The UST and MSC of the last presented frame, to derive the refresh interval.
+    last_complete: Option<(u64, u64)>,
+}
+
 #[derive(Debug)]
 pub struct SharedState {
     pub cursor_pos: Option<(f64, f64)>,
@@ -349,6 +470,9 @@ pub struct SharedState {
     pub inner_position_rel_parent: Option<(i32, i32)>,
     pub is_resizable: bool,
     pub is_decorated: bool,
+    pub enabled_buttons: WindowButtons,
+    // The buttons allowed by `_NET_WM_ALLOWED_ACTIONS`, `None` when the WM doesn't set it.
+    pub allowed_buttons: Option<WindowButtons>,
     pub last_monitor: X11MonitorHandle,
     pub dpi_adjusted: Option<(u32, u32)>,
     pub(crate) fullscreen: Option<Fullscreen>,
@@ -367,6 +491,18 @@ pub struct SharedState {
     pub has_focus: bool,
     // Use `Option` to not apply hittest logic when it was never requested.
     pub cursor_hittest: Option<bool>,
+    // The rectangles of the surface receiving the input, `None` for the whole surface.
+    pub input_region: Option<Vec<(Position, Size)>>,
+    // The rectangles of the surface hinted as opaque, `None` to derive it from the transparency.
+    pub opaque_region: Option<Vec<(Position, Size)>>,
+    // Whether the user requested to inhibit the keyboard shortcuts.
+    pub shortcuts_inhibited: bool,
+    // Whether we hold the keyboard grab to inhibit the shortcuts.
+    pub keyboard_grabbed: bool,
+    // Whether the background of the window is transparent.
+    pub is_transparent: bool,
+    // Whether the presentation hint asks to bypass the compositor while fullscreen.
+    pub bypass_compositor: bool,
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -388,6 +524,8 @@ impl SharedState {
 
             is_resizable: window_attributes.resizable,
             is_decorated: window_attributes.decorations,
+            enabled_buttons: window_attributes.enabled_buttons,
+            allowed_buttons: None,
             cursor_pos: None,
             size: None,
             position: None,
@@ -405,6 +543,12 @@ impl SharedState {
             base_size: None,
             has_focus: false,
             cursor_hittest: None,
+            input_region: None,
+            opaque_region: None,
+            shortcuts_inhibited: false,
+            keyboard_grabbed: false,
+            is_transparent: window_attributes.transparent,
+            bypass_compositor: false,
         })
     }
 }
@@ -412,6 +556,14 @@ impl SharedState {
 unsafe impl Send for UnownedWindow {}
 unsafe impl Sync for UnownedWindow {}
 
+/// The events selected by this client on an adopted window before winit, restored when it's
+/// dropped.
+#[derive(Debug)]
+struct AdoptedEventMasks {
+    core: xproto::EventMask,
+    xinput: Vec<xinput::EventMask>,
+}
+
 #[derive(Debug)]
 pub struct UnownedWindow {
     pub(crate) xconn: Arc<XConnection>, // never changes
@@ -422,14 +574,22 @@ pub struct UnownedWindow {
     #[allow(dead_code)]
     screen_id: i32, // never changes
     sync_counter_id: Option<NonZeroU32>, // never changes
+    extended_sync_counter_id: Option<NonZeroU32>, // never changes
+    adopted_event_masks: Option<AdoptedEventMasks>, // never changes
+    has_alpha: bool,                    // never changes
+    frame_sync: Mutex<FrameSync>,
+    frame_presentation: Mutex<FramePresentation>,
     selected_cursor: Mutex<SelectedCursor>,
     cursor_grabbed_mode: Mutex<CursorGrabMode>,
+    /// The position of the cursor when it was locked, restored when unlocking it.
+    locked_cursor_origin: Mutex<Option<(i16, i16)>>,
     #[allow(clippy::mutex_atomic)]
     cursor_visible: Mutex<bool>,
     ime_sender: Mutex<ImeSender>,
     pub shared_state: Mutex<SharedState>,
     redraw_sender: WakeSender<WindowId>,
     activation_sender: WakeSender<ActivationItem>,
+    shortcuts_inhibit_sender: WakeSender<WindowId>,
 }
 macro_rules! leap {
     ($e:expr) => {
@@ -561,21 +721,8 @@ impl UnownedWindow {
         let mut visual = visualtype.map_or(x11rb::COPY_FROM_PARENT, |v| v.visual_id);
 
         let window_attributes = {
-            use xproto::EventMask;
-
             let mut aux = xproto::CreateWindowAux::new();
-            let event_mask = EventMask::EXPOSURE
-                | EventMask::STRUCTURE_NOTIFY
-                | EventMask::VISIBILITY_CHANGE
-                | EventMask::KEY_PRESS
-                | EventMask::KEY_RELEASE
-                | EventMask::KEYMAP_STATE
-                | EventMask::BUTTON_PRESS
-                | EventMask::BUTTON_RELEASE
-                | EventMask::POINTER_MOTION
-                | EventMask::PROPERTY_CHANGE;
-
-            aux = aux.event_mask(event_mask).border_pixel(0);
+            aux = aux.event_mask(core_event_mask()).border_pixel(0);
 
             if x11_attributes.override_redirect {
                 aux = aux.override_redirect(true as u32);
@@ -648,13 +795,20 @@ impl UnownedWindow {
             root,
             screen_id,
             sync_counter_id: None,
+            extended_sync_counter_id: None,
+            frame_sync: Default::default(),
+            frame_presentation: Default::default(),
+            adopted_event_masks: None,
+            has_alpha: depth == 32,
             selected_cursor: Default::default(),
             cursor_grabbed_mode: Mutex::new(CursorGrabMode::None),
+            locked_cursor_origin: Mutex::new(None),
             cursor_visible: Mutex::new(true),
             ime_sender: Mutex::new(event_loop.ime_sender.clone()),
             shared_state: SharedState::new(guessed_monitor, &window_attrs),
             redraw_sender: event_loop.redraw_sender.clone(),
             activation_sender: event_loop.activation_sender.clone(),
+            shortcuts_inhibit_sender: event_loop.shortcuts_inhibit_sender.clone(),
         };
 
         // Title must be set before mapping. Some tiling window managers (i.e. i3) use the window
@@ -662,6 +816,9 @@ impl UnownedWindow {
         // act on the wrong title state.
         leap!(window.set_title_inner(&window_attrs.title)).ignore_error();
         leap!(window.set_decorations_inner(window_attrs.decorations)).ignore_error();
+        if window_attrs.enabled_buttons != WindowButtons::all() {
+            leap!(window.set_enabled_buttons_inner(window_attrs.enabled_buttons)).ignore_error();
+        }
 
         if let Some(theme) = window_attrs.preferred_theme {
             leap!(window.set_theme_inner(Some(theme))).ignore_error();
@@ -722,6 +879,13 @@ impl UnownedWindow {
 
             leap!(window.set_window_types(x11_attributes.x11_window_types)).ignore_error();
 
+            if window.has_alpha {
+                leap!(window.set_opaque_region_inner()).ignore_error();
+            }
+            if window_attrs.blur {
+                leap!(window.set_blur_inner(true)).ignore_error();
+            }
+
             // Set size hints.
             let mut min_surface_size =
                 window_attrs.min_surface_size.map(|size| size.to_physical::<u32>(scale_factor));
@@ -793,15 +957,17 @@ impl UnownedWindow {
             );
             leap!(result).ignore_error();
 
-            // Create a sync request counter
+            // Create the basic and extended sync request counters
             if leap!(xconn.xcb_connection().extension_information("SYNC")).is_some() {
                 let sync_counter_id = leap!(xconn.xcb_connection().generate_id());
                 window.sync_counter_id = NonZeroU32::new(sync_counter_id);
+                let extended_sync_counter_id = leap!(xconn.xcb_connection().generate_id());
+                window.extended_sync_counter_id = NonZeroU32::new(extended_sync_counter_id);
 
-                leap!(xconn
-                    .xcb_connection()
-                    .sync_create_counter(sync_counter_id, Int64::default()))
-                .ignore_error();
+                for counter in [sync_counter_id, extended_sync_counter_id] {
+                    leap!(xconn.xcb_connection().sync_create_counter(counter, Int64::default()))
+                        .ignore_error();
+                }
 
                 let result = xconn.xcb_connection().change_property(
                     xproto::PropMode::REPLACE,
@@ -809,23 +975,14 @@ impl UnownedWindow {
                     atoms[_NET_WM_SYNC_REQUEST_COUNTER],
                     xproto::AtomEnum::CARDINAL,
                     32,
-                    1,
-                    bytemuck::cast_slice::<u32, u8>(&[sync_counter_id]),
+                    2,
+                    bytemuck::cast_slice::<u32, u8>(&[sync_counter_id, extended_sync_counter_id]),
                 );
                 leap!(result).ignore_error();
             }
 
             // Select XInput2 events
-            let mask = xinput::XIEventMask::MOTION
-                | xinput::XIEventMask::BUTTON_PRESS
-                | xinput::XIEventMask::BUTTON_RELEASE
-                | xinput::XIEventMask::ENTER
-                | xinput::XIEventMask::LEAVE
-                | xinput::XIEventMask::FOCUS_IN
-                | xinput::XIEventMask::FOCUS_OUT
-                | xinput::XIEventMask::TOUCH_BEGIN
-                | xinput::XIEventMask::TOUCH_UPDATE
-                | xinput::XIEventMask::TOUCH_END;
+            let mask = xinput_event_mask();
             leap!(xconn.select_xinput_events(window.xwindow, ALL_MASTER_DEVICES, mask))
                 .ignore_error();
 
@@ -894,6 +1051,96 @@ impl UnownedWindow {
         Ok(window)
     }
 
+    /// Wrap a window created by the host application, without taking ownership of it.
+    pub(crate) fn adopt(
+        event_loop: &ActiveEventLoop,
+        xwindow: xproto::Window,
+    ) -> Result<UnownedWindow, RequestError> {
+        let xconn = &event_loop.xconn;
+
+        let attributes =
+            leap!(leap!(xconn.xcb_connection().get_window_attributes(xwindow)).reply());
+        let geometry = leap!(leap!(xconn.xcb_connection().get_geometry(xwindow)).reply());
+        let screen_id = xconn
+            .xcb_connection()
+            .setup()
+            .roots
+            .iter()
+            .position(|screen| screen.root == geometry.root)
+            .map_or(xconn.default_screen_index() as c_int, |id| id as c_int);
+
+        let monitor = leap!(xconn.available_monitors())
+            .into_iter()
+            .next()
+            .unwrap_or_else(X11MonitorHandle::dummy);
+        let visible = attributes.map_state == xproto::MapState::VIEWABLE;
+        let window_attrs = WindowAttributes::default().with_visible(visible);
+
+        #[allow(clippy::mutex_atomic)]
+        let window = UnownedWindow {
+            xconn: Arc::clone(xconn),
+            xwindow,
+            visual: attributes.visual,
+            root: geometry.root,
+            screen_id,
+            sync_counter_id: None,
+            extended_sync_counter_id: None,
+            frame_sync: Default::default(),
+            frame_presentation: Default::default(),
+            adopted_event_masks: Some(AdoptedEventMasks {
+                core: attributes.your_event_mask,
+                xinput: leap!(xconn.selected_xinput_events(xwindow)),
+            }),
+            // The host application manages the transparency of its window.
+            has_alpha: false,
+            selected_cursor: Default::default(),
+            cursor_grabbed_mode: Mutex::new(CursorGrabMode::None),
+            locked_cursor_origin: Mutex::new(None),
+            cursor_visible: Mutex::new(true),
+            ime_sender: Mutex::new(event_loop.ime_sender.clone()),
+            shared_state: SharedState::new(monitor, &window_attrs),
+            redraw_sender: event_loop.redraw_sender.clone(),
+            activation_sender: event_loop.activation_sender.clone(),
+            shortcuts_inhibit_sender: event_loop.shortcuts_inhibit_sender.clone(),
+        };
+
+        {
+            let mut shared_state = window.shared_state.lock().unwrap();
+            shared_state.size = Some((geometry.width.into(), geometry.height.into()));
+            // The window is already mapped, so there won't be a `VisibilityNotify` to wait for.
+            if visible {
+                shared_state.visibility = Visibility::Yes;
+            }
+        }
+        // The window manager may already manage the window.
+        window.update_allowed_buttons();
+
+        // Keep the events the connection already selected, it could be shared with the host.
+        let mut event_mask = attributes.your_event_mask | core_event_mask();
+        // Only one client can select the button presses, so leave them to the host when it uses
+        // another connection. The XInput 2 button events are still reported.
+        let button_press = xproto::EventMask::BUTTON_PRESS;
+        if attributes.all_event_masks.contains(button_press)
+            && !attributes.your_event_mask.contains(button_press)
+        {
+            event_mask = event_mask.remove(button_press);
+        }
+        let aux = xproto::ChangeWindowAttributesAux::new().event_mask(event_mask);
+        leap!(leap!(xconn.xcb_connection().change_window_attributes(xwindow, &aux)).check());
+        leap!(xconn.select_xinput_events(xwindow, ALL_MASTER_DEVICES, xinput_event_mask()))
+            .ignore_error();
+
+        if let Some(ime) = event_loop.ime.as_ref() {
+            ime.borrow_mut()
+                .create_context(xwindow as ffi::Window, false)
+                .map_err(|err| os_error!(err))?;
+        }
+
+        let window = leap!(xconn.sync_with_server().map(|_| window));
+
+        Ok(window)
+    }
+
     /// Embed this window into a parent window.
     pub(super) fn embed_window(&self) -> Result<(), RequestError> {
         let atoms = self.xconn.atoms();
@@ -1001,6 +1248,7 @@ impl UnownedWindow {
         let atoms = self.xconn.atoms();
         let fullscreen_atom = atoms[_NET_WM_STATE_FULLSCREEN];
         let flusher = self.set_netwm(fullscreen.into(), (fullscreen_atom, 0, 0, 0));
+        self.set_bypass_compositor_hint(fullscreen)?.ignore_error();
 
         if fullscreen {
             // Ensure that the fullscreen window receives input focus to prevent
@@ -1359,10 +1607,133 @@ impl UnownedWindow {
     }
 
     #[inline]
-    pub fn set_transparent(&self, _transparent: bool) {}
+    pub fn set_transparent(&self, transparent: bool) {
+        self.shared_state_lock().is_transparent = transparent;
+        if !self.has_alpha {
+            if transparent {
+                warn!("the window can't be transparent, it was created without an alpha channel");
+            }
+            return;
+        }
+
+        self.update_opaque_region();
+        self.xconn.flush_requests().expect("Failed to set the window transparency");
+    }
+
+    /// Tell the compositor which part of the window is opaque, which is the whole surface unless
+    /// the window is transparent.
+    pub(crate) fn update_opaque_region(&self) {
+        // Windows without an alpha channel are always opaque.
+        if self.has_alpha {
+            self.set_opaque_region_inner()
+                .expect_then_ignore_error("Failed to set `_NET_WM_OPAQUE_REGION`");
+        }
+    }
+
+    pub fn set_opaque_region(
+        &self,
+        region: Option<&[(Position, Size)]>,
+    ) -> Result<(), RequestError> {
+        self.shared_state_lock().opaque_region = region.map(<[_]>::to_vec);
+        if self.has_alpha {
+            self.set_opaque_region_inner().map_err(|err| os_error!(err))?.ignore_error();
+            self.xconn.flush_requests().map_err(|err| os_error!(X11Error::Xlib(err)))?;
+        }
+
+        Ok(())
+    }
+
+    fn set_opaque_region_inner(&self) -> Result<VoidCookie<'_>, X11Error> {
+        let opaque_region_atom = self.xconn.atoms()[_NET_WM_OPAQUE_REGION];
+        let (is_transparent, size, opaque_region) = {
+            let shared_state_lock = self.shared_state_lock();
+            (
+                shared_state_lock.is_transparent,
+                shared_state_lock.size,
+                shared_state_lock.opaque_region.clone(),
+            )
+        };
+
+        if let Some(region) = opaque_region {
+            let cardinals: Vec<util::Cardinal> = region_to_rectangles(&region, self.scale_factor())
+                .into_iter()
+                .flat_map(|rect| [rect.x as _, rect.y as _, rect.width.into(), rect.height.into()])
+                .collect();
+            return self.xconn.change_property(
+                self.xwindow,
+                opaque_region_atom,
+                xproto::Atom::from(xproto::AtomEnum::CARDINAL),
+                xproto::PropMode::REPLACE,
+                &cardinals,
+            );
+        }
+
+        if is_transparent {
+            return self
+                .xconn
+                .xcb_connection()
+                .delete_property(self.xwindow, opaque_region_atom)
+                .map_err(Into::into);
+        }
+
+        let (width, height) = size.unwrap_or_else(|| self.surface_size_physical());
+        self.xconn.change_property(
+            self.xwindow,
+            opaque_region_atom,
+            xproto::Atom::from(xproto::AtomEnum::CARDINAL),
+            xproto::PropMode::REPLACE,
+            &[0, 0, width, height],
+        )
+    }
 
     #[inline]
-    pub fn set_blur(&self, _blur: bool) {}
+    pub fn set_blur(&self, blur: bool) {
+        self.set_blur_inner(blur).expect_then_ignore_error("Failed to set the window blur");
+        self.xconn.flush_requests().expect("Failed to set the window blur");
+    }
+
+    fn set_blur_inner(&self, blur: bool) -> Result<VoidCookie<'_>, X11Error> {
+        let blur_atom = self.xconn.atoms()[_KDE_NET_WM_BLUR_BEHIND_REGION];
+        if blur {
+            // An empty region blurs behind the whole window.
+            let empty_region: [util::Cardinal; 0] = [];
+            self.xconn.change_property(
+                self.xwindow,
+                blur_atom,
+                xproto::Atom::from(xproto::AtomEnum::CARDINAL),
+                xproto::PropMode::REPLACE,
+                &empty_region,
+            )
+        } else {
+            self.xconn.xcb_connection().delete_property(self.xwindow, blur_atom).map_err(Into::into)
+        }
+    }
+
+    #[inline]
+    pub fn set_opacity(&self, opacity: f32) {
+        let opacity_atom = self.xconn.atoms()[_NET_WM_WINDOW_OPACITY];
+        // Keep the window visible with a bogus opacity.
+        let opacity = if opacity.is_finite() { opacity.clamp(0.0, 1.0) } else { 1.0 };
+        let result = if opacity == 1.0 {
+            // Without the property, the window is opaque.
+            self.xconn
+                .xcb_connection()
+                .delete_property(self.xwindow, opacity_atom)
+                .map_err(Into::into)
+        } else {
+            let opacity = (opacity as f64 * u32::MAX as f64).round() as u32;
+            self.xconn.change_property(
+                self.xwindow,
+                opacity_atom,
+                xproto::Atom::from(xproto::AtomEnum::CARDINAL),
+                xproto::PropMode::REPLACE,
+                &[opacity],
+            )
+        };
+
+        result.expect_then_ignore_error("Failed to set `_NET_WM_WINDOW_OPACITY`");
+        self.xconn.flush_requests().expect("Failed to set the window opacity");
+    }
 
     fn set_decorations_inner(&self, decorations: bool) -> Result<VoidCookie<'_>, X11Error> {
         self.shared_state_lock().is_decorated = decorations;
@@ -1412,6 +1783,92 @@ impl UnownedWindow {
         self.xconn.flush_requests().expect("Failed to set window-level state");
     }
 
+    pub fn set_workspace(&self, workspace: u32) -> Result<(), RequestError> {
+        let atoms = self.xconn.atoms();
+        let desktop_atom = atoms[_NET_WM_DESKTOP];
+        if !util::hint_is_supported(desktop_atom) {
+            return Err(
+                NotSupportedError::new("the window manager doesn't support workspaces").into()
+            );
+        }
+
+        // The window manager only honors the property when the window gets mapped, afterwards the
+        // change must be requested from it.
+        if self.shared_state_lock().visibility == Visibility::No {
+            self.xconn
+                .change_property(
+                    self.xwindow,
+                    desktop_atom,
+                    xproto::Atom::from(xproto::AtomEnum::CARDINAL),
+                    xproto::PropMode::REPLACE,
+                    &[workspace],
+                )
+                .map_err(|err| os_error!(err))?
+                .ignore_error();
+        } else {
+            self.xconn
+                .send_client_msg(
+                    self.xwindow,
+                    self.root,
+                    desktop_atom,
+                    Some(
+                        xproto::EventMask::SUBSTRUCTURE_REDIRECT
+                            | xproto::EventMask::SUBSTRUCTURE_NOTIFY,
+                    ),
+                    // Source indication of a normal application.
+                    [workspace, 1, 0, 0, 0],
+                )
+                .map_err(|err| os_error!(err))?
+                .ignore_error();
+        }
+
+        self.xconn.flush_requests().map_err(|err| os_error!(X11Error::Xlib(err)))?;
+        Ok(())
+    }
+
+    pub fn workspace(&self) -> Option<u32> {
+        let atoms = self.xconn.atoms();
+        let desktop_atom = atoms[_NET_WM_DESKTOP];
+        let desktop = self
+            .xconn
+            .get_property::<u32>(
+                self.xwindow,
+                desktop_atom,
+                xproto::Atom::from(xproto::AtomEnum::CARDINAL),
+            )
+            .ok()?
+            .first()
+            .copied()?;
+
+        // `0xFFFFFFFF` is used for the windows on all the workspaces.
+        (desktop != u32::MAX).then_some(desktop)
+    }
+
+    pub fn set_sticky(&self, sticky: bool) -> Result<(), RequestError> {
+        self.set_net_wm_state_hint(_NET_WM_STATE_STICKY, sticky)
+    }
+
+    pub fn set_skip_taskbar(&self, skip: bool) -> Result<(), RequestError> {
+        self.set_net_wm_state_hint(_NET_WM_STATE_SKIP_TASKBAR, skip)
+    }
+
+    pub fn set_skip_pager(&self, skip: bool) -> Result<(), RequestError> {
+        self.set_net_wm_state_hint(_NET_WM_STATE_SKIP_PAGER, skip)
+    }
+
+    /// Toggles a `_NET_WM_STATE` hint, when the window manager supports it.
+    fn set_net_wm_state_hint(&self, atom_name: AtomName, enable: bool) -> Result<(), RequestError> {
+        if !util::hint_is_supported(self.xconn.atoms()[atom_name]) {
+            return Err(
+                NotSupportedError::new("the window manager doesn't support the hint").into()
+            );
+        }
+
+        self.toggle_atom(atom_name, enable).map_err(|err| os_error!(err))?.ignore_error();
+        self.xconn.flush_requests().map_err(|err| os_error!(X11Error::Xlib(err)))?;
+        Ok(())
+    }
+
     fn set_icon_inner(&self, icon: &RgbaIcon) -> Result<VoidCookie<'_>, X11Error> {
         let atoms = self.xconn.atoms();
         let icon_atom = atoms[_NET_WM_ICON];
@@ -1481,6 +1938,9 @@ impl UnownedWindow {
                 .expect_then_ignore_error("Failed to call `xcb_unmap_window`");
             self.xconn.flush_requests().expect("Failed to call XUnmapWindow");
             shared_state.visibility = Visibility::No;
+
+            // The compositor doesn't draw the frames of unmapped windows.
+            self.frame_drawn();
         }
     }
 
@@ -1760,9 +2220,13 @@ impl UnownedWindow {
             let window_size = Some(Size::from(self.surface_size()));
             (window_size, window_size)
         };
-        self.shared_state_lock().is_resizable = resizable;
+        let maximizable = {
+            let mut shared_state_lock = self.shared_state_lock();
+            shared_state_lock.is_resizable = resizable;
+            resizable && shared_state_lock.enabled_buttons.contains(WindowButtons::MAXIMIZE)
+        };
 
-        self.set_maximizable_inner(resizable)
+        self.set_maximizable_inner(maximizable)
             .expect_then_ignore_error("Failed to call `XSetWMNormalHints`");
 
         let scale_factor = self.scale_factor();
@@ -1780,12 +2244,80 @@ impl UnownedWindow {
         self.shared_state_lock().is_resizable
     }
 
+    fn set_enabled_buttons_inner(
+        &self,
+        buttons: WindowButtons,
+    ) -> Result<VoidCookie<'_>, X11Error> {
+        let is_resizable = {
+            let mut shared_state_lock = self.shared_state_lock();
+            shared_state_lock.enabled_buttons = buttons;
+            shared_state_lock.is_resizable
+        };
+
+        // `_NET_WM_ALLOWED_ACTIONS` is owned by the window manager, which derives it from the
+        // Motif functions, so the latter is what we have to set.
+        let mut hints = self.xconn.get_motif_hints(self.xwindow);
+        hints.set_minimizable(buttons.contains(WindowButtons::MINIMIZE));
+        hints.set_maximizable(is_resizable && buttons.contains(WindowButtons::MAXIMIZE));
+        hints.set_closable(buttons.contains(WindowButtons::CLOSE));
+
+        self.xconn.set_motif_hints(self.xwindow, &hints)
+    }
+
     #[inline]
-    pub fn set_enabled_buttons(&self, _buttons: WindowButtons) {}
+    pub fn set_enabled_buttons(&self, buttons: WindowButtons) {
+        self.set_enabled_buttons_inner(buttons)
+            .expect_then_ignore_error("Failed to set the enabled buttons");
+        self.xconn.flush_requests().expect("Failed to set the enabled buttons");
+    }
 
     #[inline]
     pub fn enabled_buttons(&self) -> WindowButtons {
-        WindowButtons::all()
+        let shared_state_lock = self.shared_state_lock();
+        let mut buttons = shared_state_lock.enabled_buttons;
+        if !shared_state_lock.is_resizable {
+            buttons.remove(WindowButtons::MAXIMIZE);
+        }
+
+        // The window manager may not honor the Motif hints, so also drop the buttons for the
+        // actions it doesn't allow, when it says so.
+        if let Some(allowed_buttons) = shared_state_lock.allowed_buttons {
+            buttons &= allowed_buttons;
+        }
+
+        buttons
+    }
+
+    /// Refresh the buttons allowed by the window manager, on a change of `_NET_WM_ALLOWED_ACTIONS`.
+    pub(crate) fn update_allowed_buttons(&self) {
+        let atoms = self.xconn.atoms();
+        let allowed_actions = self
+            .xconn
+            .get_property::<xproto::Atom>(
+                self.xwindow,
+                atoms[_NET_WM_ALLOWED_ACTIONS],
+                xproto::Atom::from(xproto::AtomEnum::ATOM),
+            )
+            .unwrap_or_default();
+
+        let allowed_buttons = (!allowed_actions.is_empty()).then(|| {
+            let is_allowed = |action| allowed_actions.contains(&atoms[action]);
+            let mut buttons = WindowButtons::all();
+            if !is_allowed(_NET_WM_ACTION_MINIMIZE) {
+                buttons.remove(WindowButtons::MINIMIZE);
+            }
+            if !is_allowed(_NET_WM_ACTION_MAXIMIZE_HORZ)
+                || !is_allowed(_NET_WM_ACTION_MAXIMIZE_VERT)
+            {
+                buttons.remove(WindowButtons::MAXIMIZE);
+            }
+            if !is_allowed(_NET_WM_ACTION_CLOSE) {
+                buttons.remove(WindowButtons::CLOSE);
+            }
+            buttons
+        });
+
+        self.shared_state_lock().allowed_buttons = allowed_buttons;
     }
 
     #[allow(dead_code)]
@@ -1809,9 +2341,7 @@ impl UnownedWindow {
                     SelectedCursor::Named(icon),
                 );
 
-                #[allow(clippy::mutex_atomic)]
-                if SelectedCursor::Named(icon) != old_cursor && *self.cursor_visible.lock().unwrap()
-                {
+                if SelectedCursor::Named(icon) != old_cursor && self.is_cursor_shown() {
                     if let Err(err) = self.xconn.set_cursor_icon(self.xwindow, Some(icon)) {
                         tracing::error!("failed to set cursor icon: {err}");
                     }
@@ -1826,8 +2356,7 @@ impl UnownedWindow {
                     },
                 };
 
-                #[allow(clippy::mutex_atomic)]
-                if *self.cursor_visible.lock().unwrap() {
+                if self.is_cursor_shown() {
                     if let Err(err) = self.xconn.set_custom_cursor(self.xwindow, cursor) {
                         tracing::error!("failed to set window icon: {err}");
                     }
@@ -1840,11 +2369,6 @@ impl UnownedWindow {
 
     #[inline]
     pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), RequestError> {
-        // We don't support the locked cursor yet, so ignore it early on.
-        if mode == CursorGrabMode::Locked {
-            return Err(NotSupportedError::new("locked cursor is not implemented on X11").into());
-        }
-
         let mut grabbed_lock = self.cursor_grabbed_mode.lock().unwrap();
         if mode == *grabbed_lock {
             return Ok(());
@@ -1856,6 +2380,9 @@ impl UnownedWindow {
             .xcb_connection()
             .ungrab_pointer(x11rb::CURRENT_TIME)
             .expect_then_ignore_error("Failed to call `xcb_ungrab_pointer`");
+        if *grabbed_lock == CursorGrabMode::Locked {
+            self.unlock_cursor();
+        }
         *grabbed_lock = CursorGrabMode::None;
 
         let result = match mode {
@@ -1863,54 +2390,22 @@ impl UnownedWindow {
                 .xconn
                 .flush_requests()
                 .map_err(|err| RequestError::Os(os_error!(X11Error::Xlib(err)))),
-            CursorGrabMode::Confined => {
-                let result = self
-                    .xconn
-                    .xcb_connection()
-                    .grab_pointer(
-                        true as _,
-                        self.xwindow,
-                        xproto::EventMask::BUTTON_PRESS
-                            | xproto::EventMask::BUTTON_RELEASE
-                            | xproto::EventMask::ENTER_WINDOW
-                            | xproto::EventMask::LEAVE_WINDOW
-                            | xproto::EventMask::POINTER_MOTION
-                            | xproto::EventMask::POINTER_MOTION_HINT
-                            | xproto::EventMask::BUTTON1_MOTION
-                            | xproto::EventMask::BUTTON2_MOTION
-                            | xproto::EventMask::BUTTON3_MOTION
-                            | xproto::EventMask::BUTTON4_MOTION
-                            | xproto::EventMask::BUTTON5_MOTION
-                            | xproto::EventMask::KEYMAP_STATE,
-                        xproto::GrabMode::ASYNC,
-                        xproto::GrabMode::ASYNC,
-                        self.xwindow,
-                        0u32,
-                        x11rb::CURRENT_TIME,
-                    )
-                    .expect("Failed to call `grab_pointer`")
-                    .reply()
-                    .expect("Failed to receive reply from `grab_pointer`");
-
-                match result.status {
-                    xproto::GrabStatus::SUCCESS => Ok(()),
-                    xproto::GrabStatus::ALREADY_GRABBED => {
-                        Err("Cursor could not be confined: already confined by another client")
-                    },
-                    xproto::GrabStatus::INVALID_TIME => {
-                        Err("Cursor could not be confined: invalid time")
-                    },
-                    xproto::GrabStatus::NOT_VIEWABLE => {
-                        Err("Cursor could not be confined: confine location not viewable")
-                    },
-                    xproto::GrabStatus::FROZEN => {
-                        Err("Cursor could not be confined: frozen by another client")
-                    },
-                    _ => unreachable!(),
-                }
-                .map_err(|err| RequestError::Os(os_error!(err)))
-            },
-            CursorGrabMode::Locked => return Ok(()),
+            CursorGrabMode::Confined => self.grab_pointer(),
+            // The cursor is locked by confining it, and moving it back to the center of the
+            // window whenever it moves.
+            CursorGrabMode::Locked => self.grab_pointer().and_then(|_| {
+                self.lock_cursor().inspect_err(|_| {
+                    // Don't leave the cursor confined, nor hidden, when it can't be locked.
+                    self.unlock_cursor();
+                    self.xconn
+                        .xcb_connection()
+                        .ungrab_pointer(x11rb::CURRENT_TIME)
+                        .expect_then_ignore_error("Failed to call `xcb_ungrab_pointer`");
+                    if let Err(err) = self.xconn.flush_requests() {
+                        tracing::error!("failed to release the pointer grab: {err}");
+                    }
+                })
+            }),
         };
 
         if result.is_ok() {
@@ -1920,6 +2415,117 @@ impl UnownedWindow {
         result
     }
 
+    fn grab_pointer(&self) -> Result<(), RequestError> {
+        let result = self
+            .xconn
+            .xcb_connection()
+            .grab_pointer(
+                true as _,
+                self.xwindow,
+                xproto::EventMask::BUTTON_PRESS
+                    | xproto::EventMask::BUTTON_RELEASE
+                    | xproto::EventMask::ENTER_WINDOW
+                    | xproto::EventMask::LEAVE_WINDOW
+                    | xproto::EventMask::POINTER_MOTION
+                    | xproto::EventMask::POINTER_MOTION_HINT
+                    | xproto::EventMask::BUTTON1_MOTION
+                    | xproto::EventMask::BUTTON2_MOTION
+                    | xproto::EventMask::BUTTON3_MOTION
+                    | xproto::EventMask::BUTTON4_MOTION
+                    | xproto::EventMask::BUTTON5_MOTION
+                    | xproto::EventMask::KEYMAP_STATE,
+                xproto::GrabMode::ASYNC,
+                xproto::GrabMode::ASYNC,
+                self.xwindow,
+                0u32,
+                x11rb::CURRENT_TIME,
+            )
+            .expect("Failed to call `grab_pointer`")
+            .reply()
+            .expect("Failed to receive reply from `grab_pointer`");
+
+        match result.status {
+            xproto::GrabStatus::SUCCESS => Ok(()),
+            xproto::GrabStatus::ALREADY_GRABBED => {
+                Err("Cursor could not be confined: already confined by another client")
+            },
+            xproto::GrabStatus::INVALID_TIME => Err("Cursor could not be confined: invalid time"),
+            xproto::GrabStatus::NOT_VIEWABLE => {
+                Err("Cursor could not be confined: confine location not viewable")
+            },
+            xproto::GrabStatus::FROZEN => {
+                Err("Cursor could not be confined: frozen by another client")
+            },
+            _ => unreachable!(),
+        }
+        .map_err(|err| RequestError::Os(os_error!(err)))
+    }
+
+    /// Hide the confined cursor and move it to the center of the window.
+    fn lock_cursor(&self) -> Result<(), RequestError> {
+        let pointer = self
+            .xconn
+            .xcb_connection()
+            .query_pointer(self.xwindow)
+            .map_err(|err| os_error!(X11Error::from(err)))?
+            .reply()
+            .map_err(|err| os_error!(X11Error::from(err)))?;
+        *self.locked_cursor_origin.lock().unwrap() = Some((pointer.win_x, pointer.win_y));
+
+        if let Err(err) = self.xconn.set_cursor_icon(self.xwindow, None) {
+            tracing::error!("failed to hide the locked cursor: {err}");
+        }
+
+        let (x, y) = self.locked_cursor_center();
+        self.set_cursor_position_physical(x, y)
+    }
+
+    /// Move the cursor back to where it was locked, and show it again.
+    fn unlock_cursor(&self) {
+        if let Some((x, y)) = self.locked_cursor_origin.lock().unwrap().take() {
+            if let Err(err) = self.set_cursor_position_physical(x.into(), y.into()) {
+                tracing::error!("failed to restore the position of the locked cursor: {err}");
+            }
+        }
+
+        #[allow(clippy::mutex_atomic)]
+        let visible = *self.cursor_visible.lock().unwrap();
+        self.apply_cursor_visible(visible);
+    }
+
+    fn locked_cursor_center(&self) -> (i32, i32) {
+        let size = self.shared_state_lock().size;
+        let (width, height) = size.unwrap_or_else(|| self.surface_size_physical());
+        (width as i32 / 2, height as i32 / 2)
+    }
+
+    /// Move the locked cursor back to the center of the window after it moved to `position`.
+    ///
+    /// Returns whether the cursor is locked, in which case the motion shouldn't be reported as a
+    /// `PointerMoved` event.
+    pub(crate) fn recenter_locked_cursor(&self, position: (f64, f64)) -> bool {
+        if *self.cursor_grabbed_mode.lock().unwrap() != CursorGrabMode::Locked {
+            return false;
+        }
+
+        let (x, y) = self.locked_cursor_center();
+        if position != (x as f64, y as f64) {
+            if let Err(err) = self.set_cursor_position_physical(x, y) {
+                tracing::error!("failed to re-center the locked cursor: {err}");
+            }
+        }
+
+        true
+    }
+
+    /// Whether the cursor is shown over the window, it's hidden by the application or while it's
+    /// locked.
+    fn is_cursor_shown(&self) -> bool {
+        #[allow(clippy::mutex_atomic)]
+        let visible = *self.cursor_visible.lock().unwrap();
+        visible && *self.cursor_grabbed_mode.lock().unwrap() != CursorGrabMode::Locked
+    }
+
     #[inline]
     pub fn set_cursor_visible(&self, visible: bool) {
         #[allow(clippy::mutex_atomic)]
@@ -1927,10 +2533,18 @@ impl UnownedWindow {
         if visible == *visible_lock {
             return;
         }
-        let cursor =
-            if visible { Some((*self.selected_cursor.lock().unwrap()).clone()) } else { None };
         *visible_lock = visible;
         drop(visible_lock);
+
+        // The locked cursor stays hidden until it's unlocked.
+        if *self.cursor_grabbed_mode.lock().unwrap() != CursorGrabMode::Locked {
+            self.apply_cursor_visible(visible);
+        }
+    }
+
+    fn apply_cursor_visible(&self, visible: bool) {
+        let cursor =
+            if visible { Some((*self.selected_cursor.lock().unwrap()).clone()) } else { None };
         let result = match cursor {
             Some(SelectedCursor::Custom(cursor)) => {
                 self.xconn.set_custom_cursor(self.xwindow, &cursor)
@@ -1968,23 +2582,44 @@ impl UnownedWindow {
 
     #[inline]
     pub fn set_cursor_hittest(&self, hittest: bool) -> Result<(), RequestError> {
-        let mut rectangles: Vec<Rectangle> = Vec::new();
-        if hittest {
-            let size = self.surface_size();
-            rectangles.push(Rectangle {
-                x: 0,
-                y: 0,
-                width: size.width as u16,
-                height: size.height as u16,
-            })
-        }
-        let region = RegionWrapper::create_region(self.xconn.xcb_connection(), &rectangles)
-            .map_err(|_e| RequestError::Ignored)?;
-        self.xconn
-            .xcb_connection()
-            .xfixes_set_window_shape_region(self.xwindow, SK::INPUT, 0, 0, region.region())
-            .map_err(|_e| RequestError::Ignored)?;
         self.shared_state_lock().cursor_hittest = Some(hittest);
+        self.update_input_shape()
+    }
+
+    pub fn set_input_region(
+        &self,
+        region: Option<&[(Position, Size)]>,
+    ) -> Result<(), RequestError> {
+        self.shared_state_lock().input_region = region.map(<[_]>::to_vec);
+        self.update_input_shape()
+    }
+
+    /// Applies the input shape from the hittest and the input region.
+    pub(crate) fn update_input_shape(&self) -> Result<(), RequestError> {
+        let (hittest, input_region) = {
+            let shared_state_lock = self.shared_state_lock();
+            (
+                shared_state_lock.cursor_hittest.unwrap_or(true),
+                shared_state_lock.input_region.clone(),
+            )
+        };
+
+        let conn = self.xconn.xcb_connection();
+        let rectangles = match input_region {
+            _ if !hittest => Vec::new(),
+            Some(region) => region_to_rectangles(&region, self.scale_factor()),
+            None => {
+                // Removing the input shape restores the default, covering the whole window.
+                conn.xfixes_set_window_shape_region(self.xwindow, SK::INPUT, 0, 0, x11rb::NONE)
+                    .map_err(|_e| RequestError::Ignored)?;
+                return Ok(());
+            },
+        };
+
+        let region =
+            RegionWrapper::create_region(conn, &rectangles).map_err(|_e| RequestError::Ignored)?;
+        conn.xfixes_set_window_shape_region(self.xwindow, SK::INPUT, 0, 0, region.region())
+            .map_err(|_e| RequestError::Ignored)?;
         Ok(())
     }
 
@@ -1993,8 +2628,45 @@ impl UnownedWindow {
         self.drag_initiate(util::MOVERESIZE_MOVE)
     }
 
-    #[inline]
-    pub fn show_window_menu(&self, _position: Position) {}
+    /// Shows the window menu of the window manager.
+    pub fn show_window_menu(&self, position: Position) -> Result<(), RequestError> {
+        let atoms = self.xconn.atoms();
+        let message = atoms[_GTK_SHOW_WINDOW_MENU];
+        if !util::hint_is_supported(message) {
+            return Err(NotSupportedError::new(
+                "the window manager doesn't support `_GTK_SHOW_WINDOW_MENU`",
+            )
+            .into());
+        }
+
+        let position = position.to_physical::<i32>(self.scale_factor());
+        let window_position = self.inner_position_physical();
+
+        // The window manager grabs the pointer to show the menu.
+        let _grabbed_lock = self.release_pointer_grab()?;
+        self.xconn
+            .send_client_msg(
+                self.xwindow,
+                self.root,
+                message,
+                Some(
+                    xproto::EventMask::SUBSTRUCTURE_REDIRECT
+                        | xproto::EventMask::SUBSTRUCTURE_NOTIFY,
+                ),
+                [
+                    util::VIRTUAL_CORE_POINTER as u32,
+                    (window_position.0 + position.x) as u32,
+                    (window_position.1 + position.y) as u32,
+                    0,
+                    0,
+                ],
+            )
+            .map_err(|err| os_error!(err))?;
+
+        self.xconn.flush_requests().map_err(|err| os_error!(X11Error::Xlib(err)))?;
+
+        Ok(())
+    }
 
     /// Resizes the window while it is being dragged.
     pub fn drag_resize_window(&self, direction: ResizeDirection) -> Result<(), RequestError> {
@@ -2010,6 +2682,25 @@ impl UnownedWindow {
         })
     }
 
+    /// Releases the pointer grab, so the window manager can take it.
+    fn release_pointer_grab(&self) -> Result<MutexGuard<'_, CursorGrabMode>, RequestError> {
+        // we can't use `set_cursor_grab(false)` here because it doesn't run `XUngrabPointer`
+        // if the cursor isn't currently grabbed
+        let mut grabbed_lock = self.cursor_grabbed_mode.lock().unwrap();
+        self.xconn
+            .xcb_connection()
+            .ungrab_pointer(x11rb::CURRENT_TIME)
+            .map_err(|err| os_error!(X11Error::from(err)))?
+            .ignore_error();
+        if *grabbed_lock == CursorGrabMode::Locked {
+            self.unlock_cursor();
+        }
+        self.xconn.flush_requests().map_err(|err| os_error!(X11Error::Xlib(err)))?;
+        *grabbed_lock = CursorGrabMode::None;
+
+        Ok(grabbed_lock)
+    }
+
     /// Initiates a drag operation while the left mouse button is pressed.
     fn drag_initiate(&self, action: isize) -> Result<(), RequestError> {
         let pointer = self
@@ -2022,18 +2713,8 @@ impl UnownedWindow {
         let atoms = self.xconn.atoms();
         let message = atoms[_NET_WM_MOVERESIZE];
 
-        // we can't use `set_cursor_grab(false)` here because it doesn't run `XUngrabPointer`
-        // if the cursor isn't currently grabbed
-        let mut grabbed_lock = self.cursor_grabbed_mode.lock().unwrap();
-        self.xconn
-            .xcb_connection()
-            .ungrab_pointer(x11rb::CURRENT_TIME)
-            .map_err(|err| os_error!(X11Error::from(err)))?
-            .ignore_error();
-        self.xconn.flush_requests().map_err(|err| os_error!(X11Error::Xlib(err)))?;
-        *grabbed_lock = CursorGrabMode::None;
-
         // we keep the lock until we are done
+        let _grabbed_lock = self.release_pointer_grab()?;
         self.xconn
             .send_client_msg(
                 self.xwindow,
@@ -2083,6 +2764,20 @@ impl UnownedWindow {
     #[inline]
     pub fn set_ime_purpose(&self, _purpose: ImePurpose) {}
 
+    #[inline]
+    pub fn set_ime_surrounding_text(&self, text: &str, cursor: usize, _anchor: usize) {
+        if !text.is_char_boundary(cursor) {
+            warn!("`set_ime_surrounding_text` cursor is not on a char boundary");
+            return;
+        }
+
+        let _ = self.ime_sender.lock().unwrap().send(ImeRequest::SurroundingText(
+            self.xwindow as ffi::Window,
+            text.to_owned(),
+            cursor,
+        ));
+    }
+
     #[inline]
     pub fn focus_window(&self) {
         let atoms = self.xconn.atoms();
@@ -2168,8 +2863,54 @@ impl UnownedWindow {
         WindowId::from_raw(self.xwindow as _)
     }
 
-    pub(super) fn sync_counter_id(&self) -> Option<NonZeroU32> {
-        self.sync_counter_id
+    #[inline]
+    pub fn set_shortcuts_inhibited(&self, inhibited: bool) -> Result<(), RequestError> {
+        self.shared_state_lock().shortcuts_inhibited = inhibited;
+        // The keyboard grab is updated from the event loop to report its outcome.
+        self.shortcuts_inhibit_sender.send(self.id());
+        Ok(())
+    }
+
+    /// Grab or release the keyboard to match the requested shortcuts inhibition.
+    ///
+    /// The keyboard is only grabbed while the window has focus. Returns the new state of the
+    /// inhibition if it was changed or the grab was refused.
+    pub(crate) fn update_keyboard_grab(&self) -> Option<bool> {
+        let mut shared_state = self.shared_state_lock();
+        let grab = shared_state.shortcuts_inhibited && shared_state.has_focus;
+        if grab == shared_state.keyboard_grabbed {
+            return None;
+        }
+
+        if !grab {
+            self.xconn
+                .xcb_connection()
+                .ungrab_keyboard(x11rb::CURRENT_TIME)
+                .expect_then_ignore_error("Failed to call `xcb_ungrab_keyboard`");
+            shared_state.keyboard_grabbed = false;
+            return Some(false);
+        }
+
+        let status = self
+            .xconn
+            .xcb_connection()
+            .grab_keyboard(
+                true,
+                self.xwindow,
+                x11rb::CURRENT_TIME,
+                xproto::GrabMode::ASYNC,
+                xproto::GrabMode::ASYNC,
+            )
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+            .map(|reply| reply.status);
+
+        shared_state.keyboard_grabbed = status == Some(xproto::GrabStatus::SUCCESS);
+        if !shared_state.keyboard_grabbed {
+            tracing::warn!("Failed to grab the keyboard to inhibit shortcuts: {status:?}");
+        }
+
+        Some(shared_state.keyboard_grabbed)
     }
 
     #[inline]
@@ -2177,9 +2918,209 @@ impl UnownedWindow {
         self.redraw_sender.send(WindowId::from_raw(self.xwindow as _));
     }
 
+    /// Begins the frame on the extended sync counter.
     #[inline]
     pub fn pre_present_notify(&self) {
-        // TODO timer
+        let Some(counter) = self.extended_sync_counter_id else { return };
+        let mut frame_sync = self.frame_sync.lock().unwrap();
+
+        // The frame was already started.
+        if frame_sync.extended_value % 2 == 1 {
+            return;
+        }
+
+        // Complete the frame past the value requested by the window manager.
+        if let Some(value) = frame_sync.extended_request.take() {
+            frame_sync.extended_value = value + value % 2;
+        }
+
+        frame_sync.extended_value += 1;
+        self.set_sync_counter(counter, frame_sync.extended_value);
+
+        // The frame is presented through another connection.
+        self.xconn.flush_requests().expect("Failed to set the extended sync counter");
+    }
+
+    /// Stores the counter value requested with `_NET_WM_SYNC_REQUEST`, which is set once the
+    /// frame is done.
+    pub(crate) fn sync_request(&self, value: i64, extended: bool) {
+        let mut frame_sync = self.frame_sync.lock().unwrap();
+        if extended {
+            frame_sync.extended_request = Some(value);
+        } else {
+            frame_sync.basic_request = Some(value);
+        }
+    }
+
+    /// Completes the frame started with [`Self::pre_present_notify`], and acknowledges the
+    /// pending sync requests.
+    ///
+    /// Returns `true` when a counter was updated.
+    pub(crate) fn end_frame(&self) -> bool {
+        let mut frame_sync = self.frame_sync.lock().unwrap();
+        let mut updated = false;
+
+        if let Some((counter, value)) = self.sync_counter_id.zip(frame_sync.basic_request.take()) {
+            self.set_sync_counter(counter, value);
+            updated = true;
+        }
+
+        let Some(counter) = self.extended_sync_counter_id else { return updated };
+        if frame_sync.extended_value % 2 == 1 {
+            frame_sync.extended_value += 1;
+            self.set_sync_counter(counter, frame_sync.extended_value);
+            // Only wait for the compositor when it reports the drawn frames.
+            let reports_frames = util::is_composited()
+                && util::hint_is_supported(self.xconn.atoms()[_NET_WM_FRAME_DRAWN]);
+            frame_sync.awaiting_frame_drawn =
+                reports_frames.then(|| Instant::now() + FRAME_DRAWN_TIMEOUT);
+            updated = true;
+        } else if let Some(value) = frame_sync.extended_request.take() {
+            // Nothing was presented, so the current content is the complete frame.
+            frame_sync.extended_value = value + value % 2;
+            self.set_sync_counter(counter, frame_sync.extended_value);
+            updated = true;
+        }
+
+        updated
+    }
+
+    /// The compositor showed the last frame, reported with `_NET_WM_FRAME_DRAWN`.
+    pub(crate) fn frame_drawn(&self) {
+        let mut frame_sync = self.frame_sync.lock().unwrap();
+        frame_sync.awaiting_frame_drawn = None;
+        if mem::take(&mut frame_sync.redraw_deferred) {
+            self.request_redraw();
+        }
+    }
+
+    /// Whether the redraw must wait until the compositor showed the last frame.
+    pub(crate) fn defer_redraw(&self) -> bool {
+        let mut frame_sync = self.frame_sync.lock().unwrap();
+        let awaiting =
+            frame_sync.awaiting_frame_drawn.is_some_and(|deadline| Instant::now() < deadline);
+        frame_sync.redraw_deferred |= awaiting;
+        awaiting
+    }
+
+    /// When the deferred redraw is issued without waiting for the compositor anymore.
+    pub(crate) fn deferred_redraw_deadline(&self) -> Option<Instant> {
+        let frame_sync = self.frame_sync.lock().unwrap();
+        frame_sync.awaiting_frame_drawn.filter(|_| frame_sync.redraw_deferred)
+    }
+
+    /// Stops waiting for the compositor when it didn't report the last frame in time, like when
+    /// it was replaced or stopped.
+    pub(crate) fn frame_drawn_timeout(&self, now: Instant) {
+        if self.deferred_redraw_deadline().is_some_and(|deadline| deadline <= now) {
+            debug!("The compositor didn't report the last frame as drawn");
+            self.frame_drawn();
+        }
+    }
+
+    pub fn set_frame_presented_events(&self, enabled: bool) -> Result<(), RequestError> {
+        if self.xconn.present_opcode().is_none() {
+            return Err(NotSupportedError::new("the X server has no Present extension").into());
+        }
+
+        let mut presentation = self.frame_presentation.lock().unwrap();
+        if enabled == presentation.event_id.is_some() {
+            return Ok(());
+        }
+
+        let (event_id, event_mask) = match presentation.event_id {
+            // Selecting no events destroys the event context.
+            Some(event_id) => (event_id, present::EventMask::NO_EVENT),
+            None => (
+                leap!(self.xconn.xcb_connection().generate_id()),
+                present::EventMask::COMPLETE_NOTIFY,
+            ),
+        };
+
+        leap!(leap!(self.xconn.xcb_connection().present_select_input(
+            event_id,
+            self.xwindow,
+            event_mask
+        ))
+        .check());
+
+        presentation.event_id = enabled.then_some(event_id);
+        presentation.last_complete = None;
+        Ok(())
+    }
+
+    pub fn set_presentation_hint(&self, hint: PresentationHint) -> Result<(), RequestError> {
+        // Frames can only tear when they don't go through the compositor, which usually only
+        // unredirects the fullscreen windows.
+        let fullscreen = {
+            let mut shared_state = self.shared_state_lock();
+            shared_state.bypass_compositor = hint.allow_tearing
+                || matches!(hint.content_type, ContentType::Video | ContentType::Game);
+            shared_state.fullscreen.is_some()
+        };
+
+        self.set_bypass_compositor_hint(fullscreen).map_err(|err| os_error!(err))?.ignore_error();
+        self.xconn.flush_requests().map_err(|err| os_error!(X11Error::Xlib(err)))?;
+        Ok(())
+    }
+
+    /// Sets `_NET_WM_BYPASS_COMPOSITOR` from the presentation hint while the window is
+    /// fullscreen, and removes it otherwise, since some compositors stop compositing the whole
+    /// desktop while a window asks to bypass them.
+    fn set_bypass_compositor_hint(&self, fullscreen: bool) -> Result<VoidCookie<'_>, X11Error> {
+        let atom = self.xconn.atoms()[_NET_WM_BYPASS_COMPOSITOR];
+        if fullscreen && self.shared_state_lock().bypass_compositor {
+            // 1 asks to bypass the compositor.
+            self.xconn.change_property(
+                self.xwindow,
+                atom,
+                xproto::Atom::from(xproto::AtomEnum::CARDINAL),
+                xproto::PropMode::REPLACE,
+                &[1u32],
+            )
+        } else {
+            Ok(self.xconn.xcb_connection().delete_property(self.xwindow, atom)?)
+        }
+    }
+
+    /// Converts the `PresentCompleteNotify` event of a presented frame.
+    pub(crate) fn frame_presented(
+        &self,
+        event: &present::CompleteNotifyEvent,
+    ) -> Option<WindowEvent> {
+        let mut presentation = self.frame_presentation.lock().unwrap();
+        if presentation.event_id != Some(event.event) || event.kind != present::CompleteKind::PIXMAP
+        {
+            return None;
+        }
+
+        // The skipped frames were never shown.
+        if event.mode == present::CompleteMode::SKIP {
+            return None;
+        }
+
+        let refresh_interval =
+            presentation.last_complete.filter(|&(_, msc)| event.msc > msc).map(|(ust, msc)| {
+                Duration::from_micros(event.ust.saturating_sub(ust) / (event.msc - msc))
+            });
+        presentation.last_complete = Some((event.ust, event.msc));
+
+        let mut flags = PresentationFlags::empty();
+        flags.set(PresentationFlags::ZERO_COPY, event.mode == present::CompleteMode::FLIP);
+
+        Some(WindowEvent::FramePresented {
+            presented_at: Duration::from_micros(event.ust),
+            refresh_interval,
+            flags,
+        })
+    }
+
+    fn set_sync_counter(&self, counter: NonZeroU32, value: i64) {
+        let value = Int64 { hi: (value >> 32) as i32, lo: value as u32 };
+        self.xconn
+            .xcb_connection()
+            .sync_set_counter(counter.get(), value)
+            .expect_then_ignore_error("Failed to set XSync counter.");
     }
 
     #[inline]
@@ -2208,7 +3149,12 @@ impl UnownedWindow {
         None
     }
 
-    pub fn set_content_protected(&self, _protected: bool) {}
+    pub fn set_content_protected(&self, protected: bool) {
+        // NOTE: the X server has no way to exclude a window from being captured.
+        if protected {
+            warn!("content protection is not supported on X11");
+        }
+    }
 
     #[inline]
     pub fn has_focus(&self) -> bool {
@@ -2238,3 +3184,45 @@ fn cast_size_to_hint(size: Size, scale_factor: f64) -> (i32, i32) {
         Size::Logical(size) => size.to_physical::<i32>(scale_factor).into(),
     }
 }
+
+/// Convert the rectangles of a region to physical coordinates.
+fn region_to_rectangles(region: &[(Position, Size)], scale_factor: f64) -> Vec<Rectangle> {
+    region
+        .iter()
+        .map(|(position, size)| {
+            let position = position.to_physical::<i16>(scale_factor);
+            let size = size.to_physical::<u16>(scale_factor);
+            Rectangle { x: position.x, y: position.y, width: size.width, height: size.height }
+        })
+        .collect()
+}
+
+/// The core events selected on the windows.
+fn core_event_mask() -> xproto::EventMask {
+    use xproto::EventMask;
+
+    EventMask::EXPOSURE
+        | EventMask::STRUCTURE_NOTIFY
+        | EventMask::VISIBILITY_CHANGE
+        | EventMask::KEY_PRESS
+        | EventMask::KEY_RELEASE
+        | EventMask::KEYMAP_STATE
+        | EventMask::BUTTON_PRESS
+        | EventMask::BUTTON_RELEASE
+        | EventMask::POINTER_MOTION
+        | EventMask::PROPERTY_CHANGE
+}
+
+/// The XInput2 events selected on the windows.
+fn xinput_event_mask() -> xinput::XIEventMask {
+    xinput::XIEventMask::MOTION
+        | xinput::XIEventMask::BUTTON_PRESS
+        | xinput::XIEventMask::BUTTON_RELEASE
+        | xinput::XIEventMask::ENTER
+        | xinput::XIEventMask::LEAVE
+        | xinput::XIEventMask::FOCUS_IN
+        | xinput::XIEventMask::FOCUS_OUT
+        | xinput::XIEventMask::TOUCH_BEGIN
+        | xinput::XIEventMask::TOUCH_UPDATE
+        | xinput::XIEventMask::TOUCH_END
+}
@@ -1,13 +1,17 @@
 use std::collections::HashMap;
 use std::error::Error;
-use std::ffi::c_int;
-use std::sync::atomic::{AtomicU32, Ordering};
+use std::ffi::{c_int, c_void};
+use std::os::fd::AsRawFd;
+use std::ptr::NonNull;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::{Arc, Mutex, RwLock, RwLockReadGuard};
-use std::{fmt, ptr};
+use std::{fmt, io, ptr};
 
 use rwh_06::HasDisplayHandle;
 use winit_core::cursor::CursorIcon;
-use x11rb::connection::Connection;
+use x11rb::connection::{Connection, RequestConnection};
+use x11rb::errors::ConnectionError;
+use x11rb::protocol::present::{self, ConnectionExt as _};
 use x11rb::protocol::randr::ConnectionExt as _;
 use x11rb::protocol::render;
 use x11rb::protocol::xproto::{self, ConnectionExt};
@@ -17,6 +21,7 @@ use x11rb::xcb_ffi::XCBConnection;
 use super::atoms::Atoms;
 use super::ffi;
 use super::monitor::MonitorHandle;
+use super::util::cookie;
 use crate::event_loop::X11Error;
 
 /// A connection to an X server.
@@ -29,6 +34,17 @@ pub struct XConnection {
 
     pub display: *mut ffi::Display,
 
+    /// Whether the `Display` was opened by winit, and must be closed when dropped.
+    owns_display: bool,
+
+    /// Whether the connection to the X server was lost.
+    ///
+    /// Shared with the I/O error exit handler of Xlib.
+    disconnected: Arc<AtomicBool>,
+
+    /// `XSetIOErrorExitHandler`, when it was used to keep Xlib from terminating the process.
+    set_io_error_exit_handler: Option<XSetIOErrorExitHandler>,
+
     /// The manager for the XCB connection.
     ///
     /// The `Option` ensures that we can drop it before we close the `Display`.
@@ -55,6 +71,9 @@ pub struct XConnection {
     /// RandR version.
     randr_version: (u32, u32),
 
+    /// The major opcode of the Present extension.
+    present_opcode: Option<u8>,
+
     /// Atom for the XSettings screen.
     xsettings_screen: Option<xproto::Atom>,
 
@@ -78,23 +97,73 @@ unsafe impl Sync for XConnection {}
 pub type XErrorHandler =
     Option<unsafe extern "C" fn(*mut ffi::Display, *mut ffi::XErrorEvent) -> std::os::raw::c_int>;
 
+type XIOErrorExitHandler = Option<unsafe extern "C" fn(*mut ffi::Display, *mut c_void)>;
+
+/// `XSetIOErrorExitHandler`, available since libX11 1.7, which isn't exposed by `x11-dl`.
+type XSetIOErrorExitHandler =
+    unsafe extern "C" fn(*mut ffi::Display, XIOErrorExitHandler, *mut c_void);
+
+/// Called by Xlib when the connection to the X server is lost, instead of its default handler
+/// which terminates the process.
+unsafe extern "C" fn io_error_handler(_: *mut ffi::Display) -> c_int {
+    tracing::error!("Xlib lost the connection to the X server");
+    0
+}
+
+/// Called by Xlib after the I/O error handler, instead of terminating the process.
+unsafe extern "C" fn io_error_exit_handler(_: *mut ffi::Display, disconnected: *mut c_void) {
+    let disconnected = unsafe { &*(disconnected as *const AtomicBool) };
+    disconnected.store(true, Ordering::Relaxed);
+}
+
+/// Look up `XSetIOErrorExitHandler` in the loaded libX11.
+fn load_set_io_error_exit_handler() -> Option<XSetIOErrorExitHandler> {
+    let lib = unsafe { libc::dlopen(c"libX11.so.6".as_ptr(), libc::RTLD_LAZY | libc::RTLD_NOLOAD) };
+    if lib.is_null() {
+        return None;
+    }
+
+    // The library stays loaded by `x11-dl` after closing this handle.
+    let symbol = unsafe { libc::dlsym(lib, c"XSetIOErrorExitHandler".as_ptr()) };
+    unsafe { libc::dlclose(lib) };
+
+    NonNull::new(symbol).map(|symbol| unsafe {
+        std::mem::transmute::<*mut c_void, XSetIOErrorExitHandler>(symbol.as_ptr())
+    })
+}
+
 impl XConnection {
-    pub fn new(error_handler: XErrorHandler) -> Result<XConnection, XNotSupported> {
+    /// Open a connection, or use the `Display` of the host application when `foreign_display` is
+    /// set.
+    ///
+    /// # Safety
+    ///
+    /// `foreign_display` must point to a valid `Display`, which outlives the connection.
+    pub unsafe fn new(
+        error_handler: XErrorHandler,
+        foreign_display: Option<NonNull<ffi::Display>>,
+    ) -> Result<XConnection, XNotSupported> {
         // opening the libraries
         let xlib = ffi::Xlib::open()?;
         let xlib_xcb = ffi::Xlib_xcb::open()?;
         let xinput2 = ffi::XInput2::open()?;
 
-        unsafe { (xlib.XInitThreads)() };
+        // NOTE: This must be the first Xlib call, the host is responsible for it otherwise.
+        if foreign_display.is_none() {
+            unsafe { (xlib.XInitThreads)() };
+        }
         unsafe { (xlib.XSetErrorHandler)(error_handler) };
 
         // calling XOpenDisplay
-        let display = unsafe {
-            let display = (xlib.XOpenDisplay)(ptr::null());
-            if display.is_null() {
-                return Err(XNotSupported::XOpenDisplayFailed);
-            }
-            display
+        let display = match foreign_display {
+            Some(display) => display.as_ptr(),
+            None => unsafe {
+                let display = (xlib.XOpenDisplay)(ptr::null());
+                if display.is_null() {
+                    return Err(XNotSupported::XOpenDisplayFailed);
+                }
+                display
+            },
         };
 
         // Open the x11rb XCB connection.
@@ -111,6 +180,28 @@ impl XConnection {
             conn.map_err(|e| XNotSupported::XcbConversionError(Arc::new(WrapConnectError(e))))?
         };
 
+        // Report the lost connection instead of letting Xlib terminate the process. The host
+        // decides what happens with its own display.
+        let disconnected = Arc::new(AtomicBool::new(false));
+        let mut set_io_error_exit_handler = None;
+        if foreign_display.is_none() {
+            unsafe { (xlib.XSetIOErrorHandler)(Some(io_error_handler)) };
+            set_io_error_exit_handler = load_set_io_error_exit_handler();
+            match set_io_error_exit_handler {
+                Some(set_io_error_exit_handler) => unsafe {
+                    set_io_error_exit_handler(
+                        display,
+                        Some(io_error_exit_handler),
+                        Arc::as_ptr(&disconnected) as *mut c_void,
+                    )
+                },
+                None => tracing::warn!(
+                    "XSetIOErrorExitHandler is unavailable, Xlib terminates the process when the \
+                     connection to the X server is lost"
+                ),
+            }
+        }
+
         // Get the default screen.
         let default_screen = unsafe { (xlib.XDefaultScreen)(display) } as usize;
 
@@ -125,6 +216,8 @@ impl XConnection {
             .reply()
             .expect("failed to query XRandR version");
 
+        let present_opcode = unsafe { Self::new_present(&xlib, display, &xcb) };
+
         let xsettings_screen = Self::new_xsettings_screen(&xcb, default_screen);
         if xsettings_screen.is_none() {
             tracing::warn!("error setting XSETTINGS; Xft options won't reload automatically")
@@ -148,6 +241,9 @@ impl XConnection {
             xlib,
             xinput2,
             display,
+            owns_display: foreign_display.is_none(),
+            disconnected,
+            set_io_error_exit_handler,
             xcb: Some(xcb),
             atoms: Box::new(atoms),
             default_screen,
@@ -157,11 +253,33 @@ impl XConnection {
             database: RwLock::new(database),
             cursor_cache: Default::default(),
             randr_version: (randr_version.major_version, randr_version.minor_version),
+            present_opcode,
             render_formats: formats,
             xsettings_screen,
         })
     }
 
+    /// Query the Present extension, and let its events through Xlib.
+    unsafe fn new_present(
+        xlib: &ffi::Xlib,
+        display: *mut ffi::Display,
+        xcb: &XCBConnection,
+    ) -> Option<u8> {
+        let opcode = xcb.extension_information(present::X11_EXTENSION_NAME).ok()??.major_opcode;
+        xcb.present_query_version(1, 0).ok()?.reply().ok()?;
+
+        // Xlib drops the generic events of the extensions it doesn't know about.
+        unsafe {
+            (xlib.XESetWireToEventCookie)(
+                display,
+                opcode as c_int,
+                Some(cookie::wire_to_raw_cookie),
+            )
+        };
+
+        Some(opcode)
+    }
+
     fn new_xsettings_screen(xcb: &XCBConnection, default_screen: usize) -> Option<xproto::Atom> {
         // Fetch the _XSETTINGS_S[screen number] atom.
         let xsettings_screen = xcb
@@ -199,11 +317,65 @@ impl XConnection {
         }
     }
 
+    /// Check whether the connection to the X server is still alive.
+    ///
+    /// This must be called before reading from the connection with Xlib, which terminates the
+    /// process when the server goes away unless `XSetIOErrorExitHandler` is available.
+    pub fn check_connection(&self) -> Result<(), ConnectionError> {
+        if self.is_disconnected() {
+            return Err(ConnectionError::IoError(io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "Xlib lost the connection to the X server",
+            )));
+        }
+
+        if let Some(err) = self.xcb_connection().has_error() {
+            self.disconnected.store(true, Ordering::Relaxed);
+            return Err(err);
+        }
+
+        // Peek at the socket to find out whether the server closed its end.
+        let fd = self.xcb_connection().as_raw_fd();
+        let mut buf = [0u8; 1];
+        let read = unsafe {
+            libc::recv(fd, buf.as_mut_ptr().cast(), 1, libc::MSG_PEEK | libc::MSG_DONTWAIT)
+        };
+        let err = match read {
+            0 => io::Error::from(io::ErrorKind::UnexpectedEof),
+            -1 => io::Error::last_os_error(),
+            _ => return Ok(()),
+        };
+        if matches!(err.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::Interrupted) {
+            return Ok(());
+        }
+
+        self.disconnected.store(true, Ordering::Relaxed);
+        Err(ConnectionError::IoError(err))
+    }
+
+    /// Whether the `Display` was opened by winit, rather than passed by the host application.
+    #[inline]
+    pub fn owns_display(&self) -> bool {
+        self.owns_display
+    }
+
+    /// Whether the connection to the X server was found to be lost.
+    #[inline]
+    pub fn is_disconnected(&self) -> bool {
+        self.disconnected.load(Ordering::Relaxed)
+    }
+
     #[inline]
     pub fn randr_version(&self) -> (u32, u32) {
         self.randr_version
     }
 
+    /// The major opcode of the Present extension, if available.
+    #[inline]
+    pub fn present_opcode(&self) -> Option<u8> {
+        self.present_opcode
+    }
+
     /// Get the underlying XCB connection.
     #[inline]
     pub fn xcb_connection(&self) -> &XCBConnection {
@@ -319,7 +491,13 @@ impl Drop for XConnection {
     #[inline]
     fn drop(&mut self) {
         self.xcb = None;
-        unsafe { (self.xlib.XCloseDisplay)(self.display) };
+        // Closing the display syncs with the server, Xlib would terminate the process.
+        if self.owns_display && !self.is_disconnected() {
+            unsafe { (self.xlib.XCloseDisplay)(self.display) };
+        } else if let Some(set_io_error_exit_handler) = self.set_io_error_exit_handler {
+            // The handler points to `disconnected`, which is about to be freed.
+            unsafe { set_io_error_exit_handler(self.display, None, ptr::null_mut()) };
+        }
     }
 }
 